@@ -0,0 +1,88 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use statpack::utils::search::{
+    bisect_search_str_key,
+    prefix::{prefix_range, PrefixIndex, SortedPrefixIndex, TriePrefixIndex},
+};
+
+/// Sizes of the simulated player index
+const SIZES: [usize; 3] = [1_000, 100_000, 500_000];
+
+/// Generates `count` sorted, deterministic pseudo player names
+fn player_names(count: usize) -> Vec<String> {
+    const SYLLABLES: [&str; 16] = [
+        "ja", "mes", "le", "bron", "ke", "vin", "du", "rant", "lu", "ka", "do", "ncic", "ni", "ko",
+        "la", "jo",
+    ];
+    let mut seed = 0x2545_f491_4f6c_dd1du64;
+    let mut names: Vec<String> = (0..count)
+        .map(|_| {
+            let mut name = String::new();
+            for part in 0..5 {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                if part == 2 {
+                    name.push(' ');
+                }
+                name.push_str(SYLLABLES[(seed % SYLLABLES.len() as u64) as usize]);
+            }
+            name
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+fn bench_prefix_lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("prefix_lookup");
+
+    for size in SIZES {
+        let names = player_names(size);
+        let prefix = &names[size / 2][..6];
+        let sorted = SortedPrefixIndex::new(names.iter().map(String::as_str));
+        let trie = TriePrefixIndex::new(names.iter().map(String::as_str));
+
+        group.bench_with_input(
+            BenchmarkId::new("bisect_search_str_key", size),
+            &names,
+            |b, names| {
+                b.iter(|| {
+                    let left =
+                        bisect_search_str_key(names, black_box(prefix), false, String::as_str);
+                    let right =
+                        bisect_search_str_key(names, black_box(prefix), true, String::as_str);
+                    left.zip(right)
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("partition_point", size),
+            &names,
+            |b, names| {
+                b.iter(|| {
+                    let prefix = black_box(prefix);
+                    let left = names.partition_point(|name| name.as_str() < prefix);
+                    let right =
+                        left + names[left..].partition_point(|name| name.starts_with(prefix));
+                    (left, right)
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("prefix_range", size),
+            &names,
+            |b, names| b.iter(|| prefix_range(names, black_box(prefix), String::as_str)),
+        );
+        group.bench_function(BenchmarkId::new("sorted_prefix_index", size), |b| {
+            b.iter(|| sorted.prefix_range(black_box(prefix)))
+        });
+        group.bench_function(BenchmarkId::new("trie_prefix_index", size), |b| {
+            b.iter(|| trie.prefix_range(black_box(prefix)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_prefix_lookup);
+criterion_main!(benches);
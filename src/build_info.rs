@@ -0,0 +1,16 @@
+/// Version of the statpack crate
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Short hash of the git commit the binary was built from (`unknown` outside a git checkout)
+pub const GIT_COMMIT: &str = env!("STATPACK_GIT_COMMIT");
+/// UTC date (`YYYY-MM-DD`) the binary was built on
+pub const BUILD_DATE: &str = env!("STATPACK_BUILD_DATE");
+/// Version of the on-disk data schema the binary reads and writes
+pub const DATA_SCHEMA_VERSION: u32 = crate::store::migration::SCHEMA_VERSION;
+
+/// Returns the cargo features the binary was built with
+pub fn features() -> Vec<&'static str> {
+    env!("STATPACK_FEATURES")
+        .split(',')
+        .filter(|feature| !feature.is_empty())
+        .collect()
+}
@@ -1,8 +1,33 @@
 use super::{
-    command::{Command, _CLICommand},
-    option::CommandOption,
+    command::{_CLICommand, CLICommand, Command},
+    error::CLIError,
+    matches::ArgMatches,
+    option::{CommandOption, CommandOptionKwargs, CommandOptionType},
+    output::Format,
+    parser::parse_args,
 };
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    error::Error,
+    io::{self, Write},
+    sync::LazyLock,
+};
+
+/// Values accepted by the `--format` option
+static FORMATS: LazyLock<Vec<CommandOptionType>> = LazyLock::new(|| {
+    Format::NAMES
+        .iter()
+        .map(|name| CommandOptionType::Text(name.to_string()))
+        .collect()
+});
+static FORMAT_CHOICES: LazyLock<Vec<&'static CommandOptionType>> =
+    LazyLock::new(|| FORMATS.iter().collect());
+static FORMAT_KWARGS: LazyLock<CommandOptionKwargs<'static>> =
+    LazyLock::new(|| CommandOptionKwargs {
+        default: Some(&FORMATS[0]),
+        choices: Some(&FORMAT_CHOICES),
+        ..CommandOptionKwargs::default()
+    });
 
 /// Represents the top-level command-line interface (CLI)
 ///
@@ -11,18 +36,19 @@ use std::collections::HashSet;
 #[derive(Default, Debug)]
 pub struct CLI<'a> {
     name: &'a str,
+    version: Option<&'a str>,
     commands: Vec<Command<'a>>,
     options: Vec<CommandOption<'a>>,
     lookup: HashSet<String>,
 }
 impl<'a> _CLICommand<'a> for CLI<'a> {
-    fn commands(&self) -> &Vec<Command> {
+    fn commands(&self) -> &Vec<Command<'_>> {
         &self.commands
     }
     fn commands_mut(&mut self) -> &mut Vec<Command<'a>> {
         &mut self.commands
     }
-    fn options(&self) -> &Vec<CommandOption> {
+    fn options(&self) -> &Vec<CommandOption<'_>> {
         &self.options
     }
     fn options_mut(&mut self) -> &mut Vec<CommandOption<'a>> {
@@ -42,4 +68,176 @@ impl<'a> CLI<'a> {
             ..Default::default()
         }
     }
+    /// Returns the name of the CLI application
+    pub fn name(&self) -> &str {
+        self.name
+    }
+    /// Specifies the version of the CLI application and registers the `-V/--version` flag
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use statpack::cli_toolkit::cli::CLI;
+    /// let mut cli = CLI::new("statpack");
+    /// cli.version(env!("CARGO_PKG_VERSION"));
+    /// ```
+    pub fn version(&mut self, version: &'a str) -> &mut Self {
+        self.version = Some(version);
+        self.add_option_kwargs(
+            "-V",
+            "--version",
+            "Print version information",
+            &CommandOptionKwargs::FLAG,
+        )
+    }
+    /// Registers the global `--format` option picking how command output is rendered
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use statpack::cli_toolkit::cli::CLI;
+    /// let mut cli = CLI::new("statpack");
+    /// cli.format();
+    /// ```
+    pub fn format(&mut self) -> &mut Self {
+        self.create_option_kwargs(
+            "format",
+            "Output format: table, json, ndjson or csv",
+            &FORMAT_KWARGS,
+        )
+    }
+    /// Parses the command-line arguments (without the program name)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use statpack::cli_toolkit::cli::CLI;
+    /// # fn main() -> Result<(), statpack::cli_toolkit::error::CLIError> {
+    /// let cli = CLI::new("statpack");
+    /// let matches = cli.parse(std::env::args().skip(1))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse(&self, args: impl IntoIterator<Item = String>) -> Result<ArgMatches, CLIError> {
+        parse_args(&self.options, &self.commands, args).map(|(matches, _)| matches)
+    }
+    /// Parses the command-line arguments (without the program name), runs the handler
+    /// of the invoked command and prints its output to stdout.
+    pub fn run(&self, args: impl IntoIterator<Item = String>) -> Result<(), Box<dyn Error>> {
+        self.run_with(args, &mut io::stdout().lock())
+    }
+    /// Same as `run` but writes the rendered output to `writer`.
+    ///
+    /// `-V/--version` is answered directly when a version was specified, before any
+    /// subcommand is run.
+    pub fn run_with(
+        &self,
+        args: impl IntoIterator<Item = String>,
+        writer: &mut dyn Write,
+    ) -> Result<(), Box<dyn Error>> {
+        let (matches, command) = parse_args(&self.options, &self.commands, args)?;
+
+        if self.version.is_some() && matches.flag("version") {
+            writeln!(writer, "{}", self.version_line())?;
+            return Ok(());
+        }
+        let Some(handler) = command.and_then(|command| command.handler) else {
+            let commands = command.map_or(&self.commands, |command| &command.commands);
+            return Err(CLIError::MissingCommand(
+                commands.iter().map(|item| item.name.to_string()).collect(),
+            )
+            .into());
+        };
+        let format: Format = matches
+            .value("format")
+            .map(str::parse)
+            .transpose()?
+            .unwrap_or_default();
+
+        let output = handler(&matches)?;
+        write!(writer, "{}", format.render(output.as_ref()))?;
+        Ok(())
+    }
+    /// Returns the `name version` line printed by `--version`
+    pub fn version_line(&self) -> String {
+        format!("{} {}", self.name, self.version.unwrap_or("unknown"))
+    }
+}
+
+#[cfg(test)]
+mod run_tests {
+    use super::*;
+    use crate::cli_toolkit::output::Table;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    static RAN: AtomicBool = AtomicBool::new(false);
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_version_flag() {
+        let mut cli = CLI::new("statpack");
+        cli.version("1.2.3");
+
+        assert_eq!(cli.version_line(), "statpack 1.2.3");
+        assert!(cli.parse(args(&["-V"])).unwrap().flag("version"));
+        let mut out = vec![];
+        cli.run_with(args(&["--version"]), &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "statpack 1.2.3\n");
+
+        // Given after a subcommand, the version is printed instead of running it
+        cli.create_command("player")
+            .set_handler(|_| Err("ran player".into()));
+        let mut out = vec![];
+        cli.run_with(args(&["player", "--version"]), &mut out)
+            .unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "statpack 1.2.3\n");
+    }
+
+    #[test]
+    fn test_run_handler() {
+        let mut cli = CLI::new("statpack");
+        cli.create_command("ping").set_handler(|_| {
+            RAN.store(true, Ordering::SeqCst);
+            Ok(Box::new(Table::new(&[])))
+        });
+
+        cli.run_with(args(&["ping"]), &mut vec![]).unwrap();
+        assert!(RAN.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_run_format() {
+        let mut cli = CLI::new("statpack");
+        cli.format();
+        cli.create_command("pong").set_handler(|_| {
+            let mut table = Table::new(&["pong"]);
+            table.push(vec![true.into()]);
+            Ok(Box::new(table))
+        });
+        let mut table = vec![];
+        let mut json = vec![];
+        cli.run_with(args(&["pong"]), &mut table).unwrap();
+        cli.run_with(args(&["pong", "--format", "json"]), &mut json)
+            .unwrap();
+
+        assert_eq!(String::from_utf8(table).unwrap(), "pong\n----\ntrue\n");
+        assert_eq!(String::from_utf8(json).unwrap(), "[{\"pong\":true}]\n");
+        assert!(cli
+            .run_with(args(&["pong", "--format", "xml"]), &mut vec![])
+            .is_err());
+    }
+
+    #[test]
+    fn test_run_missing_command() {
+        let mut cli = CLI::new("statpack");
+        cli.create_command("ping");
+
+        assert_eq!(
+            cli.run(args(&[])).unwrap_err().to_string(),
+            "missing command, expected one of: ping"
+        );
+    }
 }
@@ -1,7 +1,15 @@
+use super::matches::ArgMatches;
 use super::option::{CommandOption, CommandOptionBuilder, CommandOptionKwargs};
+use super::output::Output;
 use super::parser::Parser;
-use std::borrow::BorrowMut;
 use std::collections::HashSet;
+use std::error::Error;
+
+/// The function a command runs with the parsed arguments once invoked.
+///
+/// Handlers return their data instead of printing it so the CLI can render it in the
+/// requested `--format`.
+pub type Handler = fn(&ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>>;
 
 /// The `_Command` trait for internal apis `Command` relies on
 pub(super) trait _CLICommand<'a> {
@@ -15,7 +23,7 @@ pub(super) trait _CLICommand<'a> {
         self.options_mut().push(arg);
     }
     /// Returns a immutable reference to the commands list
-    fn commands(&self) -> &Vec<Command>;
+    fn commands(&self) -> &Vec<Command<'_>>;
     /// Returns a mutable reference to the commands list
     fn commands_mut(&mut self) -> &mut Vec<Command<'a>>;
     /// Returns a immutable reference to the lookup table
@@ -25,7 +33,7 @@ pub(super) trait _CLICommand<'a> {
     /// Returns a mutable reference to the options list
     fn options_mut(&mut self) -> &mut Vec<CommandOption<'a>>;
     /// Returns a immutable reference to the options list
-    fn options(&self) -> &Vec<CommandOption>;
+    fn options(&self) -> &Vec<CommandOption<'_>>;
 }
 
 /// The `Command` trait provides methods for managing command-line commands.
@@ -41,8 +49,9 @@ pub trait CLICommand<'a>: _CLICommand<'a> + Parser {
     /// ## Example
     ///
     /// ```
+    /// # use statpack::cli_toolkit::{cli::CLI, command::CLICommand};
     /// let mut cli = CLI::new("test");
-    /// cli.create_command("test-subcommand", "Do the subcommand");
+    /// cli.create_command("test-subcommand");
     /// ```
     fn create_command(&mut self, name: &'a str) -> &mut Command<'a> {
         self.commands_mut().push(Command::new(name));
@@ -56,7 +65,8 @@ pub trait CLICommand<'a>: _CLICommand<'a> + Parser {
     /// # Example
     ///
     /// ```
-    /// let mut cli = CLI::new();
+    /// # use statpack::cli_toolkit::{cli::CLI, command::CLICommand};
+    /// let mut cli = CLI::new("test");
     /// cli.create_option("verbose", "Enable verbose mode");
     /// ```
     fn create_option(&mut self, option: &str, description: &str) -> &mut Self {
@@ -73,11 +83,15 @@ pub trait CLICommand<'a>: _CLICommand<'a> + Parser {
     /// # Example
     ///
     /// ```
-    /// let mut cli = CLI::new();
+    /// # use statpack::cli_toolkit::{
+    /// #     cli::CLI, command::CLICommand, option::CommandOptionKwargsBuilder,
+    /// # };
+    /// let mut cli = CLI::new("test");
     /// let kwargs = CommandOptionKwargsBuilder::new()
-    /// .set_deprecated()
-    /// .set_required();
-    /// cli.create_option("verbose", "Enable verbose mode", &kwargs);
+    ///     .set_deprecated()
+    ///     .set_required()
+    ///     .build();
+    /// cli.create_option_kwargs("verbose", "Enable verbose mode", &kwargs);
     /// ```
     fn create_option_kwargs(
         &mut self,
@@ -100,7 +114,8 @@ pub trait CLICommand<'a>: _CLICommand<'a> + Parser {
     /// # Example
     ///
     /// ```
-    /// let mut cli = CLI::new();
+    /// # use statpack::cli_toolkit::{cli::CLI, command::CLICommand};
+    /// let mut cli = CLI::new("test");
     /// cli.add_option("-v", "--verbose", "Enable verbose mode");
     /// ```
     fn add_option(&mut self, short: &str, long: &str, description: &str) -> &mut Self {
@@ -122,11 +137,15 @@ pub trait CLICommand<'a>: _CLICommand<'a> + Parser {
     /// # Example
     ///
     /// ```
-    /// let mut cli = CLI::new();
+    /// # use statpack::cli_toolkit::{
+    /// #     cli::CLI, command::CLICommand, option::CommandOptionKwargsBuilder,
+    /// # };
+    /// let mut cli = CLI::new("test");
     /// let kwargs = CommandOptionKwargsBuilder::new()
-    /// .set_deprecated()
-    /// .set_required();
-    /// cli.add_option("-v", "--verbose", "Enable verbose mode", &kwargs);
+    ///     .set_deprecated()
+    ///     .set_required()
+    ///     .build();
+    /// cli.add_option_kwargs("-v", "--verbose", "Enable verbose mode", &kwargs);
     /// ```
     fn add_option_kwargs(
         &mut self,
@@ -152,21 +171,23 @@ pub trait CLICommand<'a>: _CLICommand<'a> + Parser {
 }
 impl<'a, T: _CLICommand<'a>> CLICommand<'a> for T {}
 
+/// A command/subcommand of the CLI, with its own options and subcommands
 #[derive(Default, Debug)]
-pub(crate) struct Command<'a> {
+pub struct Command<'a> {
     pub(crate) name: &'a str,
     pub(crate) commands: Vec<Command<'a>>,
     pub(crate) options: Vec<CommandOption<'a>>,
     pub(crate) lookup: HashSet<String>,
+    pub(crate) handler: Option<Handler>,
 }
 impl<'a> _CLICommand<'a> for Command<'a> {
-    fn commands(&self) -> &Vec<Command> {
+    fn commands(&self) -> &Vec<Command<'_>> {
         &self.commands
     }
     fn commands_mut(&mut self) -> &mut Vec<Command<'a>> {
         &mut self.commands
     }
-    fn options(&self) -> &Vec<CommandOption> {
+    fn options(&self) -> &Vec<CommandOption<'_>> {
         &self.options
     }
     fn options_mut(&mut self) -> &mut Vec<CommandOption<'a>> {
@@ -186,6 +207,11 @@ impl<'a> Command<'a> {
             ..Default::default()
         }
     }
+    /// Specifies the function run when this command is the last one invoked
+    pub fn set_handler(&mut self, handler: Handler) -> &mut Self {
+        self.handler = Some(handler);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -199,13 +225,13 @@ pub(crate) mod mock {
         pub(crate) lookup: HashSet<String>,
     }
     impl<'a> _CLICommand<'a> for MockCommand<'a> {
-        fn commands(&self) -> &Vec<Command> {
+        fn commands(&self) -> &Vec<Command<'_>> {
             &self.commands
         }
         fn commands_mut(&mut self) -> &mut Vec<Command<'a>> {
             &mut self.commands
         }
-        fn options(&self) -> &Vec<CommandOption> {
+        fn options(&self) -> &Vec<CommandOption<'_>> {
             &self.options
         }
         fn options_mut(&mut self) -> &mut Vec<CommandOption<'a>> {
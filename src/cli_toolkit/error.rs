@@ -0,0 +1,86 @@
+use std::{error::Error, fmt};
+
+/// Represents the errors raised while parsing command-line arguments
+#[derive(Debug, Clone, PartialEq)]
+pub enum CLIError {
+    /// An option that no command in the invoked chain defines
+    UnknownOption {
+        option: String,
+        suggestions: Vec<String>,
+    },
+    /// An option was given fewer values than it expects
+    MissingValue { option: String, expected: usize },
+    /// A required option was not given
+    MissingRequired(String),
+    /// A value could not be converted to the expected type
+    InvalidValue {
+        option: String,
+        value: String,
+        expected: String,
+    },
+    /// A value is not one of the choices of the option
+    InvalidChoice {
+        option: String,
+        value: String,
+        choices: Vec<String>,
+    },
+    /// Options that exclude each other were given together
+    ConflictingOptions(String, String),
+    /// No runnable command was given, listing the available subcommands
+    MissingCommand(Vec<String>),
+}
+impl fmt::Display for CLIError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CLIError::UnknownOption {
+                option,
+                suggestions,
+            } => {
+                write!(f, "unknown option '{}'", option)?;
+                if !suggestions.is_empty() {
+                    write!(f, ", did you mean '{}'?", suggestions.join("', '"))?;
+                }
+                Ok(())
+            }
+            CLIError::MissingValue { option, expected } => {
+                write!(f, "option '{}' expects {} value(s)", option, expected)
+            }
+            CLIError::MissingRequired(option) => write!(f, "option '{}' is required", option),
+            CLIError::InvalidValue {
+                option,
+                value,
+                expected,
+            } => write!(
+                f,
+                "invalid value '{}' for '{}', expected {}",
+                value, option, expected
+            ),
+            CLIError::InvalidChoice {
+                option,
+                value,
+                choices,
+            } => write!(
+                f,
+                "invalid value '{}' for '{}', expected one of: {}",
+                value,
+                option,
+                choices.join(", ")
+            ),
+            CLIError::ConflictingOptions(first, second) => {
+                write!(
+                    f,
+                    "options '{}' and '{}' can not be combined",
+                    first, second
+                )
+            }
+            CLIError::MissingCommand(commands) => {
+                write!(
+                    f,
+                    "missing command, expected one of: {}",
+                    commands.join(", ")
+                )
+            }
+        }
+    }
+}
+impl Error for CLIError {}
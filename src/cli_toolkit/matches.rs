@@ -0,0 +1,84 @@
+use super::error::CLIError;
+use std::{collections::HashMap, str::FromStr};
+
+/// Represents the result of parsing command-line arguments against a `CLI`
+///
+/// Options are keyed by their long name without the leading dashes, so `--season 2024`
+/// is read back with `matches.value("season")`.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ArgMatches {
+    pub(super) commands: Vec<String>,
+    pub(super) values: HashMap<String, Vec<String>>,
+    pub(super) positionals: Vec<String>,
+}
+impl ArgMatches {
+    /// Returns the chain of invoked subcommands (e.g. `["player", "stats"]`)
+    pub fn commands(&self) -> &[String] {
+        &self.commands
+    }
+    /// Returns the arguments that are neither commands nor options
+    pub fn positionals(&self) -> &[String] {
+        &self.positionals
+    }
+    /// Indicates if the option was given (or has a default)
+    pub fn flag(&self, option: &str) -> bool {
+        self.values.contains_key(option)
+    }
+    /// Returns the first value of the option
+    pub fn value(&self, option: &str) -> Option<&str> {
+        self.values(option).first().map(String::as_str)
+    }
+    /// Returns every value given to the option
+    pub fn values(&self, option: &str) -> &[String] {
+        self.values.get(option).map_or(&[], Vec::as_slice)
+    }
+    /// Converts the first value of the option into `T`
+    ///
+    /// ## Returns
+    ///
+    /// `None` when the option was not given, or an `InvalidValue` error when it does not convert.
+    pub fn value_of<T: FromStr>(&self, option: &str) -> Result<Option<T>, CLIError> {
+        self.value(option)
+            .map(|value| {
+                value.parse().map_err(|_| CLIError::InvalidValue {
+                    option: format!("--{}", option),
+                    value: value.to_string(),
+                    expected: std::any::type_name::<T>().to_string(),
+                })
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod value_of_tests {
+    use super::*;
+
+    #[test]
+    fn test_value_of() {
+        let mut matches = ArgMatches::default();
+        matches
+            .values
+            .insert("last".to_string(), vec!["10".to_string()]);
+
+        assert_eq!(matches.value_of::<u32>("last"), Ok(Some(10)));
+        assert_eq!(matches.value_of::<u32>("season"), Ok(None));
+    }
+
+    #[test]
+    fn test_value_of_invalid() {
+        let mut matches = ArgMatches::default();
+        matches
+            .values
+            .insert("last".to_string(), vec!["ten".to_string()]);
+
+        assert_eq!(
+            matches.value_of::<u32>("last"),
+            Err(CLIError::InvalidValue {
+                option: "--last".to_string(),
+                value: "ten".to_string(),
+                expected: "u32".to_string(),
+            })
+        );
+    }
+}
@@ -2,5 +2,8 @@ pub mod cli;
 #[allow(private_bounds)]
 #[allow(private_interfaces)]
 pub mod command;
-mod option;
-mod parser;
+pub mod error;
+pub mod matches;
+pub mod option;
+pub mod output;
+pub mod parser;
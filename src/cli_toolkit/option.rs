@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, fmt};
 
 /// Represents additional configuration options for a command-line option.
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -11,8 +11,34 @@ pub struct CommandOptionKwargs<'a> {
     pub(super) choices: Option<&'a Vec<&'a CommandOptionType>>,
     // pub(super) prompt: Option<&'a str>,
     pub(super) confirmation: Option<&'a str>,
+    pub(super) kind: Option<&'a CommandOptionType>,
+}
+impl CommandOptionKwargs<'static> {
+    /// Kwargs of a boolean flag option that takes no value
+    pub const FLAG: Self = Self {
+        deprecated: false,
+        required: false,
+        nargs: None,
+        default: None,
+        flag: Some(true),
+        choices: None,
+        confirmation: None,
+        kind: None,
+    };
+    /// Kwargs of an option that must be given
+    pub const REQUIRED: Self = Self {
+        deprecated: false,
+        required: true,
+        nargs: None,
+        default: None,
+        flag: None,
+        choices: None,
+        confirmation: None,
+        kind: None,
+    };
 }
 impl<'a> CommandOptionKwargs<'a> {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         deprecated: bool,
         required: bool,
@@ -22,6 +48,7 @@ impl<'a> CommandOptionKwargs<'a> {
         choices: Option<&'a Vec<&'a CommandOptionType>>,
         // prompt: Option<&'a str>,
         confirmation: Option<&'a str>,
+        kind: Option<&'a CommandOptionType>,
     ) -> Self {
         Self {
             deprecated,
@@ -32,6 +59,7 @@ impl<'a> CommandOptionKwargs<'a> {
             choices,
             // prompt,
             confirmation,
+            kind,
         }
     }
 }
@@ -47,6 +75,7 @@ pub struct CommandOptionKwargsBuilder<'a> {
     choices: Option<&'a Vec<&'a CommandOptionType>>,
     // prompt: Option<&'a str>,
     confirmation: Option<&'a str>,
+    kind: Option<&'a CommandOptionType>,
 }
 impl<'a> CommandOptionKwargsBuilder<'a> {
     pub fn new() -> Self {
@@ -86,6 +115,13 @@ impl<'a> CommandOptionKwargsBuilder<'a> {
         self.choices = Some(choices);
         self
     }
+    /// Specifies a set of valid choices shared with other options, e.g. held in a static.
+    ///
+    /// Unlike `set_choices`, the default is not added, so the choices must already contain it.
+    pub fn set_shared_choices(&mut self, choices: &'a Vec<&'a CommandOptionType>) -> &mut Self {
+        self.choices = Some(choices);
+        self
+    }
     // /// Provides a prompt message for interactive user input.
     // pub fn set_prompt(&mut self, prompt: &'a str) -> &mut Self {
     //     self.prompt = Some(prompt);
@@ -96,7 +132,15 @@ impl<'a> CommandOptionKwargsBuilder<'a> {
         self.confirmation = Some(confirmation);
         self
     }
-    pub fn build(&self) -> CommandOptionKwargs {
+    /// Specifies the type values of the command-line option are validated against.
+    ///
+    /// Only the variant matters, e.g. `CommandOptionType::File(String::new())` accepts
+    /// paths of existing files. Defaults to the variant of the default value, if any.
+    pub fn set_kind(&mut self, kind: &'a CommandOptionType) -> &mut Self {
+        self.kind = Some(kind);
+        self
+    }
+    pub fn build(&self) -> CommandOptionKwargs<'a> {
         CommandOptionKwargs::new(
             self.deprecated,
             self.required,
@@ -106,6 +150,7 @@ impl<'a> CommandOptionKwargsBuilder<'a> {
             self.choices,
             // self.prompt,
             self.confirmation,
+            self.kind,
         )
     }
 }
@@ -118,6 +163,41 @@ pub enum CommandOptionType {
     Int(i32),
     Float(f64),
 }
+impl CommandOptionType {
+    /// Converts a raw command-line value into the same variant as `self`
+    ///
+    /// ## Returns
+    ///
+    /// The typed value, or a description of the expected type when the value does not convert.
+    pub fn parse_as(&self, value: &str) -> Result<CommandOptionType, String> {
+        match self {
+            CommandOptionType::Text(_) => Ok(CommandOptionType::Text(value.to_string())),
+            CommandOptionType::File(_) if std::path::Path::new(value).is_file() => {
+                Ok(CommandOptionType::File(value.to_string()))
+            }
+            CommandOptionType::File(_) => Err("an existing file".to_string()),
+            CommandOptionType::Int(_) => value
+                .parse()
+                .map(CommandOptionType::Int)
+                .map_err(|_| "an integer".to_string()),
+            CommandOptionType::Float(_) => value
+                .parse()
+                .map(CommandOptionType::Float)
+                .map_err(|_| "a number".to_string()),
+        }
+    }
+}
+impl fmt::Display for CommandOptionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandOptionType::Text(value) | CommandOptionType::File(value) => {
+                write!(f, "{}", value)
+            }
+            CommandOptionType::Int(value) => write!(f, "{}", value),
+            CommandOptionType::Float(value) => write!(f, "{}", value),
+        }
+    }
+}
 
 /// Represents a command-line option with associated metadata and optional parameters
 #[derive(Debug, PartialEq)]
@@ -141,6 +221,37 @@ impl<'a> CommandOption<'a> {
             kwargs,
         }
     }
+    /// Returns the long flag without its leading dashes, used to key parsed values
+    pub(super) fn name(&self) -> &str {
+        self.long.trim_start_matches('-')
+    }
+    /// Returns the number of values the option consumes (`0` for flags)
+    pub(super) fn nargs(&self) -> usize {
+        match self.kwargs {
+            Some(kwargs) if kwargs.flag == Some(true) => 0,
+            Some(kwargs) => kwargs.nargs.unwrap_or(1),
+            None => 1,
+        }
+    }
+    /// Indicates if the option has to be given
+    pub(super) fn required(&self) -> bool {
+        self.kwargs.is_some_and(|kwargs| kwargs.required)
+    }
+    /// Returns the value used when the option is not given
+    pub(super) fn default(&self) -> Option<&CommandOptionType> {
+        self.kwargs.and_then(|kwargs| kwargs.default)
+    }
+    /// Returns the type values are validated against, if any
+    pub(super) fn kind(&self) -> Option<&CommandOptionType> {
+        self.kwargs
+            .and_then(|kwargs| kwargs.kind.or(kwargs.default))
+    }
+    /// Returns the values the option is restricted to, if any
+    pub(super) fn choices(&self) -> Option<Vec<String>> {
+        self.kwargs
+            .and_then(|kwargs| kwargs.choices)
+            .map(|choices| choices.iter().map(|choice| choice.to_string()).collect())
+    }
 }
 
 /// A builder for creating a `CommandOption` instance
@@ -157,7 +268,7 @@ impl<'a> CommandOptionBuilder<'a> {
     }
     /// A `-` short option flag
     pub(super) fn gen_short(&mut self, option: &str, lookup_table: &HashSet<String>) -> &mut Self {
-        assert!(option.len() != 0, "option can not be empty");
+        assert!(!option.is_empty(), "option can not be empty");
 
         let mut short = String::from("-");
         let validate_gen = |string: String| {
@@ -310,3 +421,39 @@ mod gen_long_tests {
         assert_eq!(option.long, "--apple");
     }
 }
+
+#[cfg(test)]
+mod parse_as_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_as() {
+        assert_eq!(
+            CommandOptionType::Int(0).parse_as("-110"),
+            Ok(CommandOptionType::Int(-110))
+        );
+        assert_eq!(
+            CommandOptionType::Float(0.0).parse_as("2.5"),
+            Ok(CommandOptionType::Float(2.5))
+        );
+        assert_eq!(
+            CommandOptionType::Text(String::new()).parse_as("x"),
+            Ok(CommandOptionType::Text("x".to_string()))
+        );
+        assert!(CommandOptionType::Int(0).parse_as("2.5").is_err());
+    }
+
+    #[test]
+    fn test_parse_as_file() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let path = file.path().to_str().unwrap();
+
+        assert_eq!(
+            CommandOptionType::File(String::new()).parse_as(path),
+            Ok(CommandOptionType::File(path.to_string()))
+        );
+        assert!(CommandOptionType::File(String::new())
+            .parse_as("/does/not/exist.csv")
+            .is_err());
+    }
+}
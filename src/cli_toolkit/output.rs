@@ -0,0 +1,397 @@
+use super::error::CLIError;
+use serde_json::{Map, Value};
+use std::{fmt, str::FromStr};
+
+/// Represents a single value of a `Table` row
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    Text(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Null,
+}
+impl Cell {
+    /// Converts the cell to a JSON value, non-finite floats becoming `null`
+    fn to_json(&self) -> Value {
+        match self {
+            Cell::Text(value) => Value::from(value.as_str()),
+            Cell::Int(value) => Value::from(*value),
+            Cell::Float(value) => Value::from(*value),
+            Cell::Bool(value) => Value::from(*value),
+            Cell::Null => Value::Null,
+        }
+    }
+}
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cell::Text(value) => write!(f, "{}", value),
+            Cell::Int(value) => write!(f, "{}", value),
+            Cell::Float(value) => write!(f, "{}", value),
+            Cell::Bool(value) => write!(f, "{}", value),
+            Cell::Null => Ok(()),
+        }
+    }
+}
+impl From<&str> for Cell {
+    fn from(value: &str) -> Self {
+        Cell::Text(value.to_string())
+    }
+}
+impl From<String> for Cell {
+    fn from(value: String) -> Self {
+        Cell::Text(value)
+    }
+}
+impl From<i64> for Cell {
+    fn from(value: i64) -> Self {
+        Cell::Int(value)
+    }
+}
+impl From<u32> for Cell {
+    fn from(value: u32) -> Self {
+        Cell::Int(value.into())
+    }
+}
+impl From<f64> for Cell {
+    fn from(value: f64) -> Self {
+        Cell::Float(value)
+    }
+}
+impl From<bool> for Cell {
+    fn from(value: bool) -> Self {
+        Cell::Bool(value)
+    }
+}
+impl<T: Into<Cell>> From<Option<T>> for Cell {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Cell::Null, Into::into)
+    }
+}
+
+/// Represents tabular command output with named columns
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Table {
+    pub title: Option<String>,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Cell>>,
+}
+impl Table {
+    pub fn new(columns: &[&str]) -> Self {
+        Self {
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+    /// Specifies the title used to tell tables of the same output apart
+    pub fn set_title(&mut self, title: &str) -> &mut Self {
+        self.title = Some(title.to_string());
+        self
+    }
+    /// Appends a row, which must hold one cell per column
+    pub fn push(&mut self, row: Vec<Cell>) -> &mut Self {
+        assert_eq!(
+            row.len(),
+            self.columns.len(),
+            "row length must match the columns"
+        );
+        self.rows.push(row);
+        self
+    }
+}
+
+/// The `Output` trait is implemented by everything a command hands back to the CLI.
+///
+/// Commands return data instead of printing it so that the same result can be rendered
+/// in any `Format` picked with the global `--format` option.
+pub trait Output {
+    /// Returns the tables making up the output
+    fn tables(&self) -> Vec<Table>;
+}
+impl Output for Table {
+    fn tables(&self) -> Vec<Table> {
+        vec![self.clone()]
+    }
+}
+impl Output for Vec<Table> {
+    fn tables(&self) -> Vec<Table> {
+        self.clone()
+    }
+}
+
+/// Represents the formats an `Output` can be rendered in
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// Aligned columns for people
+    #[default]
+    Table,
+    /// A JSON array of row objects (an object of arrays keyed by title for several tables)
+    Json,
+    /// One JSON row object per line, wrapped as `{"table": <title>, "row": <row>}` for
+    /// titled tables
+    Ndjson,
+    /// Comma-separated values with a header line per table
+    Csv,
+}
+impl Format {
+    /// Names accepted by the `--format` option
+    pub const NAMES: [&'static str; 4] = ["table", "json", "ndjson", "csv"];
+
+    /// Renders the output in this format
+    pub fn render(&self, output: &dyn Output) -> String {
+        let tables = output.tables();
+        match self {
+            Format::Table => tables
+                .iter()
+                .map(render_table)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Format::Json => render_json(&tables),
+            Format::Ndjson => tables
+                .iter()
+                .flat_map(|table| {
+                    table.rows.iter().map(move |row| {
+                        let object = json_object(&table.columns, row);
+                        // Nesting the row keeps the title apart from its columns
+                        let record = match &table.title {
+                            Some(title) => {
+                                let mut record = Map::new();
+                                record.insert("table".to_string(), Value::from(title.as_str()));
+                                record.insert("row".to_string(), object);
+                                Value::Object(record)
+                            }
+                            None => object,
+                        };
+                        record.to_string() + "\n"
+                    })
+                })
+                .collect(),
+            Format::Csv => tables.iter().map(render_csv).collect::<Vec<_>>().join("\n"),
+        }
+    }
+}
+impl FromStr for Format {
+    type Err = CLIError;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "ndjson" => Ok(Format::Ndjson),
+            "csv" => Ok(Format::Csv),
+            _ => Err(CLIError::InvalidChoice {
+                option: "--format".to_string(),
+                value: format.to_string(),
+                choices: Format::NAMES.iter().map(|name| name.to_string()).collect(),
+            }),
+        }
+    }
+}
+
+/// Builds the JSON object of a row, keyed by column
+fn json_object(columns: &[String], row: &[Cell]) -> Value {
+    Value::Object(
+        columns
+            .iter()
+            .zip(row)
+            .map(|(column, cell)| (column.clone(), cell.to_json()))
+            .collect(),
+    )
+}
+
+fn render_json(tables: &[Table]) -> String {
+    let array = |table: &Table| {
+        Value::Array(
+            table
+                .rows
+                .iter()
+                .map(|row| json_object(&table.columns, row))
+                .collect(),
+        )
+    };
+
+    match tables {
+        [table] if table.title.is_none() => array(table).to_string() + "\n",
+        tables => {
+            let mut object = Map::new();
+            for (index, table) in tables.iter().enumerate() {
+                let mut title = table.title.clone().unwrap_or_else(|| index.to_string());
+                // Tables sharing a title are told apart by position rather than overwritten
+                if object.contains_key(&title) {
+                    title = format!("{}:{}", title, index);
+                }
+                object.insert(title, array(table));
+            }
+            Value::Object(object).to_string() + "\n"
+        }
+    }
+}
+
+fn render_csv(table: &Table) -> String {
+    let escape = |value: String| {
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value
+        }
+    };
+    let mut lines = vec![table
+        .columns
+        .iter()
+        .map(|column| escape(column.clone()))
+        .collect::<Vec<_>>()
+        .join(",")];
+    for row in &table.rows {
+        lines.push(
+            row.iter()
+                .map(|cell| escape(cell.to_string()))
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+    }
+    lines.join("\n") + "\n"
+}
+
+fn render_table(table: &Table) -> String {
+    let cells: Vec<Vec<String>> = table
+        .rows
+        .iter()
+        .map(|row| row.iter().map(Cell::to_string).collect())
+        .collect();
+    let widths: Vec<usize> = table
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            cells
+                .iter()
+                .map(|row| row[index].chars().count())
+                .chain([column.chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |values: Vec<String>| values.join("  ").trim_end().to_string();
+
+    let mut lines = vec![];
+    if let Some(title) = &table.title {
+        lines.push(title.clone());
+    }
+    lines.push(line(
+        table
+            .columns
+            .iter()
+            .zip(&widths)
+            .map(|(column, width)| format!("{:<width$}", column, width = width))
+            .collect(),
+    ));
+    lines.push(line(
+        widths.iter().map(|width| "-".repeat(*width)).collect(),
+    ));
+    for (row, values) in table.rows.iter().zip(cells) {
+        lines.push(line(
+            row.iter()
+                .zip(values)
+                .zip(&widths)
+                .map(|((cell, value), width)| match cell {
+                    Cell::Int(_) | Cell::Float(_) => format!("{:>width$}", value, width = width),
+                    _ => format!("{:<width$}", value, width = width),
+                })
+                .collect(),
+        ));
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod render_tests {
+    use super::*;
+
+    fn players() -> Table {
+        let mut table = Table::new(&["player", "pts", "note"]);
+        table
+            .push(vec!["LeBron James".into(), 27.5.into(), Cell::Null])
+            .push(vec!["Luka".into(), 33i64.into(), "says \"hi\", ok".into()]);
+        table
+    }
+
+    #[test]
+    fn test_render_table() {
+        assert_eq!(
+            Format::Table.render(&players()),
+            "player        pts   note\n\
+             ------------  ----  -------------\n\
+             LeBron James  27.5\n\
+             Luka            33  says \"hi\", ok\n"
+        );
+    }
+
+    #[test]
+    fn test_render_json() {
+        assert_eq!(
+            Format::Json.render(&players()),
+            "[{\"player\":\"LeBron James\",\"pts\":27.5,\"note\":null},\
+             {\"player\":\"Luka\",\"pts\":33,\"note\":\"says \\\"hi\\\", ok\"}]\n"
+        );
+    }
+
+    #[test]
+    fn test_render_json_titled_tables() {
+        let mut totals = Table::new(&["pts"]);
+        totals.set_title("totals").push(vec![60.5.into()]);
+        let mut games = players();
+        games.set_title("games");
+
+        assert!(Format::Json
+            .render(&vec![games, totals.clone()])
+            .ends_with(",\"totals\":[{\"pts\":60.5}]}\n"));
+        let mut again = Table::new(&["pts"]);
+        again.set_title("totals").push(vec![f64::NAN.into()]);
+        assert!(Format::Json
+            .render(&vec![totals, again])
+            .ends_with(",\"totals:1\":[{\"pts\":null}]}\n"));
+    }
+
+    #[test]
+    fn test_render_ndjson() {
+        let mut table = Table::new(&["pts"]);
+        table
+            .set_title("totals")
+            .push(vec![1i64.into()])
+            .push(vec![2i64.into()]);
+
+        assert_eq!(
+            Format::Ndjson.render(&table),
+            "{\"table\":\"totals\",\"row\":{\"pts\":1}}\n\
+             {\"table\":\"totals\",\"row\":{\"pts\":2}}\n"
+        );
+
+        let mut table = Table::new(&["table", "pts"]);
+        table.push(vec!["totals".into(), 1i64.into()]);
+        assert_eq!(
+            Format::Ndjson.render(&table),
+            "{\"table\":\"totals\",\"pts\":1}\n"
+        );
+    }
+
+    #[test]
+    fn test_render_csv() {
+        assert_eq!(
+            Format::Csv.render(&players()),
+            "player,pts,note\nLeBron James,27.5,\nLuka,33,\"says \"\"hi\"\", ok\"\n"
+        );
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("ndjson".parse(), Ok(Format::Ndjson));
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "row length must match the columns")]
+    fn test_push_mismatched_row() {
+        Table::new(&["pts"]).push(vec![]);
+    }
+}
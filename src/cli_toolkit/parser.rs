@@ -1,5 +1,10 @@
-use super::{command::CLICommand, option::CommandOption};
-use crate::utils::search::bisect_search_str_key;
+use super::{
+    command::{CLICommand, Command},
+    error::CLIError,
+    matches::ArgMatches,
+    option::CommandOption,
+};
+use crate::utils::search::{fuzzy::FuzzyMatcher, prefix::prefix_range};
 
 /// The `Parser` trait provides methods for parsing command-line commands associated with a specific command implementation.
 /// Implementations of this trait are responsible for allowing the command to handle command-line arguments efficiently.
@@ -13,47 +18,223 @@ pub trait Parser {
     /// ## Examples
     ///
     /// ```
-    /// let mut command = MockCommand::default();
-    /// command.create_option("verbose", "Enable verbose mode");
-    /// command.create_option("version", "Display version information");
+    /// # use statpack::cli_toolkit::{cli::CLI, command::CLICommand, parser::Parser};
+    /// let mut cli = CLI::new("statpack");
+    /// cli.create_option("verbose", "Enable verbose mode");
+    /// cli.create_option("version", "Display version information");
+    /// cli.sort();
     ///
-    /// let matches = command.search_options("-v");
+    /// let matches = cli.search_options("--ver");
     /// assert_eq!(matches, vec!["--verbose", "--version"]);
     /// ```
     fn search_options(&self, option: &str) -> Vec<String>;
-    /// Sort internal options list
+    /// Searches for the specified subcommand within the internal commands list.
+    ///
+    /// ## Returns
+    ///
+    /// A vector of strings containing the names of subcommands that match the given command string.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use statpack::cli_toolkit::{cli::CLI, command::CLICommand, parser::Parser};
+    /// let mut cli = CLI::new("statpack");
+    /// cli.create_command("player");
+    /// cli.create_command("players");
+    /// cli.sort();
+    ///
+    /// let matches = cli.search_commands("play");
+    /// assert_eq!(matches, vec!["player", "players"]);
+    /// ```
+    fn search_commands(&self, command: &str) -> Vec<String>;
+    /// Suggests options that closely resemble a mistyped option.
+    ///
+    /// ## Returns
+    ///
+    /// A vector of strings containing the long names of options ranked from the closest match.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use statpack::cli_toolkit::{cli::CLI, command::CLICommand, parser::Parser};
+    /// let mut cli = CLI::new("statpack");
+    /// cli.create_option("season", "Season to query");
+    ///
+    /// let suggestions = cli.suggest_options("--seasn");
+    /// assert_eq!(suggestions, vec!["--season"]);
+    /// ```
+    fn suggest_options(&self, option: &str) -> Vec<String>;
+    /// Sort internal options and commands lists
     fn sort(&mut self);
 }
 impl<'a, T: CLICommand<'a>> Parser for T {
     fn search_options(&self, option: &str) -> Vec<String> {
-        let mut find = vec![];
         let options = self.options();
+        if option.starts_with("--") {
+            prefix_range(options, option, |item| &item.long).map_or(vec![], |range| {
+                options[range]
+                    .iter()
+                    .map(|item| item.long.clone())
+                    .collect()
+            })
+        } else if option.starts_with('-') {
+            // Options are sorted by long name, so short names are scanned instead
+            options
+                .iter()
+                .filter(|item| item.short.starts_with(option))
+                .map(|item| item.long.clone())
+                .collect()
+        } else {
+            vec![]
+        }
+    }
+    fn search_commands(&self, command: &str) -> Vec<String> {
+        let commands = self.commands();
 
-        // Search range if multiple options
-        let left_index;
-        let right_index;
-        let key_extractor = |item: &CommandOption, search_key: &str| {
-            if search_key.starts_with("--") {
-                item.long.clone()
-            } else if search_key.starts_with("-") {
-                item.short.clone()
-            } else {
-                "".to_string()
-            }
-        };
-        left_index = bisect_search_str_key(self.options(), option, false, key_extractor);
-        right_index = bisect_search_str_key(self.options(), option, true, key_extractor);
+        prefix_range(commands, command, |item| item.name).map_or(vec![], |range| {
+            commands[range]
+                .iter()
+                .map(|item| item.name.to_string())
+                .collect()
+        })
+    }
+    fn suggest_options(&self, option: &str) -> Vec<String> {
+        FuzzyMatcher::new()
+            .set_limit(3)
+            .rank(option, self.options().iter().map(|item| item.long.as_str()))
+            .into_iter()
+            .map(|found| found.key.to_string())
+            .collect()
+    }
+    fn sort(&mut self) {
+        self.options_mut().sort_by(|a, b| a.long.cmp(&b.long));
+        self.commands_mut().sort_by(|a, b| a.name.cmp(b.name));
+    }
+}
 
-        if left_index != -1 && right_index != -1 {
-            for index in left_index..right_index + 1 {
-                find.push(options[index as usize].long.clone());
+/// Parses `args` against the top-level `options` and `commands` of a CLI.
+///
+/// Subcommands are matched by name until the first positional argument, and options of
+/// every command in the invoked chain are accepted, so top-level options act as globals.
+///
+/// ## Returns
+///
+/// The parsed arguments along with the innermost invoked command, if any.
+pub(super) fn parse_args<'c>(
+    options: &'c [CommandOption<'c>],
+    commands: &'c [Command<'c>],
+    args: impl IntoIterator<Item = String>,
+) -> Result<(ArgMatches, Option<&'c Command<'c>>), CLIError> {
+    let mut matches = ArgMatches::default();
+    let mut chain: Vec<&[CommandOption]> = vec![options];
+    let mut subcommands = commands;
+    let mut command = None;
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            matches.positionals.extend(args.by_ref());
+            break;
+        }
+        // Negative numbers are values, not options
+        if arg.len() > 1 && arg.starts_with('-') && arg.parse::<f64>().is_err() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let option = chain
+                .iter()
+                .rev()
+                .flat_map(|options| options.iter())
+                .find(|option| {
+                    option.long == flag || (!option.short.is_empty() && option.short == flag)
+                })
+                .ok_or_else(|| CLIError::UnknownOption {
+                    option: flag.to_string(),
+                    suggestions: suggest(&chain, flag),
+                })?;
+            let nargs = option.nargs();
+            let mut values: Vec<String> = inline.into_iter().collect();
+            while values.len() < nargs {
+                match args.next() {
+                    Some(value) => values.push(value),
+                    None => break,
+                }
+            }
+            if values.len() != nargs {
+                return Err(CLIError::MissingValue {
+                    option: option.long.clone(),
+                    expected: nargs,
+                });
+            }
+            if let Some(kind) = option.kind() {
+                for value in &values {
+                    kind.parse_as(value)
+                        .map_err(|expected| CLIError::InvalidValue {
+                            option: option.long.clone(),
+                            value: value.clone(),
+                            expected,
+                        })?;
+                }
+            }
+            if let Some(choices) = option.choices() {
+                if let Some(value) = values.iter().find(|value| !choices.contains(value)) {
+                    return Err(CLIError::InvalidChoice {
+                        option: option.long.clone(),
+                        value: value.clone(),
+                        choices,
+                    });
+                }
+            }
+            matches
+                .values
+                .entry(option.name().to_string())
+                .or_default()
+                .extend(values);
+            continue;
+        }
+        if matches.positionals.is_empty() {
+            if let Some(found) = subcommands.iter().find(|item| item.name == arg) {
+                matches.commands.push(arg);
+                chain.push(&found.options);
+                subcommands = &found.commands;
+                command = Some(found);
+                continue;
             }
         }
-        find
+        matches.positionals.push(arg);
     }
-    fn sort(&mut self) {
-        self.options_mut().sort_by(|a, b| a.long.cmp(&b.long));
+
+    for option in chain.iter().flat_map(|options| options.iter()) {
+        if matches.values.contains_key(option.name()) {
+            continue;
+        }
+        if let Some(default) = option.default() {
+            matches
+                .values
+                .insert(option.name().to_string(), vec![default.to_string()]);
+        } else if option.required() {
+            return Err(CLIError::MissingRequired(option.long.clone()));
+        }
     }
+
+    Ok((matches, command))
+}
+
+/// Suggests the closest options of the invoked command chain for a mistyped flag
+fn suggest(chain: &[&[CommandOption]], flag: &str) -> Vec<String> {
+    FuzzyMatcher::new()
+        .set_limit(3)
+        .rank(
+            flag,
+            chain
+                .iter()
+                .flat_map(|options| options.iter())
+                .map(|option| option.long.as_str()),
+        )
+        .into_iter()
+        .map(|found| found.key.to_string())
+        .collect()
 }
 
 #[cfg(test)]
@@ -73,6 +254,20 @@ mod search_options_tests {
         assert_eq!(result, vec!["--apple"]);
     }
 
+    #[test]
+    fn test_search_options_short_flag() {
+        let mut command = MockCommand::default();
+        command
+            .add_option("-z", "--apple", "Description for Apple option")
+            .add_option("-b", "--banana", "Description for Banana option")
+            .add_option("-a", "--zucchini", "Description for Zucchini option")
+            .sort();
+
+        assert_eq!(command.search_options("-a"), vec!["--zucchini"]);
+        assert_eq!(command.search_options("-z"), vec!["--apple"]);
+        assert!(command.search_options("-c").is_empty());
+    }
+
     #[test]
     fn test_search_options_multiple_options() {
         let mut command = MockCommand::default();
@@ -99,3 +294,207 @@ mod search_options_tests {
         assert!(result.is_empty());
     }
 }
+
+#[cfg(test)]
+mod search_commands_tests {
+    use super::*;
+    use crate::cli_toolkit::command::mock::MockCommand;
+
+    #[test]
+    fn test_search_commands_multiple_commands() {
+        let mut command = MockCommand::default();
+        command.create_command("team");
+        command.create_command("player");
+        command.create_command("players");
+        command.sort();
+        let result = command.search_commands("play");
+
+        assert_eq!(result, vec!["player", "players"]);
+    }
+
+    #[test]
+    fn test_search_commands_command_not_found() {
+        let mut command = MockCommand::default();
+        command.create_command("team");
+        command.sort();
+        let result = command.search_commands("odds");
+
+        assert!(result.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod suggest_options_tests {
+    use super::*;
+    use crate::cli_toolkit::command::mock::MockCommand;
+
+    #[test]
+    fn test_suggest_options_typo() {
+        let mut command = MockCommand::default();
+        command
+            .add_option("-s", "--season", "mock")
+            .add_option("-st", "--stat", "mock")
+            .add_option("-v", "--vs", "mock");
+        let result = command.suggest_options("--seasn");
+
+        assert_eq!(result, vec!["--season"]);
+    }
+
+    #[test]
+    fn test_suggest_options_no_suggestion() {
+        let mut command = MockCommand::default();
+        command.add_option("-s", "--season", "mock");
+        let result = command.suggest_options("--bankroll");
+
+        assert!(result.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod parse_args_tests {
+    use super::*;
+    use crate::cli_toolkit::{
+        command::mock::MockCommand,
+        option::{CommandOptionKwargs, CommandOptionKwargsBuilder, CommandOptionType},
+    };
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args_subcommands_and_values() {
+        let mut command = MockCommand::default();
+        command.create_option_kwargs("verbose", "mock", &CommandOptionKwargs::FLAG);
+        command
+            .create_command("player")
+            .create_command("stats")
+            .create_option("season", "mock");
+        let (matches, found) = parse_args(
+            &command.options,
+            &command.commands,
+            args(&["player", "stats", "lebron", "--season=2024", "-v"]),
+        )
+        .unwrap();
+
+        assert_eq!(found.unwrap().name, "stats");
+        assert_eq!(matches.commands(), ["player", "stats"]);
+        assert_eq!(matches.positionals(), ["lebron"]);
+        assert_eq!(matches.value("season"), Some("2024"));
+        assert!(matches.flag("verbose"));
+    }
+
+    #[test]
+    fn test_parse_args_nargs_and_negative_values() {
+        let mut command = MockCommand::default();
+        let kwargs = CommandOptionKwargsBuilder::new().set_nargs(2).build();
+        command.create_option_kwargs("odds", "mock", &kwargs);
+        let (matches, _) = parse_args(
+            &command.options,
+            &command.commands,
+            args(&["--odds", "-110", "+150"]),
+        )
+        .unwrap();
+
+        assert_eq!(matches.values("odds"), ["-110", "+150"]);
+    }
+
+    #[test]
+    fn test_parse_args_defaults_and_required() {
+        let default = CommandOptionType::Int(10);
+        let mut builder = CommandOptionKwargsBuilder::new();
+        let last = builder.set_default(&default).build();
+        let season = CommandOptionKwargsBuilder::new().set_required().build();
+        let mut command = MockCommand::default();
+        command
+            .create_option_kwargs("last", "mock", &last)
+            .create_option_kwargs("season", "mock", &season);
+
+        let (matches, _) =
+            parse_args(&command.options, &command.commands, args(&["-s", "2024"])).unwrap();
+        assert_eq!(matches.value_of::<u32>("last"), Ok(Some(10)));
+
+        assert_eq!(
+            parse_args(&command.options, &command.commands, args(&[])).unwrap_err(),
+            CLIError::MissingRequired("--season".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_args_choices() {
+        let (json, csv) = (
+            CommandOptionType::Text("json".to_string()),
+            CommandOptionType::Text("csv".to_string()),
+        );
+        let mut choices = vec![&json, &csv];
+        let mut builder = CommandOptionKwargsBuilder::new();
+        let kwargs = builder.set_choices(&mut choices).build();
+        let mut command = MockCommand::default();
+        command.create_option_kwargs("format", "mock", &kwargs);
+
+        let (matches, _) =
+            parse_args(&command.options, &command.commands, args(&["-f", "csv"])).unwrap();
+        assert_eq!(matches.value("format"), Some("csv"));
+
+        assert_eq!(
+            parse_args(&command.options, &command.commands, args(&["-f", "xml"])).unwrap_err(),
+            CLIError::InvalidChoice {
+                option: "--format".to_string(),
+                value: "xml".to_string(),
+                choices: vec!["json".to_string(), "csv".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_args_kind() {
+        let kind = CommandOptionType::Float(0.0);
+        let mut builder = CommandOptionKwargsBuilder::new();
+        let kwargs = builder.set_kind(&kind).build();
+        let mut command = MockCommand::default();
+        command.create_option_kwargs("stake", "mock", &kwargs);
+
+        assert!(parse_args(&command.options, &command.commands, args(&["-s", "2.5"])).is_ok());
+        assert_eq!(
+            parse_args(&command.options, &command.commands, args(&["-s", "ten"])).unwrap_err(),
+            CLIError::InvalidValue {
+                option: "--stake".to_string(),
+                value: "ten".to_string(),
+                expected: "a number".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_args_unknown_option() {
+        let mut command = MockCommand::default();
+        command.create_option("season", "mock");
+
+        assert_eq!(
+            parse_args(
+                &command.options,
+                &command.commands,
+                args(&["--seasn", "2024"])
+            )
+            .unwrap_err(),
+            CLIError::UnknownOption {
+                option: "--seasn".to_string(),
+                suggestions: vec!["--season".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_args_missing_value() {
+        let mut command = MockCommand::default();
+        command.create_option("season", "mock");
+
+        assert_eq!(
+            parse_args(&command.options, &command.commands, args(&["--season"])).unwrap_err(),
+            CLIError::MissingValue {
+                option: "--season".to_string(),
+                expected: 1,
+            }
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use super::{odds_format, price, round, store};
+use crate::{
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        matches::ArgMatches,
+        option::{CommandOptionKwargs, CommandOptionKwargsBuilder, CommandOptionType},
+        output::{Cell, Output, Table},
+    },
+    model::{game::Game, odds::OddsSnapshot, sport::League},
+    odds::{
+        backtest::{backtest, Strategy},
+        ledger::closing_line_value,
+        value::ValueBet,
+    },
+};
+use std::{error::Error, fs, path::Path, sync::LazyLock};
+
+static FILE: CommandOptionType = CommandOptionType::File(String::new());
+static STRATEGY_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_required()
+        .set_kind(&FILE)
+        .build()
+});
+
+/// Registers `statpack backtest --strategy <file>`
+pub(super) fn register(cli: &mut CLI<'static>) {
+    cli.create_command("backtest")
+        .set_handler(run)
+        .create_option_kwargs(
+            "strategy",
+            "Path of the .toml file describing the games to bet on, the pick and the stake",
+            &STRATEGY_KWARGS,
+        );
+}
+
+/// Replays a betting strategy over the stored games and odds, then sums up how it fared
+/// and logs every bet
+fn run(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let store = store(matches)?;
+    let path = matches.value("strategy").unwrap_or_default();
+    let strategy = Strategy::from_toml(&fs::read_to_string(path)?)?;
+    let mut games = store.repo::<Game>().all()?;
+    games.sort_by(|a, b| a.start.cmp(&b.start).then_with(|| a.id.cmp(&b.id)));
+    let leagues = store.repo::<League>().all()?;
+    let odds = store.repo::<OddsSnapshot>().all()?;
+    let backtest = backtest(&strategy, &games, &leagues, &odds);
+    let format = odds_format(matches);
+    let percent = |value: Option<f64>| value.map(|value| round(value * 100.0));
+
+    let mut summary = Table::new(&[
+        "bets",
+        "record",
+        "win_pct",
+        "staked",
+        "profit",
+        "roi_pct",
+        "bankroll",
+        "final_bankroll",
+        "max_drawdown",
+        "max_drawdown_pct",
+        "sharpe",
+        "clv_pct",
+        "beat_close_pct",
+    ]);
+    summary.set_title(&strategy.name.clone().unwrap_or_else(|| {
+        Path::new(path).file_stem().map_or_else(
+            || path.to_string(),
+            |stem| stem.to_string_lossy().to_string(),
+        )
+    }));
+    let ledger = &backtest.summary;
+    let (drawdown, drawdown_share) = backtest.max_drawdown();
+    summary.push(vec![
+        (ledger.bets as i64).into(),
+        ledger.record.to_string().into(),
+        percent(ledger.win_rate()).into(),
+        round(ledger.staked).into(),
+        round(ledger.profit).into(),
+        percent(ledger.roi()).into(),
+        round(backtest.bankroll).into(),
+        round(backtest.final_bankroll()).into(),
+        round(drawdown).into(),
+        round(drawdown_share * 100.0).into(),
+        backtest.sharpe().map(round).into(),
+        percent(ledger.closing_line_value()).into(),
+        percent(ledger.beat_close_rate()).into(),
+    ]);
+
+    let mut bets = Table::new(&[
+        "date",
+        "game",
+        "market",
+        "selection",
+        "line",
+        "book",
+        "odds",
+        "fair_pct",
+        "edge_pct",
+        "stake",
+        "result",
+        "profit",
+        "bankroll",
+        "closing",
+        "clv_pct",
+    ]);
+    bets.set_title("bets");
+    for placed in &backtest.bets {
+        let bet = &placed.bet;
+        bets.push(vec![
+            bet.placed_at.date_naive().to_string().into(),
+            bet.game.to_string().into(),
+            bet.market.to_string().into(),
+            bet.selection.name().into(),
+            bet.line.into(),
+            bet.book.clone().into(),
+            price(format, bet.odds),
+            percent(placed.probability).into(),
+            percent(
+                placed
+                    .probability
+                    .map(|probability| ValueBet::new(probability, bet.odds).edge()),
+            )
+            .into(),
+            round(bet.stake).into(),
+            bet.result.map(|result| result.name()).into(),
+            bet.profit().map(round).into(),
+            round(placed.bankroll).into(),
+            placed
+                .closing
+                .map_or(Cell::Null, |closing| price(format, closing)),
+            percent(
+                placed
+                    .closing
+                    .map(|closing| closing_line_value(bet.odds, closing)),
+            )
+            .into(),
+        ]);
+    }
+    Ok(Box::new(vec![summary, bets]))
+}
+
+#[cfg(test)]
+mod backtest_tests {
+    use crate::{
+        commands::{
+            mock::{db, run},
+            team::mock::seed,
+        },
+        model::{
+            game::mock::game,
+            odds::{Market, OddsSnapshot, Selection},
+        },
+        store::Store,
+    };
+    use chrono::Duration;
+    use std::{fs, path::Path};
+
+    #[test]
+    fn test_backtest() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        seed(&db);
+        // lal was the home favorite of games 1 and 3, winning both
+        let store = Store::open(Path::new(&db)).unwrap();
+        for (id, day, home, away) in [("1", 0, 1.5, 2.7), ("3", 2, 1.6, 2.4)] {
+            let mut game = game(id, "lal", "bos", None);
+            game.start += Duration::days(day);
+            for (selection, price) in [(Selection::Home, home), (Selection::Away, away)] {
+                store
+                    .repo()
+                    .upsert(&OddsSnapshot {
+                        game: game.id.clone(),
+                        book: "pinnacle".to_string(),
+                        market: Market::Moneyline,
+                        selection,
+                        line: None,
+                        price,
+                        taken_at: game.start - Duration::hours(1),
+                    })
+                    .unwrap();
+            }
+        }
+        let strategy = dir.path().join("home-favorites.toml");
+        fs::write(
+            &strategy,
+            "situation = \"home & !b2b\"\n\
+             [pick]\n\
+             market = \"moneyline\"\n\
+             side = \"favorite\"\n\
+             [stake]\n\
+             rule = \"flat\"\n\
+             amount = 10\n",
+        )
+        .unwrap();
+        let strategy = strategy.to_str().unwrap();
+
+        // Game 3 is lal's second night of a back-to-back
+        assert_eq!(
+            run(&[
+                "backtest",
+                "--strategy",
+                strategy,
+                "--db",
+                &db,
+                "--format",
+                "csv"
+            ])
+            .unwrap(),
+            "bets,record,win_pct,staked,profit,roi_pct,bankroll,final_bankroll,max_drawdown,\
+             max_drawdown_pct,sharpe,clv_pct,beat_close_pct\n\
+             1,1-0-0,100,10,5,50,100,105,0,0,,0,0\n\
+             \n\
+             date,game,market,selection,line,book,odds,fair_pct,edge_pct,stake,result,profit,\
+             bankroll,closing,clv_pct\n\
+             2025-01-01,1,moneyline,home,,pinnacle,1.50,64.29,-2.38,10,won,5,105,1.50,0\n"
+        );
+        // Nothing was known before the season started
+        assert!(run(&[
+            "backtest",
+            "--strategy",
+            strategy,
+            "--as-of",
+            "2024-12-31",
+            "--db",
+            &db,
+            "--format",
+            "csv"
+        ])
+        .unwrap()
+        .contains("\n0,0-0-0,,0,0,,100,100,0,0,,,\n"));
+
+        fs::write(
+            dir.path().join("totals.toml"),
+            "[pick]\nmarket = \"total\"\nside = \"home\"\n[stake]\nrule = \"kelly\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            run(&[
+                "backtest",
+                "--strategy",
+                dir.path().join("totals.toml").to_str().unwrap(),
+                "--db",
+                &db
+            ])
+            .unwrap_err()
+            .to_string(),
+            "invalid strategy: cannot pick the home side of the total market"
+        );
+    }
+}
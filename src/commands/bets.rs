@@ -0,0 +1,647 @@
+use super::{decimal_odds, game, now, odds_format, price, round, store, texts};
+use crate::{
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        error::CLIError,
+        matches::ArgMatches,
+        option::{CommandOptionKwargs, CommandOptionKwargsBuilder, CommandOptionType},
+        output::{Cell, Output, Table},
+    },
+    model::{
+        bet::{Bet, BetResult},
+        game::Game,
+        odds::{Market, OddsSnapshot, Selection},
+        sport::League,
+        stat::StatLine,
+    },
+    odds::{
+        ledger::{closing_line_value, closing_price, grade, LedgerSummary},
+        OddsFormat,
+    },
+    stats::error::StatsError,
+    store::{error::StoreError, Store},
+};
+use chrono::{NaiveDate, Utc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    sync::LazyLock,
+};
+
+static AMOUNT: CommandOptionType = CommandOptionType::Float(0.0);
+static UNIT: CommandOptionType = CommandOptionType::Float(1.0);
+static AMOUNT_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_required()
+        .set_kind(&AMOUNT)
+        .build()
+});
+static LINE_KWARGS: LazyLock<CommandOptionKwargs<'static>> =
+    LazyLock::new(|| CommandOptionKwargsBuilder::new().set_kind(&AMOUNT).build());
+static RESULTS: LazyLock<Vec<CommandOptionType>> = LazyLock::new(|| texts(BetResult::ALL));
+static RESULT_CHOICES: LazyLock<Vec<&'static CommandOptionType>> =
+    LazyLock::new(|| RESULTS.iter().collect());
+static RESULT_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_shared_choices(&RESULT_CHOICES)
+        .build()
+});
+static UNIT_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_kind(&UNIT)
+        .set_default(&UNIT)
+        .build()
+});
+
+/// Registers `statpack bets add|settle|list|report`
+pub(super) fn register(cli: &mut CLI<'static>) {
+    let bets = cli.create_command("bets");
+    bets.create_command("add")
+        .set_handler(add)
+        .create_option_kwargs(
+            "game",
+            "Game the bet is on, by ID",
+            &CommandOptionKwargs::REQUIRED,
+        )
+        .create_option_kwargs(
+            "book",
+            "Bookmaker the bet was placed with",
+            &CommandOptionKwargs::REQUIRED,
+        )
+        .create_option_kwargs(
+            "market",
+            "Market of the bet: moneyline, spread, total or prop:<player>:<stat>",
+            &CommandOptionKwargs::REQUIRED,
+        )
+        .create_option_kwargs(
+            "selection",
+            "Side of the bet: home, away, draw, over or under",
+            &CommandOptionKwargs::REQUIRED,
+        )
+        .create_option_kwargs(
+            "line",
+            "Spread or total taken, from the point of view of the selection",
+            &LINE_KWARGS,
+        )
+        .create_option_kwargs(
+            "odds",
+            "Odds taken, in --odds-format",
+            &CommandOptionKwargs::REQUIRED,
+        )
+        .create_option_kwargs("stake", "Amount staked", &AMOUNT_KWARGS)
+        .create_option(
+            "date",
+            "Day the bet was placed, YYYY-MM-DD (defaults to now)",
+        );
+    bets.create_command("settle")
+        .set_handler(settle)
+        .create_option_kwargs(
+            "result",
+            "Settle the bet given by ID by hand: won, lost, push or void",
+            &RESULT_KWARGS,
+        );
+    bets.create_command("list")
+        .set_handler(list)
+        .create_option_kwargs(
+            "open",
+            "Only bets not settled yet",
+            &CommandOptionKwargs::FLAG,
+        );
+    bets.create_command("report")
+        .set_handler(report)
+        .create_option_kwargs(
+            "unit",
+            "Amount of a unit, profit is reported in",
+            &UNIT_KWARGS,
+        );
+}
+
+/// Logs a bet in the ledger
+fn add(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let store = store(matches)?;
+    let game = game(&store, matches.value("game").unwrap_or_default())?;
+    let market: Market = matches.value("market").unwrap_or_default().parse()?;
+    let selection: Selection = matches
+        .value("selection")
+        .unwrap_or_default()
+        .parse()
+        .map_err(|_| CLIError::InvalidChoice {
+            option: "--selection".to_string(),
+            value: matches.value("selection").unwrap_or_default().to_string(),
+            choices: Selection::ALL
+                .iter()
+                .map(|item| item.name().to_string())
+                .collect(),
+        })?;
+    let line: Option<f64> = matches.value_of("line")?;
+    check_selection(&market, selection, line)?;
+    let odds = match decimal_odds(matches, "odds")?[..] {
+        [odds] => odds,
+        _ => {
+            return Err(CLIError::MissingValue {
+                option: "--odds".to_string(),
+                expected: 1,
+            }
+            .into())
+        }
+    };
+    let stake: f64 = matches.value_of("stake")?.unwrap_or_default();
+    if stake <= 0.0 {
+        return Err(CLIError::InvalidValue {
+            option: "--stake".to_string(),
+            value: stake.to_string(),
+            expected: "a positive amount".to_string(),
+        }
+        .into());
+    }
+    let placed_at = match matches.value_of::<NaiveDate>("date")? {
+        Some(date) => date.and_time(Default::default()).and_utc(),
+        None => Utc::now(),
+    };
+
+    let repo = store.repo::<Bet>();
+    let bet = Bet {
+        id: repo.all()?.iter().map(|bet| bet.id).max().unwrap_or(0) + 1,
+        placed_at,
+        game: game.id,
+        book: matches.value("book").unwrap_or_default().to_string(),
+        market,
+        selection,
+        line,
+        odds,
+        stake,
+        result: None,
+        settled_at: None,
+    };
+    repo.upsert(&bet)?;
+    Ok(Box::new(bets_table(&store, odds_format(matches), &[bet])?))
+}
+
+/// Fails unless the selection is a side of the market and the line is given when the
+/// market has one
+fn check_selection(
+    market: &Market,
+    selection: Selection,
+    line: Option<f64>,
+) -> Result<(), CLIError> {
+    let (sides, lined): (&[Selection], bool) = match market {
+        Market::Moneyline => (&[Selection::Home, Selection::Away, Selection::Draw], false),
+        Market::Spread => (&[Selection::Home, Selection::Away], true),
+        Market::Total | Market::Prop { .. } => (&[Selection::Over, Selection::Under], true),
+    };
+    if !sides.contains(&selection) {
+        return Err(CLIError::InvalidChoice {
+            option: "--selection".to_string(),
+            value: selection.name().to_string(),
+            choices: sides.iter().map(|side| side.name().to_string()).collect(),
+        });
+    }
+    match (lined, line) {
+        (true, None) => Err(CLIError::MissingRequired("--line".to_string())),
+        (false, Some(line)) => Err(CLIError::InvalidValue {
+            option: "--line".to_string(),
+            value: line.to_string(),
+            expected: format!("no line on a {} bet", market),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Settles the bet given by ID with `--result`, or every open bet whose game is graded
+fn settle(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let store = store(matches)?;
+    let repo = store.repo::<Bet>();
+    let now = Utc::now();
+    let mut settled = vec![];
+
+    if let Some(result) = matches.value("result") {
+        let result: BetResult = result.parse()?;
+        let Some(id) = matches.positionals().first() else {
+            return Err(CLIError::MissingRequired("<id>".to_string()).into());
+        };
+        let mut bet = find_bet(&store, id)?;
+        bet.settle(result, now);
+        repo.upsert(&bet)?;
+        settled.push(bet);
+    } else {
+        let bets = match matches.positionals().first() {
+            Some(id) => vec![find_bet(&store, id)?],
+            None => repo.all()?,
+        };
+        let mut games = GameCache::new(&store);
+        for mut bet in bets.into_iter().filter(|bet| bet.result.is_none()) {
+            let Some(game) = games.get(&bet)? else {
+                continue;
+            };
+            let line = match &bet.market {
+                Market::Prop { player, .. } => store
+                    .repo::<StatLine>()
+                    .get(&format!("{}|{}", game.id, player))?,
+                _ => None,
+            };
+            if let Some(result) = grade(&bet, game, line.as_ref()) {
+                bet.settle(result, now);
+                repo.upsert(&bet)?;
+                settled.push(bet);
+            }
+        }
+    }
+
+    let mut table = bets_table(&store, odds_format(matches), &settled)?;
+    table.set_title(&format!("settled {} bets", settled.len()));
+    Ok(Box::new(table))
+}
+
+/// Returns the bet with the number
+fn find_bet(store: &Store, id: &str) -> Result<Bet, Box<dyn Error>> {
+    let number: u32 = id.parse().map_err(|_| CLIError::InvalidValue {
+        option: "<id>".to_string(),
+        value: id.to_string(),
+        expected: "a bet number".to_string(),
+    })?;
+    store
+        .repo::<Bet>()
+        .all()?
+        .into_iter()
+        .find(|bet| bet.id == number)
+        .ok_or_else(|| {
+            StatsError::NotFound {
+                kind: "bet",
+                query: id.to_string(),
+                suggestions: vec![],
+            }
+            .into()
+        })
+}
+
+fn list(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let store = store(matches)?;
+    let mut bets = store.repo::<Bet>().all()?;
+    if matches.flag("open") {
+        bets.retain(|bet| bet.result.is_none());
+    }
+    Ok(Box::new(bets_table(&store, odds_format(matches), &bets)?))
+}
+
+/// Sums up the ledger overall, then by sport, market and book
+fn report(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let store = store(matches)?;
+    let unit: f64 = matches.value_of("unit")?.unwrap_or(1.0);
+    if unit <= 0.0 {
+        return Err(CLIError::InvalidValue {
+            option: "--unit".to_string(),
+            value: unit.to_string(),
+            expected: "a positive amount".to_string(),
+        }
+        .into());
+    }
+    let leagues: HashMap<String, League> = store
+        .repo::<League>()
+        .all()?
+        .into_iter()
+        .map(|league| (league.id.to_string(), league))
+        .collect();
+
+    let mut games = GameCache::new(&store);
+    let mut overall = LedgerSummary::default();
+    let mut splits: [BTreeMap<String, LedgerSummary>; 3] = Default::default();
+    for bet in store.repo::<Bet>().all()? {
+        let closing = games.closing_price(&bet)?;
+        let sport = games
+            .get(&bet)?
+            .and_then(|game| leagues.get(game.league.as_str()))
+            .map_or("unknown".to_string(), |league| league.sport.to_string());
+        overall.add(&bet, closing);
+        for (split, value) in
+            splits
+                .iter_mut()
+                .zip([sport, bet.market.kind().to_string(), bet.book.clone()])
+        {
+            split.entry(value).or_default().add(&bet, closing);
+        }
+    }
+
+    let mut table = Table::new(&[
+        "split",
+        "value",
+        "bets",
+        "open",
+        "record",
+        "win_pct",
+        "staked",
+        "profit",
+        "units",
+        "roi_pct",
+        "clv_pct",
+        "beat_close_pct",
+    ]);
+    let mut push = |split: &str, value: Option<&String>, summary: &LedgerSummary| {
+        let percent = |value: Option<f64>| value.map(|value| round(value * 100.0));
+        table.push(vec![
+            split.into(),
+            value.cloned().into(),
+            (summary.bets as i64).into(),
+            (summary.open as i64).into(),
+            summary.record.to_string().into(),
+            percent(summary.win_rate()).into(),
+            round(summary.staked).into(),
+            round(summary.profit).into(),
+            round(summary.units(unit)).into(),
+            percent(summary.roi()).into(),
+            percent(summary.closing_line_value()).into(),
+            percent(summary.beat_close_rate()).into(),
+        ]);
+    };
+    push("all", None, &overall);
+    for (name, split) in ["sport", "market", "book"].into_iter().zip(&splits) {
+        for (value, summary) in split {
+            push(name, Some(value), summary);
+        }
+    }
+    Ok(Box::new(table))
+}
+
+/// Lists bets with their result and closing line value
+fn bets_table(store: &Store, format: OddsFormat, bets: &[Bet]) -> Result<Table, Box<dyn Error>> {
+    let mut games = GameCache::new(store);
+    let mut table = Table::new(&[
+        "id",
+        "placed",
+        "game",
+        "book",
+        "market",
+        "selection",
+        "line",
+        "odds",
+        "stake",
+        "result",
+        "profit",
+        "closing",
+        "clv_pct",
+    ]);
+    for bet in bets {
+        let closing = games.closing_price(bet)?;
+        table.push(vec![
+            i64::from(bet.id).into(),
+            bet.placed_at.date_naive().to_string().into(),
+            bet.game.to_string().into(),
+            bet.book.clone().into(),
+            bet.market.to_string().into(),
+            bet.selection.name().into(),
+            bet.line.into(),
+            price(format, bet.odds),
+            round(bet.stake).into(),
+            bet.result.map(|result| result.name()).into(),
+            bet.profit().map(round).into(),
+            closing.map_or(Cell::Null, |closing| price(format, closing)),
+            closing
+                .map(|closing| round(closing_line_value(bet.odds, closing) * 100.0))
+                .into(),
+        ]);
+    }
+    Ok(table)
+}
+
+/// Loads the game and odds of bets once per game
+struct GameCache<'s> {
+    store: &'s Store,
+    games: HashMap<String, Option<(Game, Vec<OddsSnapshot>)>>,
+}
+impl<'s> GameCache<'s> {
+    fn new(store: &'s Store) -> Self {
+        Self {
+            store,
+            games: HashMap::new(),
+        }
+    }
+    fn load(&mut self, bet: &Bet) -> Result<Option<&(Game, Vec<OddsSnapshot>)>, StoreError> {
+        let id = bet.game.as_str();
+        if !self.games.contains_key(id) {
+            let game = match self.store.repo::<Game>().get(id)? {
+                Some(game) => Some((game, self.store.repo::<OddsSnapshot>().for_game(id)?)),
+                None => None,
+            };
+            self.games.insert(id.to_string(), game);
+        }
+        Ok(self.games[id].as_ref())
+    }
+    /// Returns the stored game of the bet
+    fn get(&mut self, bet: &Bet) -> Result<Option<&Game>, StoreError> {
+        Ok(self.load(bet)?.map(|(game, _)| game))
+    }
+    /// Returns the closing price of the bet once its game has started
+    fn closing_price(&mut self, bet: &Bet) -> Result<Option<f64>, StoreError> {
+        let now = now(self.store);
+        Ok(self
+            .load(bet)?
+            .filter(|(game, _)| game.start <= now)
+            .and_then(|(game, odds)| closing_price(bet, game, odds)))
+    }
+}
+
+#[cfg(test)]
+mod bets_tests {
+    use crate::{
+        commands::{
+            mock::{db, run},
+            team::mock::seed,
+        },
+        model::{
+            game::mock::game,
+            odds::{Market, OddsSnapshot, Selection},
+        },
+        store::Store,
+    };
+    use chrono::Duration;
+    use std::path::Path;
+
+    #[test]
+    fn test_bets() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        seed(&db);
+        // lal closed at 1.8 on the moneyline of game 1, which they won 110-100
+        let start = game("1", "lal", "bos", None).start;
+        Store::open(Path::new(&db))
+            .unwrap()
+            .repo()
+            .upsert(&OddsSnapshot {
+                game: "1".into(),
+                book: "pinnacle".to_string(),
+                market: Market::Moneyline,
+                selection: Selection::Home,
+                line: None,
+                price: 1.8,
+                taken_at: start - Duration::hours(1),
+            })
+            .unwrap();
+        let bet = |args: &[&str]| {
+            let mut all = vec!["bets", "add", "--db", &db, "--book", "pinnacle"];
+            all.extend(args);
+            run(&all).unwrap()
+        };
+
+        assert_eq!(
+            run(&[
+                "bets",
+                "add",
+                "--db",
+                &db,
+                "--game",
+                "1",
+                "--book",
+                "pinnacle",
+                "--market",
+                "moneyline",
+                "--selection",
+                "home",
+                "--odds",
+                "+100",
+                "--odds-format",
+                "american",
+                "--stake",
+                "10",
+                "--date",
+                "2024-12-31",
+                "--format",
+                "csv",
+            ])
+            .unwrap(),
+            "id,placed,game,book,market,selection,line,odds,stake,result,profit,closing,clv_pct\n\
+             1,2024-12-31,1,pinnacle,moneyline,home,,+100,10,,,-125,11.11\n"
+        );
+        // bos +4.5 lost by 10, den/lal went over 218.5, game 5 is not played yet
+        bet(&[
+            "--game",
+            "1",
+            "--market",
+            "spread",
+            "--selection",
+            "away",
+            "--line",
+            "4.5",
+            "--odds",
+            "1.9",
+            "--stake",
+            "20",
+        ]);
+        bet(&[
+            "--game",
+            "2",
+            "--market",
+            "total",
+            "--selection",
+            "over",
+            "--line",
+            "218.5",
+            "--odds",
+            "11/10",
+            "--odds-format",
+            "fractional",
+            "--stake",
+            "10",
+        ]);
+        bet(&[
+            "--game",
+            "5",
+            "--market",
+            "moneyline",
+            "--selection",
+            "away",
+            "--odds",
+            "2.5",
+            "--stake",
+            "10",
+        ]);
+        assert!(run(&[
+            "bets",
+            "add",
+            "--db",
+            &db,
+            "--book",
+            "pinnacle",
+            "--game",
+            "1",
+            "--market",
+            "total",
+            "--selection",
+            "home",
+            "--line",
+            "200",
+            "--odds",
+            "2",
+            "--stake",
+            "10",
+        ])
+        .is_err());
+        assert!(run(&[
+            "bets",
+            "add",
+            "--db",
+            &db,
+            "--book",
+            "pinnacle",
+            "--game",
+            "1",
+            "--market",
+            "spread",
+            "--selection",
+            "home",
+            "--odds",
+            "2",
+            "--stake",
+            "10",
+        ])
+        .is_err());
+        assert!(run(&[
+            "bets",
+            "add",
+            "--db",
+            &db,
+            "--book",
+            "pinnacle",
+            "--game",
+            "9",
+            "--market",
+            "moneyline",
+            "--selection",
+            "home",
+            "--odds",
+            "2",
+            "--stake",
+            "10",
+        ])
+        .is_err());
+
+        let settled = run(&["bets", "settle", "--db", &db, "--format", "csv"]).unwrap();
+        assert_eq!(
+            settled
+                .lines()
+                .map(|line| line.split(',').nth(9).unwrap())
+                .collect::<Vec<_>>(),
+            ["result", "won", "lost", "won"]
+        );
+        run(&["bets", "settle", "4", "--result", "void", "--db", &db]).unwrap();
+        assert!(run(&["bets", "settle", "9", "--result", "void", "--db", &db]).is_err());
+        assert_eq!(
+            run(&["bets", "list", "--open", "--db", &db, "--format", "csv"])
+                .unwrap()
+                .lines()
+                .count(),
+            1
+        );
+
+        assert_eq!(
+            run(&["bets", "report", "--db", &db, "--unit", "5", "--format", "csv"]).unwrap(),
+            "split,value,bets,open,record,win_pct,staked,profit,units,roi_pct,clv_pct,\
+             beat_close_pct\n\
+             all,,4,0,2-1-0,66.67,40,1,0.2,2.5,11.11,100\n\
+             sport,basketball,4,0,2-1-0,66.67,40,1,0.2,2.5,11.11,100\n\
+             market,moneyline,2,0,1-0-0,100,10,10,2,100,11.11,100\n\
+             market,spread,1,0,0-1-0,0,20,-20,-4,-100,,\n\
+             market,total,1,0,1-0-0,100,10,11,2.2,110,,\n\
+             book,pinnacle,4,0,2-1-0,66.67,40,1,0.2,2.5,11.11,100\n"
+        );
+    }
+}
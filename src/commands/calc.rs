@@ -0,0 +1,446 @@
+use super::{decimal_odds, odds_format, price, round, texts};
+use crate::{
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        error::CLIError,
+        matches::ArgMatches,
+        option::{CommandOptionKwargs, CommandOptionKwargsBuilder, CommandOptionType},
+        output::{Cell, Output, Table},
+    },
+    odds::bets::{
+        break_even_per_leg, parlay, round_robin, single, EachWay, MultipleBet, Payout, System,
+    },
+};
+use std::{error::Error, sync::LazyLock};
+
+static AMOUNT: CommandOptionType = CommandOptionType::Float(0.0);
+static COUNT: CommandOptionType = CommandOptionType::Int(0);
+static ODDS: CommandOptionType = CommandOptionType::Text(String::new());
+static PLACE_TERMS: CommandOptionType = CommandOptionType::Float(0.25);
+static AMOUNT_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_required()
+        .set_kind(&AMOUNT)
+        .build()
+});
+static COUNT_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_required()
+        .set_kind(&COUNT)
+        .build()
+});
+static ODDS_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_required()
+        .set_kind(&ODDS)
+        .build()
+});
+static HEDGE_KWARGS: LazyLock<CommandOptionKwargs<'static>> =
+    LazyLock::new(|| CommandOptionKwargsBuilder::new().set_kind(&ODDS).build());
+static PLACE_TERMS_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_kind(&PLACE_TERMS)
+        .set_default(&PLACE_TERMS)
+        .build()
+});
+static SYSTEMS: LazyLock<Vec<CommandOptionType>> = LazyLock::new(|| texts(System::ALL));
+static SYSTEM_CHOICES: LazyLock<Vec<&'static CommandOptionType>> =
+    LazyLock::new(|| SYSTEMS.iter().collect());
+static SYSTEM_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_required()
+        .set_shared_choices(&SYSTEM_CHOICES)
+        .build()
+});
+
+/// Registers `statpack calc single|parlay|teaser|round-robin|each-way|system`
+pub(super) fn register(cli: &mut CLI<'static>) {
+    let calc = cli.create_command("calc");
+    calc.create_command("single")
+        .set_handler(calc_single)
+        .create_option_kwargs("stake", "Amount staked", &AMOUNT_KWARGS)
+        .create_option_kwargs(
+            "odds",
+            "Odds of the selection, in --odds-format",
+            &ODDS_KWARGS,
+        )
+        .create_option_kwargs(
+            "hedge-odds",
+            "Odds available on the other side",
+            &HEDGE_KWARGS,
+        );
+    calc.create_command("parlay")
+        .set_handler(calc_parlay)
+        .create_option_kwargs("stake", "Amount staked", &AMOUNT_KWARGS)
+        .create_option_kwargs("odds", "Odds of a leg, once per leg", &ODDS_KWARGS)
+        .create_option_kwargs(
+            "hedge-odds",
+            "Odds available against the last leg",
+            &HEDGE_KWARGS,
+        );
+    calc.create_command("teaser")
+        .set_handler(calc_teaser)
+        .create_option_kwargs("stake", "Amount staked", &AMOUNT_KWARGS)
+        .create_option_kwargs("odds", "Odds the teaser pays, e.g. -120", &ODDS_KWARGS)
+        .create_option_kwargs("legs", "Number of legs in the teaser", &COUNT_KWARGS)
+        .create_option_kwargs(
+            "hedge-odds",
+            "Odds available against the last leg",
+            &HEDGE_KWARGS,
+        );
+    calc.create_command("round-robin")
+        .set_handler(calc_round_robin)
+        .create_option_kwargs("stake", "Amount staked on each parlay", &AMOUNT_KWARGS)
+        .create_option_kwargs("odds", "Odds of a leg, once per leg", &ODDS_KWARGS)
+        .create_option_kwargs("size", "Number of legs in each parlay", &COUNT_KWARGS);
+    calc.create_command("each-way")
+        .set_handler(calc_each_way)
+        .create_option_kwargs(
+            "stake",
+            "Amount staked on each part, the bet costs twice as much",
+            &AMOUNT_KWARGS,
+        )
+        .create_option_kwargs("odds", "Win odds of the selection", &ODDS_KWARGS)
+        .create_option_kwargs(
+            "place-terms",
+            "Share of the win odds the place part pays (0.25 for 1/4 odds)",
+            &PLACE_TERMS_KWARGS,
+        );
+    calc.create_command("system")
+        .set_handler(calc_system)
+        .create_option_kwargs(
+            "type",
+            "System bet, e.g. trixie, yankee or lucky-15",
+            &SYSTEM_KWARGS,
+        )
+        .create_option_kwargs(
+            "stake",
+            "Amount staked on each bet of the system",
+            &AMOUNT_KWARGS,
+        )
+        .create_option_kwargs(
+            "odds",
+            "Odds of a selection, once per selection",
+            &ODDS_KWARGS,
+        );
+}
+
+/// Returns the `--stake`
+fn stake(matches: &ArgMatches) -> Result<f64, CLIError> {
+    Ok(matches.value_of("stake")?.unwrap_or_default())
+}
+
+/// Returns the `--odds` given once
+fn one_leg(matches: &ArgMatches) -> Result<f64, Box<dyn Error>> {
+    match decimal_odds(matches, "odds")?[..] {
+        [odds] => Ok(odds),
+        _ => Err(CLIError::MissingValue {
+            option: "--odds".to_string(),
+            expected: 1,
+        }
+        .into()),
+    }
+}
+
+/// Formats a probability as a percentage with two decimals
+fn percent(probability: f64) -> f64 {
+    round(probability * 100.0)
+}
+
+/// Lays out what a bet returns, with the hedge against it when `--hedge-odds` is given
+fn payout_table(
+    matches: &ArgMatches,
+    bet: Payout,
+    legs: usize,
+) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let format = odds_format(matches);
+    let hedge = decimal_odds(matches, "hedge-odds")?
+        .first()
+        .map(|odds| bet.hedge(*odds));
+    let mut table = Table::new(&[
+        "stake",
+        "odds",
+        "payout",
+        "profit",
+        "probability_pct",
+        "break_even_pct",
+        "hedge",
+        "hedge_profit",
+    ]);
+    table.push(vec![
+        bet.stake.into(),
+        price(format, bet.odds),
+        round(bet.payout()).into(),
+        round(bet.profit()).into(),
+        percent(bet.implied_probability()).into(),
+        percent(break_even_per_leg(bet.odds, legs)).into(),
+        hedge.map(|(hedge, _)| round(hedge)).into(),
+        hedge.map(|(_, profit)| round(profit)).into(),
+    ]);
+    Ok(Box::new(table))
+}
+
+/// Lays out the cost and best return of several bets placed together
+fn multiple_table(bet: &MultipleBet, legs: &[f64]) -> Box<dyn Output> {
+    let mut table = Table::new(&[
+        "bets",
+        "total_stake",
+        "max_payout",
+        "max_profit",
+        "probability_pct",
+    ]);
+    let max_payout = bet.max_payout(legs);
+    table.push(vec![
+        (bet.bets.len() as i64).into(),
+        round(bet.total_stake()).into(),
+        round(max_payout).into(),
+        round(max_payout - bet.total_stake()).into(),
+        percent(legs.iter().map(|odds| 1.0 / odds).product()).into(),
+    ]);
+    Box::new(table)
+}
+
+fn calc_single(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    payout_table(matches, single(stake(matches)?, one_leg(matches)?), 1)
+}
+
+fn calc_parlay(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let legs = decimal_odds(matches, "odds")?;
+    if legs.len() < 2 {
+        return Err(CLIError::MissingValue {
+            option: "--odds".to_string(),
+            expected: 2,
+        }
+        .into());
+    }
+    payout_table(matches, parlay(stake(matches)?, &legs), legs.len())
+}
+
+fn calc_teaser(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let legs: usize = matches.value_of("legs")?.unwrap_or_default();
+    if legs < 2 {
+        return Err(CLIError::InvalidValue {
+            option: "--legs".to_string(),
+            value: legs.to_string(),
+            expected: "at least 2 legs".to_string(),
+        }
+        .into());
+    }
+    payout_table(matches, single(stake(matches)?, one_leg(matches)?), legs)
+}
+
+fn calc_round_robin(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let legs = decimal_odds(matches, "odds")?;
+    let size: usize = matches.value_of("size")?.unwrap_or_default();
+    if size < 2 || size >= legs.len() {
+        return Err(CLIError::InvalidValue {
+            option: "--size".to_string(),
+            value: size.to_string(),
+            expected: format!("between 2 and {}", legs.len().saturating_sub(1)),
+        }
+        .into());
+    }
+    Ok(multiple_table(
+        &round_robin(stake(matches)?, legs.len(), size),
+        &legs,
+    ))
+}
+
+fn calc_system(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let system: System = matches.value("type").unwrap_or_default().parse()?;
+    let legs = decimal_odds(matches, "odds")?;
+    if legs.len() != system.selections() {
+        return Err(CLIError::MissingValue {
+            option: "--odds".to_string(),
+            expected: system.selections(),
+        }
+        .into());
+    }
+    Ok(multiple_table(&system.bets(stake(matches)?), &legs))
+}
+
+fn calc_each_way(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let bet = EachWay {
+        stake: stake(matches)?,
+        odds: one_leg(matches)?,
+        place_terms: matches.value_of("place-terms")?.unwrap_or(0.25),
+    };
+    let format = odds_format(matches);
+    let total = bet.stake * 2.0;
+    let mut table = Table::new(&["outcome", "odds", "stake", "payout", "profit"]);
+    for (outcome, odds, payout) in [
+        ("win", Some(bet.odds), bet.win_payout()),
+        ("place", Some(bet.place_odds()), bet.place_payout()),
+        ("lose", None, 0.0),
+    ] {
+        table.push(vec![
+            outcome.into(),
+            odds.map_or(Cell::Null, |odds| price(format, odds)),
+            round(total).into(),
+            round(payout).into(),
+            round(payout - total).into(),
+        ]);
+    }
+    Ok(Box::new(table))
+}
+
+#[cfg(test)]
+mod calc_tests {
+    use crate::commands::mock::run;
+
+    fn calc(args: &[&str]) -> String {
+        let mut args = args.to_vec();
+        args.extend(["--format", "csv"]);
+        run(&args).unwrap()
+    }
+
+    #[test]
+    fn test_calc_single_and_parlay() {
+        assert_eq!(
+            calc(&[
+                "calc",
+                "single",
+                "--stake",
+                "110",
+                "--odds",
+                "-110",
+                "--odds-format",
+                "american",
+                "--hedge-odds",
+                "+120"
+            ]),
+            "stake,odds,payout,profit,probability_pct,break_even_pct,hedge,hedge_profit\n\
+             110,-110,210,100,52.38,52.38,95.45,4.55\n"
+        );
+        assert_eq!(
+            calc(&[
+                "calc", "parlay", "--stake", "10", "--odds", "2", "--odds", "1.5", "--odds", "3"
+            ]),
+            "stake,odds,payout,profit,probability_pct,break_even_pct,hedge,hedge_profit\n\
+             10,9.00,90,80,11.11,48.07,,\n"
+        );
+        assert_eq!(
+            calc(&[
+                "calc",
+                "single",
+                "--stake",
+                "100",
+                "--odds",
+                "6/4",
+                "--odds-format",
+                "fractional",
+                "--hedge-odds",
+                "3/2"
+            ]),
+            "stake,odds,payout,profit,probability_pct,break_even_pct,hedge,hedge_profit\n\
+             100,3/2,250,150,40,40,100,50\n"
+        );
+        assert_eq!(
+            calc(&[
+                "calc",
+                "parlay",
+                "--stake",
+                "10",
+                "--odds",
+                "50%",
+                "--odds",
+                "40%",
+                "--odds-format",
+                "probability"
+            ]),
+            "stake,odds,payout,profit,probability_pct,break_even_pct,hedge,hedge_profit\n\
+             10,20.00%,50,40,20,44.72,,\n"
+        );
+        assert!(run(&["calc", "parlay", "--stake", "10", "--odds", "2"]).is_err());
+        assert!(run(&[
+            "calc",
+            "single",
+            "--stake",
+            "10",
+            "--odds",
+            "2/0",
+            "--odds-format",
+            "fractional"
+        ])
+        .is_err());
+        assert!(run(&["calc", "single", "--stake", "ten", "--odds", "2"]).is_err());
+        assert!(run(&["calc", "single", "--stake", "10", "--odds", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_calc_teaser() {
+        assert_eq!(
+            calc(&[
+                "calc",
+                "teaser",
+                "--stake",
+                "120",
+                "--odds",
+                "-120",
+                "--odds-format",
+                "american",
+                "--legs",
+                "2"
+            ]),
+            "stake,odds,payout,profit,probability_pct,break_even_pct,hedge,hedge_profit\n\
+             120,-120,220,100,54.55,73.85,,\n"
+        );
+    }
+
+    #[test]
+    fn test_calc_multiples() {
+        assert_eq!(
+            calc(&[
+                "calc",
+                "round-robin",
+                "--stake",
+                "10",
+                "--odds",
+                "2",
+                "--odds",
+                "2",
+                "--odds",
+                "2",
+                "--size",
+                "2"
+            ]),
+            "bets,total_stake,max_payout,max_profit,probability_pct\n\
+             3,30,120,90,12.5\n"
+        );
+        assert_eq!(
+            calc(&[
+                "calc", "system", "--type", "trixie", "--stake", "1", "--odds", "2", "--odds", "2",
+                "--odds", "2"
+            ]),
+            "bets,total_stake,max_payout,max_profit,probability_pct\n\
+             4,4,20,16,12.5\n"
+        );
+        assert!(
+            run(&["calc", "system", "--type", "trixie", "--stake", "1", "--odds", "2"]).is_err()
+        );
+        assert!(
+            run(&["calc", "system", "--type", "double", "--stake", "1", "--odds", "2"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_calc_each_way() {
+        assert_eq!(
+            calc(&[
+                "calc",
+                "each-way",
+                "--stake",
+                "10",
+                "--odds",
+                "10",
+                "--odds-format",
+                "fractional"
+            ]),
+            "outcome,odds,stake,payout,profit\n\
+             win,10/1,20,145,125\n\
+             place,5/2,20,35,15\n\
+             lose,,20,0,-20\n"
+        );
+    }
+}
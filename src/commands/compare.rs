@@ -0,0 +1,290 @@
+use super::{round, sport, store, texts};
+use crate::{
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        error::CLIError,
+        matches::ArgMatches,
+        option::{CommandOptionKwargs, CommandOptionKwargsBuilder, CommandOptionType},
+        output::{Cell, Output, Table},
+    },
+    model::team::Player,
+    stats::{
+        compare::{compare, shared_window, Basis},
+        per_minutes,
+        player::{game_lines, Aggregate, GameLine, PlayerFilter},
+        resolve::resolve,
+    },
+};
+use std::{collections::BTreeSet, error::Error, sync::LazyLock};
+
+static COUNT: CommandOptionType = CommandOptionType::Int(0);
+static LAST_KWARGS: LazyLock<CommandOptionKwargs<'static>> =
+    LazyLock::new(|| CommandOptionKwargsBuilder::new().set_kind(&COUNT).build());
+static BASES: LazyLock<Vec<CommandOptionType>> =
+    LazyLock::new(|| texts(["game", "minutes", "possessions"]));
+static BASIS_CHOICES: LazyLock<Vec<&'static CommandOptionType>> =
+    LazyLock::new(|| BASES.iter().collect());
+static PER_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_shared_choices(&BASIS_CHOICES)
+        .build()
+});
+
+/// Possessions per-possession numbers are scaled to
+const POSSESSIONS: f64 = 100.0;
+
+/// Registers `statpack compare <player> <player> [...] [--season] [--last] [--per] [--stat]`
+pub(super) fn register(cli: &mut CLI<'static>) {
+    cli.create_command("compare")
+        .set_handler(run)
+        .create_option("season", "Season of the games, by name (2024-25) or ID")
+        .create_option_kwargs(
+            "last",
+            "Only the most recent N games of each player",
+            &LAST_KWARGS,
+        )
+        .create_option_kwargs(
+            "per",
+            "Normalize stats per game, per minutes (per-36, per-90) or per 100 possessions \
+             (defaults to minutes when the sport has a basis)",
+            &PER_KWARGS,
+        )
+        .create_option("stat", "Comma-separated stats to compare, e.g. pts,reb,ast");
+}
+
+fn run(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let store = store(matches)?;
+    if matches.positionals().len() < 2 {
+        return Err(CLIError::MissingValue {
+            option: "<player>".to_string(),
+            expected: 2,
+        }
+        .into());
+    }
+    let players = store.repo::<Player>().all()?;
+    let mut compared = vec![];
+    for name in matches.positionals() {
+        compared.push(resolve("player", name, &players, |player| {
+            vec![player.name.as_str()]
+        })?);
+    }
+
+    let mut filter = PlayerFilter::new();
+    if let Some(season) = matches.value("season") {
+        filter.set_season(season);
+    }
+    if let Some(last) = matches.value_of("last")? {
+        filter.set_last(last);
+    }
+    let mut lines = vec![];
+    for player in &compared {
+        lines.push(filter.apply(game_lines(&store, &player.id)?));
+    }
+    let window = shared_window(&lines);
+    let in_window = |lines: Vec<GameLine>| -> Vec<GameLine> {
+        lines
+            .into_iter()
+            .filter(|line| {
+                window
+                    .as_ref()
+                    .is_some_and(|window| window.contains(&line.game.start))
+            })
+            .collect()
+    };
+    let lines: Vec<Vec<GameLine>> = lines.into_iter().map(in_window).collect();
+
+    let basis = match (
+        matches.value("per"),
+        sport(&store, lines.concat().as_slice())?,
+    ) {
+        (Some("game"), _) => Basis::Game,
+        (Some("possessions"), _) => Basis::Possessions(POSSESSIONS),
+        (_, sport) => sport
+            .and_then(per_minutes)
+            .map_or(Basis::Game, Basis::Minutes),
+    };
+
+    // Everyone at the position of a compared player, over the same games
+    let mut populations = vec![];
+    for player in &compared {
+        let mut population = vec![];
+        for other in players
+            .iter()
+            .filter(|other| other.position == player.position)
+        {
+            let other_lines = in_window(filter.apply(game_lines(&store, &other.id)?));
+            if !other_lines.is_empty() {
+                population.push(Aggregate::new(other_lines.iter().map(|line| &line.line)));
+            }
+        }
+        populations.push(population);
+    }
+
+    let aggregates: Vec<Aggregate> = lines
+        .iter()
+        .map(|lines| Aggregate::new(lines.iter().map(|line| &line.line)))
+        .collect();
+    let keys: Vec<String> = match matches.value("stat") {
+        Some(stats) => stats
+            .split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+            .collect(),
+        None => aggregates
+            .iter()
+            .flat_map(|aggregate| aggregate.totals.keys().cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect(),
+    };
+
+    let names: Vec<&str> = compared.iter().map(|player| player.name.as_str()).collect();
+    let mut columns = vec!["stat".to_string()];
+    columns.extend(names.iter().map(|name| name.to_string()));
+    columns.extend(names.iter().map(|name| format!("{} pct", name)));
+    columns.push("leader".to_string());
+    let mut table = Table::new(&columns.iter().map(String::as_str).collect::<Vec<_>>());
+    table.set_title(&basis.to_string());
+
+    let mut games: Vec<Cell> = vec!["games".into()];
+    games.extend(
+        aggregates
+            .iter()
+            .map(|aggregate| Cell::from(aggregate.games as i64)),
+    );
+    games.extend(names.iter().map(|_| Cell::Null));
+    games.push(Cell::Null);
+    table.push(games);
+
+    for comparison in compare(&aggregates, &populations, &keys, basis) {
+        let mut row: Vec<Cell> = vec![comparison.key.into()];
+        row.extend(
+            comparison
+                .values
+                .iter()
+                .map(|value| Cell::from(value.map(round))),
+        );
+        row.extend(
+            comparison
+                .percentiles
+                .iter()
+                .map(|percentile| Cell::from(percentile.map(round))),
+        );
+        row.push(comparison.leader.map(|leader| names[leader]).into());
+        table.push(row);
+    }
+    Ok(Box::new(table))
+}
+
+#[cfg(test)]
+mod compare_command_tests {
+    use crate::{
+        commands::mock::{db, run},
+        model::{
+            game::{mock::game, Game},
+            id::{LeagueId, PlayerId, TeamId},
+            sport::{League, Sport},
+            stat::StatLine,
+            team::Player,
+        },
+        store::Store,
+    };
+    use std::path::Path;
+
+    /// Stores three guards and a center, the center only playing the last game
+    fn seed(db: &str) {
+        let store = Store::open(Path::new(db)).unwrap();
+        store
+            .repo()
+            .upsert(&League {
+                id: LeagueId::new("nba"),
+                sport: Sport::Basketball,
+                name: "NBA".to_string(),
+                country: None,
+                rules: None,
+            })
+            .unwrap();
+        for (id, name, position) in [
+            ("curry", "Stephen Curry", "G"),
+            ("lillard", "Damian Lillard", "G"),
+            ("young", "Trae Young", "G"),
+            ("jokic", "Nikola Jokic", "C"),
+        ] {
+            store
+                .repo()
+                .upsert(&Player {
+                    id: PlayerId::new(id),
+                    name: name.to_string(),
+                    team: None,
+                    position: Some(position.to_string()),
+                    birth_date: None,
+                })
+                .unwrap();
+        }
+
+        let lines: [&[(&str, f64, f64, f64)]; 3] = [
+            &[("curry", 36.0, 30.0, 4.0), ("lillard", 36.0, 20.0, 2.0)],
+            &[
+                ("curry", 18.0, 10.0, 1.0),
+                ("lillard", 36.0, 30.0, 3.0),
+                ("young", 36.0, 18.0, 6.0),
+            ],
+            &[("curry", 36.0, 36.0, 2.0), ("jokic", 36.0, 30.0, 3.0)],
+        ];
+        for (day, lines) in lines.iter().enumerate() {
+            let mut game: Game = game(&day.to_string(), "gsw", "por", Some((100, 90)));
+            game.start += chrono::Duration::days(day as i64);
+            store.repo().upsert(&game).unwrap();
+            for (player, minutes, points, turnovers) in lines.iter() {
+                let mut line =
+                    StatLine::new(game.id.clone(), PlayerId::new(*player), TeamId::new("gsw"));
+                line.minutes = Some(*minutes);
+                line.set("pts", *points).set("tov", *turnovers);
+                store.repo().upsert(&line).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        seed(&db);
+
+        let output = run(&[
+            "compare", "curry", "lillard", "--db", &db, "--format", "csv",
+        ])
+        .unwrap();
+        assert_eq!(
+            output,
+            "stat,Stephen Curry,Damian Lillard,Stephen Curry pct,Damian Lillard pct,leader\n\
+             games,2,2,,,\n\
+             pts,26.67,25,100,66.67,Stephen Curry\n\
+             tov,3.33,2.5,66.67,100,Damian Lillard\n"
+        );
+
+        let per_game = run(&[
+            "compare", "curry", "lillard", "--db", &db, "--per", "game", "--stat", "pts",
+            "--format", "csv",
+        ])
+        .unwrap();
+        assert!(per_game.contains("pts,20,25,66.67,100,Damian Lillard"));
+    }
+
+    #[test]
+    fn test_compare_window() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        seed(&db);
+
+        let output = run(&[
+            "compare", "curry", "jokic", "--db", &db, "--stat", "pts", "--format", "csv",
+        ])
+        .unwrap();
+        assert!(output.contains("games,1,1,,,"));
+        assert!(output.contains("pts,36,30,100,100,Stephen Curry"));
+        assert!(run(&["compare", "curry", "--db", &db]).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use super::db_path;
+use crate::{
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        matches::ArgMatches,
+        output::{Output, Table},
+    },
+    store::Store,
+};
+use std::error::Error;
+
+/// Registers `statpack db migrate` and `statpack db status`
+pub(super) fn register(cli: &mut CLI<'static>) {
+    let db = cli.create_command("db");
+    db.create_command("migrate").set_handler(migrate);
+    db.create_command("status").set_handler(status);
+}
+
+fn migrate(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let mut store = Store::open(&db_path(matches))?;
+    let mut table = Table::new(&["version", "name"]);
+    for migration in store.migrate()? {
+        table.push(vec![migration.version.into(), migration.name.into()]);
+    }
+    Ok(Box::new(table))
+}
+
+fn status(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let store = Store::open(&db_path(matches))?;
+    let mut table = Table::new(&["version", "name", "applied_at"]);
+    for item in store.status()? {
+        table.push(vec![
+            item.migration.version.into(),
+            item.migration.name.into(),
+            item.applied_at
+                .unwrap_or_else(|| "pending".to_string())
+                .into(),
+        ]);
+    }
+    Ok(Box::new(table))
+}
+
+#[cfg(test)]
+mod db_tests {
+    use super::*;
+    use crate::{commands::mock::run, store::migration::MIGRATIONS};
+    use std::path::Path;
+
+    #[test]
+    fn test_db_status_and_migrate() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = dir.path().join("nested/statpack.db");
+        let db = db.to_str().unwrap();
+
+        let pending = run(&["db", "status", "--db", db, "--format", "csv"]).unwrap();
+        assert!(pending.lines().nth(1).unwrap().ends_with(",pending"));
+
+        let applied = run(&["db", "migrate", "--db", db, "--format", "csv"]).unwrap();
+        assert_eq!(applied.lines().count(), MIGRATIONS.len() + 1);
+        assert!(Store::open(Path::new(db))
+            .unwrap()
+            .ensure_migrated()
+            .is_ok());
+
+        let status = run(&["db", "status", "--db", db, "--format", "csv"]).unwrap();
+        assert!(!status.contains("pending"));
+    }
+}
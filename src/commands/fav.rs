@@ -0,0 +1,169 @@
+use super::store;
+use crate::{
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        error::CLIError,
+        matches::ArgMatches,
+        output::{Output, Table},
+    },
+    model::{
+        favorite::{Favorite, FavoriteKind},
+        sport::{League, Sport},
+        team::{Player, Team},
+    },
+    stats::resolve::resolve,
+    store::{record::Record, Store},
+};
+use chrono::Utc;
+use std::error::Error;
+
+/// Registers `statpack fav add|remove <kind> <name>` and `statpack fav list`
+pub(super) fn register(cli: &mut CLI<'static>) {
+    let fav = cli.create_command("fav");
+    fav.create_command("add").set_handler(add);
+    fav.create_command("remove").set_handler(remove);
+    fav.create_command("list").set_handler(list);
+}
+
+/// Splits the positionals into the favorite kind and the name
+fn kind_and_name(matches: &ArgMatches) -> Result<(FavoriteKind, String), CLIError> {
+    let Some((kind, name)) = matches.positionals().split_first() else {
+        return Err(CLIError::MissingRequired("<kind>".to_string()));
+    };
+    let kind = kind.parse().map_err(|_| CLIError::InvalidChoice {
+        option: "<kind>".to_string(),
+        value: kind.to_string(),
+        choices: FavoriteKind::ALL.iter().map(ToString::to_string).collect(),
+    })?;
+    if name.is_empty() {
+        return Err(CLIError::MissingRequired("<name>".to_string()));
+    }
+    Ok((kind, name.join(" ")))
+}
+
+fn add(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let store = store(matches)?;
+    let (kind, name) = kind_and_name(matches)?;
+    let (id, name) = match kind {
+        FavoriteKind::Sport => {
+            let sport: Sport = name.parse().map_err(|_| CLIError::InvalidChoice {
+                option: "<name>".to_string(),
+                value: name.clone(),
+                choices: Sport::ALL.iter().map(ToString::to_string).collect(),
+            })?;
+            (sport.to_string(), sport.to_string())
+        }
+        FavoriteKind::League => {
+            let leagues = store.repo::<League>().all()?;
+            let league = resolve("league", &name, &leagues, |league| {
+                vec![league.name.as_str(), league.id.as_str()]
+            })?;
+            (league.id.to_string(), league.name.clone())
+        }
+        FavoriteKind::Team => {
+            let teams = store.repo::<Team>().all()?;
+            let team = resolve("team", &name, &teams, |team| team.names().collect())?;
+            (team.id.to_string(), team.name.clone())
+        }
+        FavoriteKind::Player => {
+            let players = store.repo::<Player>().all()?;
+            let player = resolve("player", &name, &players, |player| {
+                vec![player.name.as_str()]
+            })?;
+            (player.id.to_string(), player.name.clone())
+        }
+    };
+    // Adding a favorite again keeps the date it was first added
+    let favorite = Favorite {
+        kind,
+        id,
+        name,
+        added_at: Utc::now(),
+    };
+    if store.repo::<Favorite>().get(&favorite.key())?.is_none() {
+        store.repo().upsert(&favorite)?;
+    }
+    Ok(Box::new(favorites_table(&store)?))
+}
+
+fn remove(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let store = store(matches)?;
+    let (kind, name) = kind_and_name(matches)?;
+    let favorites = store.repo::<Favorite>().find("kind", kind.name())?;
+    let favorite = resolve("favorite", &name, &favorites, |favorite| {
+        vec![favorite.name.as_str(), favorite.id.as_str()]
+    })?;
+    store.repo::<Favorite>().delete(&favorite.key())?;
+    Ok(Box::new(favorites_table(&store)?))
+}
+
+fn list(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    Ok(Box::new(favorites_table(&store(matches)?)?))
+}
+
+/// Lists every favorite grouped by kind
+fn favorites_table(store: &Store) -> Result<Table, Box<dyn Error>> {
+    let mut table = Table::new(&["kind", "id", "name", "added"]);
+    table.set_title("favorites");
+    for kind in FavoriteKind::ALL {
+        for favorite in store.repo::<Favorite>().find("kind", kind.name())? {
+            table.push(vec![
+                kind.name().into(),
+                favorite.id.into(),
+                favorite.name.into(),
+                favorite.added_at.date_naive().to_string().into(),
+            ]);
+        }
+    }
+    Ok(table)
+}
+
+#[cfg(test)]
+mod fav_tests {
+    use crate::commands::{
+        mock::{db, run},
+        team::mock::seed,
+    };
+    use chrono::Utc;
+
+    #[test]
+    fn test_fav() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        seed(&db);
+        let today = Utc::now().date_naive();
+
+        run(&["fav", "add", "team", "lakers", "--db", &db]).unwrap();
+        run(&["fav", "add", "sport", "basketball", "--db", &db]).unwrap();
+        run(&["fav", "add", "team", "lakers", "--db", &db]).unwrap();
+        assert_eq!(
+            run(&["fav", "add", "league", "NBA", "--db", &db, "--format", "csv"]).unwrap(),
+            format!(
+                "kind,id,name,added\n\
+                 sport,basketball,basketball,{0}\n\
+                 league,nba,NBA,{0}\n\
+                 team,lal,Lakers,{0}\n",
+                today
+            )
+        );
+
+        assert_eq!(
+            run(&["fav", "remove", "team", "lak", "--db", &db, "--format", "csv"]).unwrap(),
+            format!(
+                "kind,id,name,added\n\
+                 sport,basketball,basketball,{0}\n\
+                 league,nba,NBA,{0}\n",
+                today
+            )
+        );
+        assert_eq!(
+            run(&["fav", "remove", "team", "lakers", "--db", &db])
+                .unwrap_err()
+                .to_string(),
+            "no favorite named 'lakers'"
+        );
+        assert!(run(&["fav", "add", "coach", "phil", "--db", &db]).is_err());
+        assert!(run(&["fav", "add", "sport", "curling", "--db", &db]).is_err());
+    }
+}
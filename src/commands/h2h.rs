@@ -0,0 +1,257 @@
+use super::{game_odds, round, store};
+use crate::{
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        error::CLIError,
+        matches::ArgMatches,
+        option::{CommandOptionKwargs, CommandOptionKwargsBuilder, CommandOptionType},
+        output::{Output, Table},
+    },
+    model::{
+        game::{Game, Outcome},
+        id::{TeamId, VenueId},
+        odds::{Market, Selection},
+        team::{Team, Venue},
+    },
+    stats::{
+        h2h::{against_the_spread, ats_record, closing_line, meetings, over_under, totals_record},
+        resolve::resolve,
+        team::TeamSummary,
+    },
+};
+use std::{collections::HashMap, error::Error, sync::LazyLock};
+
+static LAST: CommandOptionType = CommandOptionType::Int(5);
+static LAST_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_kind(&LAST)
+        .set_default(&LAST)
+        .build()
+});
+
+/// Registers `statpack h2h <team> <team> [--last]`
+pub(super) fn register(cli: &mut CLI<'static>) {
+    cli.create_command("h2h")
+        .set_handler(run)
+        .create_option_kwargs(
+            "last",
+            "Number of recent meetings in the 'last' record",
+            &LAST_KWARGS,
+        );
+}
+
+fn run(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let store = store(matches)?;
+    let [first, second] = matches.positionals() else {
+        return Err(CLIError::MissingValue {
+            option: "<team>".to_string(),
+            expected: 2,
+        }
+        .into());
+    };
+    let teams = store.repo::<Team>().all()?;
+    let team = resolve("team", first, &teams, |team| team.names().collect())?;
+    let opponent = resolve("team", second, &teams, |team| team.names().collect())?;
+    let last: usize = matches.value_of("last")?.unwrap_or(5);
+
+    let games = store.repo::<Game>().for_team(&team.id)?;
+    let meetings = meetings(&games, &team.id, &opponent.id);
+    let odds = game_odds(&store, meetings.iter().copied())?;
+    let venues: HashMap<VenueId, String> = store
+        .repo::<Venue>()
+        .all()?
+        .into_iter()
+        .map(|venue| (venue.id, venue.name))
+        .collect();
+    let short = |team: &Team| {
+        team.abbreviation
+            .clone()
+            .unwrap_or_else(|| team.name.clone())
+    };
+
+    let mut table = Table::new(&[
+        "date", "home", "away", "score", "winner", "venue", "spread", "total", "ats", "o/u",
+    ]);
+    table.set_title(&format!("{} vs {}", team.name, opponent.name));
+    for game in &meetings {
+        let name = |id: &TeamId| {
+            if *id == team.id {
+                short(team)
+            } else {
+                short(opponent)
+            }
+        };
+        let winner = match game.outcome_for(&team.id) {
+            Some(Outcome::Win) => Some(short(team)),
+            Some(Outcome::Loss) => Some(short(opponent)),
+            _ => None,
+        };
+        // Lines are shown from the point of view of the first team
+        let spread = closing_line(game, &odds, &Market::Spread, Selection::Home).map(|spread| {
+            if game.home == team.id {
+                spread
+            } else {
+                -spread
+            }
+        });
+        let total = closing_line(game, &odds, &Market::Total, Selection::Over);
+        table.push(vec![
+            game.start.date_naive().to_string().into(),
+            name(&game.home).into(),
+            name(&game.away).into(),
+            game.home_score
+                .zip(game.away_score)
+                .map(|(home, away)| format!("{}-{}", home, away))
+                .into(),
+            winner.into(),
+            game.venue
+                .as_ref()
+                .map(|venue| {
+                    venues
+                        .get(venue)
+                        .cloned()
+                        .unwrap_or_else(|| venue.to_string())
+                })
+                .into(),
+            spread.into(),
+            total.into(),
+            closing_line(game, &odds, &Market::Spread, Selection::Home)
+                .and_then(|spread| against_the_spread(game, &team.id, spread))
+                .map(|outcome| match outcome {
+                    Outcome::Draw => "P".to_string(),
+                    outcome => outcome.letter().to_string(),
+                })
+                .into(),
+            total
+                .map(|total| match over_under(game, total) {
+                    Some(Selection::Over) => "O",
+                    Some(_) => "U",
+                    None => "P",
+                })
+                .into(),
+        ]);
+    }
+
+    let mut records = Table::new(&[
+        "split", "gp", "w", "l", "d", "pf", "pa", "pct", "ats", "o/u",
+    ]);
+    records.set_title(&format!("{} record", team.name));
+    let mut splits = vec![
+        ("overall".to_string(), meetings.clone()),
+        (
+            format!("last {}", last),
+            meetings[meetings.len().saturating_sub(last)..].to_vec(),
+        ),
+    ];
+    let mut at_venue: Vec<(&VenueId, Vec<&Game>)> = vec![];
+    for game in &meetings {
+        let Some(venue) = &game.venue else {
+            continue;
+        };
+        match at_venue.iter_mut().find(|(id, _)| *id == venue) {
+            Some((_, games)) => games.push(game),
+            None => at_venue.push((venue, vec![game])),
+        }
+    }
+    splits.extend(at_venue.into_iter().map(|(venue, games)| {
+        (
+            format!(
+                "at {}",
+                venues
+                    .get(venue)
+                    .cloned()
+                    .unwrap_or_else(|| venue.to_string())
+            ),
+            games,
+        )
+    }));
+    for (split, games) in splits {
+        let record = TeamSummary::new(&team.id, games.iter().copied()).overall;
+        let ats = ats_record(games.iter().copied(), &team.id, &odds);
+        let totals = totals_record(games.iter().copied(), &odds);
+        records.push(vec![
+            split.into(),
+            record.played().into(),
+            record.wins.into(),
+            record.losses.into(),
+            record.draws.into(),
+            record.scored.into(),
+            record.conceded.into(),
+            record.win_percentage().map(round).into(),
+            (ats.graded() > 0).then(|| ats.to_string()).into(),
+            (totals.graded() > 0).then(|| totals.to_string()).into(),
+        ]);
+    }
+    Ok(Box::new(vec![table, records]))
+}
+
+#[cfg(test)]
+mod h2h_command_tests {
+    use crate::{
+        commands::{
+            mock::{db, run},
+            team::mock::seed,
+        },
+        model::{game::Game, id::VenueId, odds::Market, team::Venue},
+        stats::h2h::mock::line,
+        store::Store,
+    };
+    use std::path::Path;
+
+    #[test]
+    fn test_h2h() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        seed(&db);
+
+        assert_eq!(
+            run(&["h2h", "lakers", "nuggets", "--db", &db, "--format", "csv"]).unwrap(),
+            "date,home,away,score,winner,venue,spread,total,ats,o/u\n\
+             2025-01-02,DEN,LAL,120-101,DEN,,,,,\n\
+             2025-01-03,LAL,DEN,99-98,LAL,,,,,\n\
+             \n\
+             split,gp,w,l,d,pf,pa,pct,ats,o/u\n\
+             overall,2,1,1,0,200,218,0.5,,\n\
+             last 5,2,1,1,0,200,218,0.5,,\n"
+        );
+        assert!(run(&["h2h", "lakers", "--db", &db]).is_err());
+    }
+
+    #[test]
+    fn test_h2h_odds_and_venues() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        seed(&db);
+        let store = Store::open(Path::new(&db)).unwrap();
+        store
+            .repo()
+            .upsert(&Venue {
+                id: VenueId::new("ball-arena"),
+                name: "Ball Arena".to_string(),
+                city: None,
+                country: None,
+                capacity: None,
+            })
+            .unwrap();
+        let mut game = store.repo::<Game>().get("2").unwrap().unwrap();
+        game.venue = Some(VenueId::new("ball-arena"));
+        store.repo().upsert(&game).unwrap();
+        store
+            .repo()
+            .upsert_all(&[
+                line(&game, "pinnacle", Market::Spread, -4.5, 2),
+                line(&game, "pinnacle", Market::Total, 225.5, 2),
+            ])
+            .unwrap();
+
+        let output = run(&[
+            "h2h", "lakers", "nuggets", "--db", &db, "--last", "1", "--format", "csv",
+        ])
+        .unwrap();
+        assert!(output.contains("2025-01-02,DEN,LAL,120-101,DEN,Ball Arena,4.5,225.5,L,U\n"));
+        assert!(output.contains("overall,2,1,1,0,200,218,0.5,0-1-0,0-1-0\n"));
+        assert!(output.contains("last 1,1,1,0,0,99,98,1,,\n"));
+        assert!(output.contains("at Ball Arena,1,0,1,0,101,120,0,0-1-0,0-1-0\n"));
+    }
+}
@@ -0,0 +1,167 @@
+use super::{store, texts};
+use crate::{
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        matches::ArgMatches,
+        option::{CommandOptionKwargs, CommandOptionKwargsBuilder, CommandOptionType},
+        output::{Output, Table},
+    },
+    import::{self, reader::read_file, ImportReport},
+    model::sport::Sport,
+};
+use std::{error::Error, path::Path, sync::LazyLock};
+
+static FILE: CommandOptionType = CommandOptionType::File(String::new());
+static SPORTS: LazyLock<Vec<CommandOptionType>> = LazyLock::new(|| texts(Sport::ALL));
+static SPORT_CHOICES: LazyLock<Vec<&'static CommandOptionType>> =
+    LazyLock::new(|| SPORTS.iter().collect());
+static SPORT_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_required()
+        .set_shared_choices(&SPORT_CHOICES)
+        .build()
+});
+static FILE_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_required()
+        .set_kind(&FILE)
+        .build()
+});
+
+/// Registers `statpack import --sport <sport> --file <path> [--league <name>] [--dry-run]`
+pub(super) fn register(cli: &mut CLI<'static>) {
+    cli.create_command("import")
+        .set_handler(run)
+        .create_option_kwargs(
+            "sport",
+            "Sport whose column profile the file uses",
+            &SPORT_KWARGS,
+        )
+        .create_option_kwargs(
+            "file",
+            "Path of the .csv or .json file to import",
+            &FILE_KWARGS,
+        )
+        .create_option(
+            "league",
+            "League of every row, for files without a league column",
+        )
+        .create_option_kwargs(
+            "dry-run",
+            "Validate and count the rows without storing anything",
+            &CommandOptionKwargs::FLAG,
+        );
+}
+
+fn run(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let sport: Sport = matches.value("sport").unwrap_or_default().parse()?;
+    let rows = read_file(Path::new(matches.value("file").unwrap_or_default()))?;
+    let league = matches.value("league");
+    let dry_run = matches.flag("dry-run");
+
+    let mut store = store(matches)?;
+    let report = if dry_run {
+        store.dry_run(|tx| import::import(tx, sport, league, &rows))?
+    } else {
+        store.transaction(|tx| import::import(tx, sport, league, &rows))?
+    };
+    Ok(Box::new(tables(&report, dry_run)))
+}
+
+/// Lays the report out as a summary table and a table of the invalid rows
+fn tables(report: &ImportReport, dry_run: bool) -> Vec<Table> {
+    let mut summary = Table::new(&[
+        "rows",
+        "inserted",
+        "updated",
+        "duplicates",
+        "invalid",
+        "dry_run",
+    ]);
+    summary.set_title("summary").push(vec![
+        (report.rows as i64).into(),
+        (report.inserted as i64).into(),
+        (report.updated as i64).into(),
+        (report.duplicates as i64).into(),
+        (report.errors.len() as i64).into(),
+        dry_run.into(),
+    ]);
+
+    let mut errors = Table::new(&["row", "error"]);
+    errors.set_title("errors");
+    for error in &report.errors {
+        errors.push(vec![
+            (error.row as i64).into(),
+            error.message.clone().into(),
+        ]);
+    }
+    vec![summary, errors]
+}
+
+#[cfg(test)]
+mod import_tests {
+    use crate::commands::mock::{db, run};
+    use std::fs;
+
+    #[test]
+    fn test_import_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        let file = dir.path().join("games.csv");
+        fs::write(
+            &file,
+            "date,home,away,home_score,away_score\n\
+             2025-01-01,Lakers,Celtics,110,102\n\
+             2025-01-02,Lakers,,99,90\n",
+        )
+        .unwrap();
+        let file = file.to_str().unwrap();
+        let import = |extra: &[&str]| {
+            let mut args = vec![
+                "import",
+                "--sport",
+                "basketball",
+                "--file",
+                file,
+                "--league",
+                "NBA",
+                "--db",
+                &db,
+                "--format",
+                "csv",
+            ];
+            args.extend(extra);
+            run(&args).unwrap()
+        };
+
+        assert_eq!(
+            import(&["--dry-run"]),
+            "rows,inserted,updated,duplicates,invalid,dry_run\n2,1,0,0,1,true\n\n\
+             row,error\n3,missing value for 'away'\n"
+        );
+        assert!(import(&[]).starts_with("rows,inserted,updated,duplicates,invalid,dry_run\n2,1,0"));
+        assert!(import(&[]).starts_with("rows,inserted,updated,duplicates,invalid,dry_run\n2,0,1"));
+    }
+
+    #[test]
+    fn test_import_command_invalid_args() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+
+        let missing = run(&[
+            "import", "--sport", "soccer", "--file", "nope.csv", "--db", &db,
+        ]);
+        assert_eq!(
+            missing.unwrap_err().to_string(),
+            "invalid value 'nope.csv' for '--file', expected an existing file"
+        );
+        let sport = run(&[
+            "import", "--sport", "curling", "--file", "nope.csv", "--db", &db,
+        ]);
+        assert!(sport
+            .unwrap_err()
+            .to_string()
+            .contains("expected one of: soccer"));
+    }
+}
@@ -0,0 +1,299 @@
+use crate::{
+    build_info,
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        error::CLIError,
+        matches::ArgMatches,
+        option::{CommandOptionKwargs, CommandOptionKwargsBuilder, CommandOptionType},
+        output::Cell,
+        parser::Parser,
+    },
+    model::{
+        game::Game,
+        odds::OddsSnapshot,
+        sport::{League, Sport},
+    },
+    odds::{error::OddsError, Odds, OddsFormat},
+    provider::{fixture::FixtureProvider, http::HttpProvider, DataProvider},
+    stats::{error::StatsError, player::GameLine},
+    store::{error::StoreError, Store},
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::{env, error::Error, path::PathBuf, sync::LazyLock};
+
+mod backtest;
+mod bets;
+mod calc;
+mod compare;
+mod db;
+mod fav;
+mod h2h;
+mod import;
+mod odds;
+mod player;
+mod stake;
+mod standings;
+mod sync;
+mod team;
+mod today;
+mod trends;
+mod version;
+
+/// Environment variable holding the database path when `--db` is not given
+const DB_ENV: &str = "STATPACK_DB";
+/// Environment variable holding the data API URL when `--api-url` is not given
+const API_URL_ENV: &str = "STATPACK_API_URL";
+/// Environment variable holding the data API key when `--api-key` is not given
+const API_KEY_ENV: &str = "STATPACK_API_KEY";
+
+/// Values accepted by the `--odds-format` option
+static ODDS_FORMATS: LazyLock<Vec<CommandOptionType>> = LazyLock::new(|| texts(OddsFormat::ALL));
+static ODDS_FORMAT_CHOICES: LazyLock<Vec<&'static CommandOptionType>> =
+    LazyLock::new(|| ODDS_FORMATS.iter().collect());
+static ODDS_FORMAT_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_shared_choices(&ODDS_FORMAT_CHOICES)
+        .set_default(&ODDS_FORMATS[0])
+        .build()
+});
+
+/// Builds the `statpack` command-line interface with every command registered
+pub fn cli() -> CLI<'static> {
+    let mut cli = CLI::new("statpack");
+    cli.version(build_info::VERSION)
+        .format()
+        .create_option("db", "Path of the local stats database (env: STATPACK_DB)")
+        .create_option(
+            "api-url",
+            "Base URL of the data API (env: STATPACK_API_URL)",
+        )
+        .create_option("api-key", "Key of the data API (env: STATPACK_API_KEY)")
+        .create_option(
+            "as-of",
+            "Show only what was known at a past time, YYYY-MM-DD (start of the day, UTC) or \
+             RFC 3339",
+        );
+
+    cli.create_option_kwargs(
+        "odds-format",
+        "Odds format of prices: decimal, american, fractional, hong-kong, indonesian, malay \
+         or probability",
+        &ODDS_FORMAT_KWARGS,
+    );
+
+    backtest::register(&mut cli);
+    bets::register(&mut cli);
+    calc::register(&mut cli);
+    compare::register(&mut cli);
+    db::register(&mut cli);
+    fav::register(&mut cli);
+    h2h::register(&mut cli);
+    import::register(&mut cli);
+    odds::register(&mut cli);
+    player::register(&mut cli);
+    standings::register(&mut cli);
+    stake::register(&mut cli);
+    sync::register(&mut cli);
+    team::register(&mut cli);
+    today::register(&mut cli);
+    trends::register(&mut cli);
+    version::register(&mut cli);
+
+    cli.sort();
+    cli
+}
+
+/// Resolves the database path from `--db`, then `STATPACK_DB`, then the user data directory
+fn db_path(matches: &ArgMatches) -> PathBuf {
+    if let Some(path) = matches.value("db") {
+        return PathBuf::from(path);
+    }
+    if let Ok(path) = env::var(DB_ENV) {
+        return PathBuf::from(path);
+    }
+    env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .map_or_else(
+            || PathBuf::from("statpack.db"),
+            |data| data.join("statpack").join("statpack.db"),
+        )
+}
+
+/// Returns the value of an option, falling back on an environment variable
+fn value_or_env(matches: &ArgMatches, option: &str, env: &str) -> Option<String> {
+    matches
+        .value(option)
+        .map(str::to_string)
+        .or_else(|| env::var(env).ok().filter(|value| !value.is_empty()))
+}
+
+/// Resolves the data source, recorded fixtures from `--fixtures` or the API at `--api-url`
+///
+/// API responses are cached under the user cache directory.
+fn provider(matches: &ArgMatches) -> Result<Box<dyn DataProvider>, CLIError> {
+    if let Some(dir) = matches.value("fixtures") {
+        return Ok(Box::new(FixtureProvider::new(dir)));
+    }
+    let url = value_or_env(matches, "api-url", API_URL_ENV)
+        .ok_or_else(|| CLIError::MissingRequired("--api-url".to_string()))?;
+
+    let mut provider = HttpProvider::new(&url);
+    if let Some(api_key) = value_or_env(matches, "api-key", API_KEY_ENV) {
+        provider.set_api_key(&api_key);
+    }
+    if let Some(cache) = env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+    {
+        provider.set_cache(cache.join("statpack").join("http"));
+    }
+    Ok(Box::new(provider))
+}
+
+/// Opens the database and makes sure its schema is up to date, looking back to `--as-of`
+/// when given
+fn store(matches: &ArgMatches) -> Result<Store, Box<dyn Error>> {
+    let mut store = Store::open(&db_path(matches))?;
+    store.ensure_migrated()?;
+    if let Some(at) = as_of(matches)? {
+        store.set_as_of(at)?;
+    }
+    Ok(store)
+}
+
+/// Returns the time given with `--as-of`, a date standing for the start of its day in UTC
+fn as_of(matches: &ArgMatches) -> Result<Option<DateTime<Utc>>, CLIError> {
+    let Some(value) = matches.value("as-of") else {
+        return Ok(None);
+    };
+    value
+        .parse::<NaiveDate>()
+        .map(|date| date.and_time(Default::default()).and_utc())
+        .or_else(|_| DateTime::parse_from_rfc3339(value).map(|at| at.to_utc()))
+        .map(Some)
+        .map_err(|_| CLIError::InvalidValue {
+            option: "--as-of".to_string(),
+            value: value.to_string(),
+            expected: "a date (YYYY-MM-DD) or an RFC 3339 time".to_string(),
+        })
+}
+
+/// Returns the present of the store, the `--as-of` time when it looks back
+fn now(store: &Store) -> DateTime<Utc> {
+    store.as_of().unwrap_or_else(Utc::now)
+}
+
+/// Returns the sport of the league the lines were recorded in
+fn sport(store: &Store, lines: &[GameLine]) -> Result<Option<Sport>, StoreError> {
+    let Some(line) = lines.first() else {
+        return Ok(None);
+    };
+    Ok(store
+        .repo::<League>()
+        .get(line.game.league.as_str())?
+        .map(|league| league.sport))
+}
+
+/// Returns the stored game with the ID
+fn game(store: &Store, id: &str) -> Result<Game, Box<dyn Error>> {
+    match store.repo::<Game>().get(id)? {
+        Some(game) => Ok(game),
+        None => Err(StatsError::NotFound {
+            kind: "game",
+            query: id.to_string(),
+            suggestions: vec![],
+        }
+        .into()),
+    }
+}
+
+/// Returns the stored odds of the games
+fn game_odds<'g>(
+    store: &Store,
+    games: impl IntoIterator<Item = &'g Game>,
+) -> Result<Vec<OddsSnapshot>, StoreError> {
+    let mut odds = vec![];
+    for game in games {
+        odds.extend(store.repo::<OddsSnapshot>().for_game(game.id.as_str())?);
+    }
+    Ok(odds)
+}
+
+/// Returns the odds format chosen with `--odds-format`
+fn odds_format(matches: &ArgMatches) -> OddsFormat {
+    matches
+        .value("odds-format")
+        .and_then(|format| format.parse().ok())
+        .unwrap_or(OddsFormat::Decimal)
+}
+
+/// Returns every value of an odds option as decimal odds, reading them in the odds format
+fn decimal_odds(matches: &ArgMatches, option: &str) -> Result<Vec<f64>, OddsError> {
+    let format = odds_format(matches);
+    matches
+        .values(option)
+        .iter()
+        .map(|value| Ok(Odds::parse(format, value)?.decimal().to_f64()))
+        .collect()
+}
+
+/// Returns every value of a probability option, each between `0` and `1`
+fn probabilities(matches: &ArgMatches, option: &str) -> Result<Vec<f64>, CLIError> {
+    matches
+        .values(option)
+        .iter()
+        .map(|value| {
+            value
+                .parse::<f64>()
+                .ok()
+                .filter(|probability| (0.0..=1.0).contains(probability))
+                .ok_or_else(|| CLIError::InvalidValue {
+                    option: format!("--{}", option),
+                    value: value.clone(),
+                    expected: "a probability between 0 and 1".to_string(),
+                })
+        })
+        .collect()
+}
+
+/// Quotes a decimal price in the odds format
+fn price(format: OddsFormat, price: f64) -> Cell {
+    Odds::from_price(price)
+        .map(|odds| odds.format(format))
+        .into()
+}
+
+/// Rounds a number to two decimals for display
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Returns the text values of an option choosing between names
+fn texts(names: impl IntoIterator<Item = impl ToString>) -> Vec<CommandOptionType> {
+    names
+        .into_iter()
+        .map(|name| CommandOptionType::Text(name.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::*;
+    use std::error::Error;
+
+    /// Runs the statpack CLI and returns what it printed
+    pub(crate) fn run(args: &[&str]) -> Result<String, Box<dyn Error>> {
+        let mut out = vec![];
+        cli().run_with(args.iter().map(|arg| arg.to_string()), &mut out)?;
+        Ok(String::from_utf8(out)?)
+    }
+
+    /// Creates a migrated database in `dir` and returns its path
+    pub(crate) fn db(dir: &tempfile::TempDir) -> String {
+        let path = dir.path().join("statpack.db");
+        Store::open(&path).unwrap().migrate().unwrap();
+        path.to_str().unwrap().to_string()
+    }
+}
@@ -0,0 +1,594 @@
+use super::{decimal_odds, game, odds_format, price, probabilities, provider, round, store, texts};
+use crate::{
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        error::CLIError,
+        matches::ArgMatches,
+        option::{CommandOptionKwargs, CommandOptionKwargsBuilder, CommandOptionType},
+        output::{Cell, Output, Table},
+    },
+    model::{game::GameStatus, id::LeagueId, odds::OddsSnapshot},
+    odds::{
+        market::compare_books,
+        vig::{margin, Method},
+        OddsFormat,
+    },
+    provider::error::ProviderError,
+    stats::lines::line_histories,
+    store::repository::Repository,
+};
+use chrono::{NaiveDate, Utc};
+use std::{collections::HashSet, error::Error, sync::LazyLock};
+
+static AMOUNT: CommandOptionType = CommandOptionType::Float(0.0);
+static PRICE: CommandOptionType = CommandOptionType::Text(String::new());
+static PRICE_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_required()
+        .set_kind(&PRICE)
+        .build()
+});
+static PROBABILITY_KWARGS: LazyLock<CommandOptionKwargs<'static>> =
+    LazyLock::new(|| CommandOptionKwargsBuilder::new().set_kind(&AMOUNT).build());
+static METHODS: LazyLock<Vec<CommandOptionType>> = LazyLock::new(|| texts(Method::ALL));
+static METHOD_CHOICES: LazyLock<Vec<&'static CommandOptionType>> =
+    LazyLock::new(|| METHODS.iter().collect());
+static METHOD_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_shared_choices(&METHOD_CHOICES)
+        .build()
+});
+
+/// Registers `statpack odds sync|history|compare|fair`
+pub(super) fn register(cli: &mut CLI<'static>) {
+    let odds = cli.create_command("odds");
+    odds.create_command("sync")
+        .set_handler(sync)
+        .create_option_kwargs(
+            "league",
+            "League whose games are priced",
+            &CommandOptionKwargs::REQUIRED,
+        )
+        .create_option("date", "Day of the games, YYYY-MM-DD (defaults to today)")
+        .create_option(
+            "fixtures",
+            "Replay recorded responses from the directory instead of the API",
+        );
+    odds.create_command("history")
+        .set_handler(history)
+        .create_option("book", "Only prices of the bookmaker")
+        .create_option(
+            "market",
+            "Only prices of the market: moneyline, spread, total or prop",
+        );
+    odds.create_command("compare")
+        .set_handler(compare)
+        .create_option_kwargs(
+            "game",
+            "Game whose prices are compared, by ID",
+            &CommandOptionKwargs::REQUIRED,
+        );
+    odds.create_command("fair")
+        .set_handler(fair)
+        .create_option_kwargs(
+            "odds",
+            "Odds of a selection, once per selection of the market",
+            &PRICE_KWARGS,
+        )
+        .create_option_kwargs(
+            "method",
+            "Only this method: multiplicative, additive, power or shin",
+            &METHOD_KWARGS,
+        )
+        .create_option_kwargs(
+            "prob",
+            "Model probability of a selection (0-1), once per selection, to get the edge",
+            &PROBABILITY_KWARGS,
+        );
+}
+
+/// Stores a snapshot of the current prices of every game that has not finished yet.
+/// Snapshots accumulate, so syncing regularly records how lines move.
+fn sync(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let league = LeagueId::new(matches.value("league").unwrap_or_default());
+    let date = matches
+        .value_of::<NaiveDate>("date")?
+        .unwrap_or_else(|| Utc::now().date_naive());
+    let provider = provider(matches)?;
+    let mut store = store(matches)?;
+
+    let games = provider.schedule(&league, date)?;
+    let mut odds = vec![];
+    for game in &games {
+        if game.status == GameStatus::Final {
+            continue;
+        }
+        match provider.odds(&game.id) {
+            Ok(snapshots) => odds.push(snapshots),
+            // Books take games off the board once they start
+            Err(ProviderError::NotFound(_)) => odds.push(vec![]),
+            Err(error) => return Err(error.into()),
+        }
+    }
+
+    store.transaction(|tx| {
+        Repository::new(tx).upsert_all(&games)?;
+        Repository::new(tx).upsert_all(odds.iter().flatten())?;
+        Ok(())
+    })?;
+
+    let mut table = Table::new(&["game", "home", "away", "books", "prices"]);
+    for (game, snapshots) in games
+        .iter()
+        .filter(|game| game.status != GameStatus::Final)
+        .zip(&odds)
+    {
+        let books: HashSet<&str> = snapshots
+            .iter()
+            .map(|snapshot| snapshot.book.as_str())
+            .collect();
+        table.push(vec![
+            game.id.to_string().into(),
+            game.home.to_string().into(),
+            game.away.to_string().into(),
+            (books.len() as i64).into(),
+            (snapshots.len() as i64).into(),
+        ]);
+    }
+    Ok(Box::new(table))
+}
+
+/// Lists the opening and current line of every book and market, then each move
+fn history(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let store = store(matches)?;
+    let id = matches.positionals().join(" ");
+    if id.is_empty() {
+        return Err(CLIError::MissingRequired("<game>".to_string()).into());
+    }
+    let game = game(&store, &id)?;
+
+    let odds: Vec<OddsSnapshot> = store
+        .repo::<OddsSnapshot>()
+        .for_game(game.id.as_str())?
+        .into_iter()
+        .filter(|snapshot| {
+            matches
+                .value("book")
+                .is_none_or(|book| snapshot.book == book)
+        })
+        .filter(|snapshot| {
+            matches.value("market").is_none_or(|market| {
+                let name = snapshot.market.to_string();
+                name == market || name.starts_with(&format!("{}:", market))
+            })
+        })
+        .collect();
+    let histories = line_histories(&odds);
+    let format = odds_format(matches);
+
+    let mut lines = Table::new(&[
+        "book",
+        "market",
+        "selection",
+        "open_line",
+        "open_price",
+        "line",
+        "price",
+        "line_move",
+        "price_move",
+        "updated_at",
+    ]);
+    lines.set_title(&format!("{} at {}", game.away, game.home));
+    let mut moves = Table::new(&["taken_at", "book", "market", "selection", "line", "price"]);
+    moves.set_title("movement");
+    for history in &histories {
+        let (opening, current) = (history.opening(), history.current());
+        lines.push(vec![
+            history.book.as_str().into(),
+            history.market.to_string().into(),
+            history.selection.name().into(),
+            opening.line.into(),
+            price(format, opening.price),
+            current.line.into(),
+            price(format, current.price),
+            history.line_move().map(round).into(),
+            round(history.price_move()).into(),
+            current.taken_at.to_rfc3339().into(),
+        ]);
+    }
+
+    let mut changes: Vec<&OddsSnapshot> = histories
+        .iter()
+        .flat_map(|history| history.moves())
+        .collect();
+    changes.sort_by_key(|snapshot| snapshot.taken_at);
+    for snapshot in changes {
+        moves.push(vec![
+            snapshot.taken_at.to_rfc3339().into(),
+            snapshot.book.as_str().into(),
+            snapshot.market.to_string().into(),
+            snapshot.selection.name().into(),
+            snapshot.line.into(),
+            price(format, snapshot.price),
+        ]);
+    }
+    Ok(Box::new(vec![lines, moves]))
+}
+
+/// Lists the best price of every selection across books, the no-vig consensus and the
+/// markets where backing every side at its best price guarantees a profit
+fn compare(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let store = store(matches)?;
+    let game = game(&store, matches.value("game").unwrap_or_default())?;
+    let format = odds_format(matches);
+    let odds = store.repo::<OddsSnapshot>().for_game(game.id.as_str())?;
+
+    let mut table = Table::new(&[
+        "market",
+        "line",
+        "selection",
+        "best",
+        "book",
+        "consensus",
+        "fair",
+        "books",
+        "arbitrage",
+        "stake",
+    ]);
+    table.set_title(&format!("{} at {}", game.away, game.home));
+    for comparison in compare_books(&odds) {
+        let stakes = comparison.arbitrage_stakes();
+        for (best, stake) in comparison.best.iter().zip(stakes) {
+            let fair = best.consensus.map(|consensus| 1.0 / consensus);
+            table.push(vec![
+                comparison.market.to_string().into(),
+                best.line.into(),
+                best.selection.name().into(),
+                price(format, best.price),
+                best.book.as_str().into(),
+                fair.map_or(Cell::Null, |fair| price(OddsFormat::Probability, fair)),
+                fair.map_or(Cell::Null, |fair| price(format, fair)),
+                (comparison.books as i64).into(),
+                comparison
+                    .arbitrage
+                    .map(|arbitrage| format!("{}%", round(arbitrage * 100.0)))
+                    .into(),
+                comparison
+                    .arbitrage
+                    .map(|_| format!("{}%", round(stake * 100.0)))
+                    .into(),
+            ]);
+        }
+    }
+    Ok(Box::new(table))
+}
+
+/// Lists the fair probability and odds of every selection of a market, under every
+/// margin removal method, and the edge of model probabilities over them
+fn fair(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let format = odds_format(matches);
+    let odds = decimal_odds(matches, "odds")?;
+    if odds.len() < 2 {
+        return Err(CLIError::MissingValue {
+            option: "--odds".to_string(),
+            expected: 2,
+        }
+        .into());
+    }
+    let model = probabilities(matches, "prob")?;
+    if !model.is_empty() && model.len() != odds.len() {
+        return Err(CLIError::MissingValue {
+            option: "--prob".to_string(),
+            expected: odds.len(),
+        }
+        .into());
+    }
+    let methods = match matches.value("method") {
+        Some(method) => vec![method.parse::<Method>()?],
+        None => Method::ALL.to_vec(),
+    };
+
+    let implied: Vec<f64> = odds.iter().map(|odds| 1.0 / odds).collect();
+    let mut table = Table::new(&[
+        "selection",
+        "odds",
+        "implied_pct",
+        "method",
+        "fair_pct",
+        "fair_odds",
+        "model_pct",
+        "edge_pct",
+    ]);
+    table.set_title(&format!("margin {}%", round(margin(&implied) * 100.0)));
+    for method in methods {
+        let fair = method.fair(&implied);
+        for (selection, (odds, fair)) in odds.iter().zip(fair).enumerate() {
+            let model = model.get(selection);
+            table.push(vec![
+                (selection as i64 + 1).into(),
+                price(format, *odds),
+                round(100.0 / odds).into(),
+                method.name().into(),
+                round(fair * 100.0).into(),
+                // The solvers leave noise past the 9th decimal, enough to quote an even
+                // price as -100 instead of +100
+                (fair > 0.0)
+                    .then(|| (1e9 / fair).round() / 1e9)
+                    .map_or(Cell::Null, |fair| price(format, fair)),
+                model.map(|model| round(model * 100.0)).into(),
+                model.map(|model| round((model - fair) * 100.0)).into(),
+            ]);
+        }
+    }
+    Ok(Box::new(table))
+}
+
+#[cfg(test)]
+mod odds_tests {
+    use crate::{
+        commands::mock::{db, run},
+        model::{
+            game::mock::game,
+            id::LeagueId,
+            odds::{Market, OddsSnapshot, Selection},
+        },
+        provider::{fixture::FixtureProvider, Resource},
+        store::Store,
+    };
+    use chrono::{Duration, NaiveDate};
+    use std::path::Path;
+
+    fn snapshot(
+        book: &str,
+        market: Market,
+        line: Option<f64>,
+        price: f64,
+        hours: i64,
+    ) -> OddsSnapshot {
+        let game = game("nba:1", "lal", "bos", None);
+        OddsSnapshot {
+            game: game.id,
+            book: book.to_string(),
+            selection: match market {
+                Market::Total => Selection::Over,
+                _ => Selection::Home,
+            },
+            market,
+            line,
+            price,
+            taken_at: game.start - Duration::hours(hours),
+        }
+    }
+
+    #[test]
+    fn test_odds_sync_and_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        let fixtures = FixtureProvider::new(dir.path().join("fixtures"));
+        let scheduled = game("nba:1", "lal", "bos", None);
+        let played = game("nba:2", "den", "mia", Some((99, 90)));
+        fixtures
+            .record(
+                &Resource::Schedule {
+                    league: &LeagueId::new("nba"),
+                    date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                },
+                &[&scheduled, &played],
+            )
+            .unwrap();
+        let sync = |odds: &[OddsSnapshot]| {
+            fixtures
+                .record(&Resource::Odds(&scheduled.id), &odds)
+                .unwrap();
+            run(&[
+                "odds",
+                "sync",
+                "--league",
+                "nba",
+                "--date",
+                "2025-01-01",
+                "--fixtures",
+                fixtures.root().to_str().unwrap(),
+                "--db",
+                &db,
+                "--format",
+                "csv",
+            ])
+            .unwrap()
+        };
+
+        assert_eq!(
+            sync(&[
+                snapshot("pinnacle", Market::Spread, Some(-3.0), 1.91, 24),
+                snapshot("pinnacle", Market::Total, Some(220.5), 1.91, 24),
+                snapshot("fanduel", Market::Moneyline, None, 1.6, 24),
+            ]),
+            "game,home,away,books,prices\n\
+             nba:1,lal,bos,2,3\n"
+        );
+        sync(&[snapshot("pinnacle", Market::Spread, Some(-3.0), 1.91, 12)]);
+        sync(&[
+            snapshot("pinnacle", Market::Spread, Some(-4.5), 1.87, 1),
+            snapshot("fanduel", Market::Moneyline, None, 1.55, 1),
+        ]);
+        let store = Store::open(Path::new(&db)).unwrap();
+        assert_eq!(store.repo::<OddsSnapshot>().count().unwrap(), 6);
+
+        assert_eq!(
+            run(&[
+                "odds", "history", "nba:1", "--db", &db, "--market", "spread", "--format", "csv",
+            ])
+            .unwrap(),
+            "book,market,selection,open_line,open_price,line,price,line_move,price_move,updated_at\n\
+             pinnacle,spread,home,-3,1.91,-4.5,1.87,-1.5,-0.04,2024-12-31T23:00:00+00:00\n\
+             \n\
+             taken_at,book,market,selection,line,price\n\
+             2024-12-31T00:00:00+00:00,pinnacle,spread,home,-3,1.91\n\
+             2024-12-31T23:00:00+00:00,pinnacle,spread,home,-4.5,1.87\n"
+        );
+        assert!(
+            run(&["odds", "history", "nba:1", "--db", &db, "--format", "csv"])
+                .unwrap()
+                .contains("fanduel,moneyline,home,,1.60,,1.55,,-0.05,")
+        );
+        assert!(run(&[
+            "odds",
+            "history",
+            "nba:1",
+            "--db",
+            &db,
+            "--market",
+            "spread",
+            "--odds-format",
+            "american",
+            "--format",
+            "csv",
+        ])
+        .unwrap()
+        .contains("pinnacle,spread,home,-3,-110,-4.5,-115,"));
+        assert!(run(&[
+            "odds",
+            "history",
+            "nba:1",
+            "--db",
+            &db,
+            "--odds-format",
+            "us"
+        ])
+        .is_err());
+        assert_eq!(
+            run(&["odds", "history", "nba:9", "--db", &db])
+                .unwrap_err()
+                .to_string(),
+            "no game named 'nba:9'"
+        );
+    }
+}
+
+#[cfg(test)]
+mod odds_compare_tests {
+    use crate::{
+        commands::mock::{db, run},
+        model::{
+            game::mock::game,
+            odds::{Market, OddsSnapshot, Selection},
+        },
+        store::Store,
+    };
+    use std::path::Path;
+
+    #[test]
+    fn test_odds_compare() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        let store = Store::open(Path::new(&db)).unwrap();
+        let game = game("nba:1", "lal", "bos", None);
+        store.repo().upsert(&game).unwrap();
+        let snapshot = |book: &str, market: Market, selection, line, price| OddsSnapshot {
+            game: game.id.clone(),
+            book: book.to_string(),
+            market,
+            selection,
+            line,
+            price,
+            taken_at: game.start,
+        };
+        store
+            .repo()
+            .upsert_all(&[
+                snapshot("pinnacle", Market::Moneyline, Selection::Home, None, 1.8),
+                snapshot("pinnacle", Market::Moneyline, Selection::Away, None, 2.05),
+                snapshot("fanduel", Market::Moneyline, Selection::Home, None, 1.85),
+                snapshot("fanduel", Market::Moneyline, Selection::Away, None, 1.95),
+                snapshot("pinnacle", Market::Total, Selection::Over, Some(220.5), 2.1),
+                snapshot(
+                    "pinnacle",
+                    Market::Total,
+                    Selection::Under,
+                    Some(220.5),
+                    1.75,
+                ),
+                snapshot("fanduel", Market::Total, Selection::Over, Some(220.5), 1.8),
+                snapshot(
+                    "fanduel",
+                    Market::Total,
+                    Selection::Under,
+                    Some(220.5),
+                    2.05,
+                ),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            run(&["odds", "compare", "--game", "nba:1", "--db", &db, "--format", "csv"]).unwrap(),
+            "market,line,selection,best,book,consensus,fair,books,arbitrage,stake\n\
+             moneyline,,home,1.85,fanduel,52.28%,1.91,2,,\n\
+             moneyline,,away,2.05,pinnacle,47.72%,2.10,2,,\n\
+             total,220.5,over,2.10,pinnacle,49.35%,2.03,2,3.73%,49.4%\n\
+             total,220.5,under,2.05,fanduel,50.65%,1.97,2,3.73%,50.6%\n"
+        );
+        assert!(run(&["odds", "compare", "--db", &db]).is_err());
+    }
+}
+
+#[cfg(test)]
+mod odds_fair_tests {
+    use crate::commands::mock::run;
+
+    #[test]
+    fn test_odds_fair() {
+        assert_eq!(
+            run(&[
+                "odds", "fair", "--odds", "1.5", "--odds", "2.6", "--method", "power", "--prob",
+                "0.6", "--prob", "0.4", "--format", "csv",
+            ])
+            .unwrap(),
+            "selection,odds,implied_pct,method,fair_pct,fair_odds,model_pct,edge_pct\n\
+             1,1.50,66.67,power,64.46,1.55,60,-4.46\n\
+             2,2.60,38.46,power,35.54,2.81,40,4.46\n"
+        );
+        let all = run(&[
+            "odds",
+            "fair",
+            "--odds",
+            "-110",
+            "--odds",
+            "-110",
+            "--odds-format",
+            "american",
+            "--format",
+            "csv",
+        ])
+        .unwrap();
+        assert_eq!(all.lines().count(), 9);
+        assert!(all.contains("1,-110,52.38,shin,50,+100,,\n"));
+        let fractional = run(&[
+            "odds",
+            "fair",
+            "--odds",
+            "6/4",
+            "--odds",
+            "1/2",
+            "--odds-format",
+            "fractional",
+            "--method",
+            "additive",
+            "--format",
+            "csv",
+        ])
+        .unwrap();
+        assert!(fractional.contains("1,3/2,40,additive,36.67,"));
+        assert!(fractional.contains("2,1/2,66.67,additive,63.33,"));
+
+        assert!(run(&["odds", "fair", "--odds", "1.5"]).is_err());
+        assert!(
+            run(&["odds", "fair", "--odds", "1.5", "--odds", "2.6", "--method", "exact"]).is_err()
+        );
+        assert!(run(&[
+            "odds", "fair", "--odds", "1.5", "--odds", "2.6", "--prob", "1.2", "--prob", "0"
+        ])
+        .is_err());
+        assert!(run(&["odds", "fair", "--odds", "1.5", "--odds", "2.6", "--prob", "0.5"]).is_err());
+    }
+}
@@ -0,0 +1,316 @@
+use super::{round, sport, store};
+use crate::{
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        error::CLIError,
+        matches::ArgMatches,
+        option::{CommandOptionKwargs, CommandOptionKwargsBuilder, CommandOptionType},
+        output::{Cell, Output, Table},
+    },
+    model::{
+        id::TeamId,
+        team::{Player, Team},
+    },
+    stats::{
+        per_minutes,
+        player::{game_lines, Aggregate, GameLine, PlayerFilter},
+        resolve::resolve,
+        HomeAway,
+    },
+};
+use std::{collections::HashMap, error::Error, sync::LazyLock};
+
+static COUNT: CommandOptionType = CommandOptionType::Int(0);
+static COUNT_KWARGS: LazyLock<CommandOptionKwargs<'static>> =
+    LazyLock::new(|| CommandOptionKwargsBuilder::new().set_kind(&COUNT).build());
+
+/// Registers `statpack player stats <name> [--season] [--last] [--vs] [--home|--away] [--stat]`
+pub(super) fn register(cli: &mut CLI<'static>) {
+    cli.create_command("player")
+        .create_command("stats")
+        .set_handler(stats)
+        .create_option("season", "Season of the games, by name (2024-25) or ID")
+        .create_option_kwargs("last", "Only the most recent N games", &COUNT_KWARGS)
+        .create_option("vs", "Only games against the team")
+        .create_option_kwargs("home", "Only home games", &CommandOptionKwargs::FLAG)
+        .create_option_kwargs("away", "Only away games", &CommandOptionKwargs::FLAG)
+        .create_option("stat", "Comma-separated stats to show, e.g. pts,reb,ast");
+}
+
+fn stats(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let store = store(matches)?;
+    let name = matches.positionals().join(" ");
+    if name.is_empty() {
+        return Err(CLIError::MissingRequired("<name>".to_string()).into());
+    }
+    let players = store.repo::<Player>().all()?;
+    let player = resolve("player", &name, &players, |player| {
+        vec![player.name.as_str()]
+    })?;
+    let teams = store.repo::<Team>().all()?;
+
+    let mut filter = PlayerFilter::new();
+    if let Some(season) = matches.value("season") {
+        filter.set_season(season);
+    }
+    if let Some(last) = matches.value_of("last")? {
+        filter.set_last(last);
+    }
+    if let Some(team) = matches.value("vs") {
+        filter.set_opponent(
+            resolve("team", team, &teams, |team| team.names().collect())?
+                .id
+                .clone(),
+        );
+    }
+    match (matches.flag("home"), matches.flag("away")) {
+        (true, true) => {
+            return Err(
+                CLIError::ConflictingOptions("--home".to_string(), "--away".to_string()).into(),
+            )
+        }
+        (true, false) => {
+            filter.set_side(HomeAway::Home);
+        }
+        (false, true) => {
+            filter.set_side(HomeAway::Away);
+        }
+        (false, false) => {}
+    }
+    let lines = filter.apply(game_lines(&store, &player.id)?);
+
+    let aggregate = Aggregate::new(lines.iter().map(|line| &line.line));
+    let keys: Vec<String> = match matches.value("stat") {
+        Some(stats) => stats
+            .split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+            .collect(),
+        None => aggregate.totals.keys().cloned().collect(),
+    };
+    let names: HashMap<&TeamId, &str> = teams
+        .iter()
+        .map(|team| (&team.id, team.abbreviation.as_deref().unwrap_or(&team.name)))
+        .collect();
+    let minutes = sport(&store, &lines)?.and_then(per_minutes);
+
+    Ok(Box::new(vec![
+        games_table(&player.name, &lines, &keys, &names),
+        summary_table(&aggregate, &keys, minutes),
+    ]))
+}
+
+/// Lists one row per game with the result and the chosen stats
+fn games_table(
+    player: &str,
+    lines: &[GameLine],
+    keys: &[String],
+    names: &HashMap<&TeamId, &str>,
+) -> Table {
+    let mut columns = vec!["date", "opponent", "side", "result", "min"];
+    columns.extend(keys.iter().map(String::as_str));
+    let mut table = Table::new(&columns);
+    table.set_title(player);
+
+    for line in lines {
+        let opponent = line.opponent().map(|team| {
+            names
+                .get(team)
+                .map_or_else(|| team.to_string(), |name| name.to_string())
+        });
+        let result = line
+            .game
+            .outcome_for(&line.line.team)
+            .zip(line.game.score_for(&line.line.team))
+            .map(|(outcome, (score, against))| {
+                format!("{} {}-{}", outcome.letter(), score, against)
+            });
+        let side = line.side().map(|side| match side {
+            HomeAway::Home => "home",
+            HomeAway::Away => "away",
+        });
+
+        let mut row: Vec<Cell> = vec![
+            line.game.start.date_naive().to_string().into(),
+            opponent.into(),
+            side.into(),
+            result.into(),
+            line.line.minutes.into(),
+        ];
+        row.extend(keys.iter().map(|key| Cell::from(line.line.get(key))));
+        table.push(row);
+    }
+    table
+}
+
+/// Lists totals, per-game averages and per-minute numbers when the sport has a basis
+fn summary_table(aggregate: &Aggregate, keys: &[String], minutes: Option<f64>) -> Table {
+    let mut columns = vec!["", "games", "min"];
+    columns.extend(keys.iter().map(String::as_str));
+    let mut table = Table::new(&columns);
+    table.set_title("summary");
+
+    let mut total: Vec<Cell> = vec![
+        "total".into(),
+        (aggregate.games as i64).into(),
+        round(aggregate.minutes).into(),
+    ];
+    total.extend(keys.iter().map(|key| Cell::from(aggregate.total(key))));
+    table.push(total);
+
+    let mut average: Vec<Cell> = vec![
+        "average".into(),
+        Cell::Null,
+        (aggregate.timed_games > 0)
+            .then(|| round(aggregate.minutes / aggregate.timed_games as f64))
+            .into(),
+    ];
+    average.extend(
+        keys.iter()
+            .map(|key| Cell::from(aggregate.average(key).map(round))),
+    );
+    table.push(average);
+
+    if let Some(minutes) = minutes {
+        let mut per: Vec<Cell> = vec![
+            format!("per-{}", minutes).into(),
+            Cell::Null,
+            minutes.into(),
+        ];
+        per.extend(
+            keys.iter()
+                .map(|key| Cell::from(aggregate.per_minutes(key, minutes).map(round))),
+        );
+        table.push(per);
+    }
+    table
+}
+
+#[cfg(test)]
+mod player_stats_tests {
+    use crate::{
+        commands::mock::{db, run},
+        model::{
+            game::{mock::game, Game},
+            id::{LeagueId, PlayerId, TeamId},
+            sport::{League, Sport},
+            stat::StatLine,
+            team::{Player, Team},
+        },
+        store::Store,
+    };
+    use std::path::Path;
+
+    fn seed(db: &str) {
+        let store = Store::open(Path::new(db)).unwrap();
+        store
+            .repo()
+            .upsert(&League {
+                id: LeagueId::new("nba"),
+                sport: Sport::Basketball,
+                name: "NBA".to_string(),
+                country: None,
+                rules: None,
+            })
+            .unwrap();
+        for (id, name) in [("lal", "Lakers"), ("bos", "Celtics"), ("den", "Nuggets")] {
+            store
+                .repo()
+                .upsert(&Team {
+                    id: TeamId::new(id),
+                    league: LeagueId::new("nba"),
+                    name: name.to_string(),
+                    abbreviation: Some(id.to_uppercase()),
+                    venue: None,
+                    aliases: vec![],
+                })
+                .unwrap();
+        }
+        store
+            .repo()
+            .upsert(&Player {
+                id: PlayerId::new("lebron"),
+                name: "LeBron James".to_string(),
+                team: Some(TeamId::new("lal")),
+                position: None,
+                birth_date: None,
+            })
+            .unwrap();
+
+        let games = [
+            game("1", "lal", "bos", Some((110, 100))),
+            game("2", "den", "lal", Some((120, 101))),
+            game("3", "lal", "den", Some((99, 98))),
+        ];
+        for (day, (game, (minutes, points))) in games
+            .iter()
+            .zip([(36.0, 30.0), (30.0, 20.0), (42.0, 28.0)])
+            .enumerate()
+        {
+            let mut game: Game = game.clone();
+            game.start += chrono::Duration::days(day as i64);
+            store.repo().upsert(&game).unwrap();
+            let mut line =
+                StatLine::new(game.id.clone(), PlayerId::new("lebron"), TeamId::new("lal"));
+            line.minutes = Some(minutes);
+            line.set("pts", points).set("ast", 8.0);
+            store.repo().upsert(&line).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_player_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        seed(&db);
+
+        let output = run(&[
+            "player", "stats", "lebron", "--db", &db, "--stat", "pts", "--format", "csv",
+        ])
+        .unwrap();
+        assert_eq!(
+            output,
+            "date,opponent,side,result,min,pts\n\
+             2025-01-01,BOS,home,W 110-100,36,30\n\
+             2025-01-02,DEN,away,L 101-120,30,20\n\
+             2025-01-03,DEN,home,W 99-98,42,28\n\
+             \n\
+             ,games,min,pts\n\
+             total,3,108,78\n\
+             average,,36,26\n\
+             per-36,,36,26\n"
+        );
+    }
+
+    #[test]
+    fn test_player_stats_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        seed(&db);
+
+        let output = run(&[
+            "player", "stats", "LeBron", "James", "--db", &db, "--vs", "nugg", "--home",
+            "--format", "csv",
+        ])
+        .unwrap();
+        assert_eq!(
+            output.lines().nth(1).unwrap(),
+            "2025-01-03,DEN,home,W 99-98,42,8,28"
+        );
+
+        let last = run(&[
+            "player", "stats", "lebron", "--db", &db, "--last", "1", "--format", "csv",
+        ])
+        .unwrap();
+        assert!(last.contains("total,1,42,8,28"));
+        assert!(run(&["player", "stats", "lebron", "--db", &db, "--home", "--away"]).is_err());
+        assert_eq!(
+            run(&["player", "stats", "lebrn", "--db", &db])
+                .unwrap_err()
+                .to_string(),
+            "no player named 'lebrn', did you mean 'LeBron James'?"
+        );
+    }
+}
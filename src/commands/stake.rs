@@ -0,0 +1,204 @@
+use super::{decimal_odds, odds_format, price, probabilities, round};
+use crate::{
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        error::CLIError,
+        matches::ArgMatches,
+        option::{CommandOptionKwargs, CommandOptionKwargsBuilder, CommandOptionType},
+        output::{Output, Table},
+    },
+    odds::value::ValueBet,
+};
+use std::{error::Error, sync::LazyLock};
+
+static AMOUNT: CommandOptionType = CommandOptionType::Float(0.0);
+static KELLY_FRACTION: CommandOptionType = CommandOptionType::Float(1.0);
+static ODDS: CommandOptionType = CommandOptionType::Text(String::new());
+static AMOUNT_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_required()
+        .set_kind(&AMOUNT)
+        .build()
+});
+static ODDS_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_required()
+        .set_kind(&ODDS)
+        .build()
+});
+static BANKROLL_KWARGS: LazyLock<CommandOptionKwargs<'static>> =
+    LazyLock::new(|| CommandOptionKwargsBuilder::new().set_kind(&AMOUNT).build());
+static KELLY_FRACTION_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_kind(&KELLY_FRACTION)
+        .set_default(&KELLY_FRACTION)
+        .build()
+});
+
+/// Registers `statpack stake --prob <0-1> --odds <odds> [--bankroll] [--kelly-fraction]`
+pub(super) fn register(cli: &mut CLI<'static>) {
+    cli.create_command("stake")
+        .set_handler(run)
+        .create_option_kwargs(
+            "prob",
+            "Model probability of the selection winning (0-1)",
+            &AMOUNT_KWARGS,
+        )
+        .create_option_kwargs("odds", "Odds offered, in --odds-format", &ODDS_KWARGS)
+        .create_option_kwargs(
+            "bankroll",
+            "Bankroll the stake is taken from",
+            &BANKROLL_KWARGS,
+        )
+        .create_option_kwargs(
+            "kelly-fraction",
+            "Share of the Kelly stake to bet, 0.5 for half Kelly",
+            &KELLY_FRACTION_KWARGS,
+        );
+}
+
+/// Weighs a price against a model probability and recommends a Kelly stake
+fn run(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let (probability, odds) = match (
+        &probabilities(matches, "prob")?[..],
+        &decimal_odds(matches, "odds")?[..],
+    ) {
+        ([probability], [odds]) => (*probability, *odds),
+        ([_], _) => {
+            return Err(CLIError::MissingValue {
+                option: "--odds".to_string(),
+                expected: 1,
+            }
+            .into())
+        }
+        _ => {
+            return Err(CLIError::MissingValue {
+                option: "--prob".to_string(),
+                expected: 1,
+            }
+            .into())
+        }
+    };
+    let bankroll: Option<f64> = matches.value_of("bankroll")?;
+    if let Some(bankroll) = bankroll.filter(|bankroll| *bankroll <= 0.0) {
+        return Err(CLIError::InvalidValue {
+            option: "--bankroll".to_string(),
+            value: bankroll.to_string(),
+            expected: "a positive amount".to_string(),
+        }
+        .into());
+    }
+    let fraction: f64 = matches.value_of("kelly-fraction")?.unwrap_or(1.0);
+    if fraction <= 0.0 || fraction > 1.0 {
+        return Err(CLIError::InvalidValue {
+            option: "--kelly-fraction".to_string(),
+            value: fraction.to_string(),
+            expected: "a share of the Kelly stake above 0 and up to 1".to_string(),
+        }
+        .into());
+    }
+
+    let bet = ValueBet::new(probability, odds);
+    let share = bet.kelly() * fraction;
+    let stake = bankroll.map(|bankroll| bet.stake(bankroll, fraction));
+    let mut table = Table::new(&[
+        "probability_pct",
+        "odds",
+        "implied_pct",
+        "edge_pct",
+        "ev_pct",
+        "kelly_pct",
+        "stake_pct",
+        "stake",
+        "expected_profit",
+        "growth_pct",
+    ]);
+    if !bet.is_value() {
+        table.set_title("no edge at these odds, no stake");
+    }
+    table.push(vec![
+        round(probability * 100.0).into(),
+        price(odds_format(matches), odds),
+        round(bet.implied_probability() * 100.0).into(),
+        round(bet.edge() * 100.0).into(),
+        round(bet.expected_value() * 100.0).into(),
+        round(bet.kelly() * 100.0).into(),
+        round(share * 100.0).into(),
+        stake.map(round).into(),
+        stake
+            .map(|stake| round(stake * bet.expected_value()))
+            .into(),
+        round(bet.growth(share) * 100.0).into(),
+    ]);
+    Ok(Box::new(table))
+}
+
+#[cfg(test)]
+mod stake_tests {
+    use crate::commands::mock::run;
+
+    #[test]
+    fn test_stake() {
+        assert_eq!(
+            run(&[
+                "stake",
+                "--prob",
+                "0.55",
+                "--odds",
+                "+100",
+                "--odds-format",
+                "american",
+                "--bankroll",
+                "1000",
+                "--kelly-fraction",
+                "0.5",
+                "--format",
+                "csv",
+            ])
+            .unwrap(),
+            "probability_pct,odds,implied_pct,edge_pct,ev_pct,kelly_pct,stake_pct,stake,\
+             expected_profit,growth_pct\n\
+             55,+100,50,5,10,10,5,50,5,0.38\n"
+        );
+        assert_eq!(
+            run(&["stake", "--prob", "0.5", "--odds", "1.91", "--format", "csv"]).unwrap(),
+            "probability_pct,odds,implied_pct,edge_pct,ev_pct,kelly_pct,stake_pct,stake,\
+             expected_profit,growth_pct\n\
+             50,1.91,52.36,-2.36,-4.5,0,0,,,0\n"
+        );
+        assert_eq!(
+            run(&[
+                "stake",
+                "--prob",
+                "0.5",
+                "--odds",
+                "6/4",
+                "--odds-format",
+                "fractional",
+                "--bankroll",
+                "1000",
+                "--format",
+                "csv",
+            ])
+            .unwrap(),
+            "probability_pct,odds,implied_pct,edge_pct,ev_pct,kelly_pct,stake_pct,stake,\
+             expected_profit,growth_pct\n\
+             50,3/2,40,10,25,16.67,16.67,166.67,41.67,2.04\n"
+        );
+
+        assert!(run(&["stake", "--prob", "55", "--odds", "2"]).is_err());
+        assert!(run(&["stake", "--prob", "0.5", "--odds", "2", "--odds", "3"]).is_err());
+        assert!(run(&["stake", "--prob", "0.5", "--odds", "2", "--bankroll", "-5"]).is_err());
+        assert!(run(&[
+            "stake",
+            "--prob",
+            "0.5",
+            "--odds",
+            "2",
+            "--kelly-fraction",
+            "2"
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,239 @@
+use super::{round, store};
+use crate::{
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        error::CLIError,
+        matches::ArgMatches,
+        option::CommandOptionKwargs,
+        output::{Output, Table},
+    },
+    model::{
+        game::Game,
+        id::TeamId,
+        sport::{League, TieBreaker},
+        team::Team,
+    },
+    stats::{is_season, resolve::resolve, team::standings},
+};
+use std::{collections::HashMap, error::Error};
+
+/// Registers `statpack standings --league <name> [--season] [--tie-breakers]`
+pub(super) fn register(cli: &mut CLI<'static>) {
+    cli.create_command("standings")
+        .set_handler(run)
+        .create_option_kwargs(
+            "league",
+            "League of the table, by name or ID",
+            &CommandOptionKwargs::REQUIRED,
+        )
+        .create_option(
+            "season",
+            "Season of the table, by name (2024-25) or ID (defaults to the latest)",
+        )
+        .create_option(
+            "tie-breakers",
+            "Comma-separated tie-breakers overriding the league rules, e.g. points,head-to-head",
+        );
+}
+
+fn run(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let store = store(matches)?;
+    let leagues = store.repo::<League>().all()?;
+    let league = resolve(
+        "league",
+        matches.value("league").unwrap_or_default(),
+        &leagues,
+        |league| vec![league.name.as_str(), league.id.as_str()],
+    )?;
+
+    let games = store.repo::<Game>().find("league", league.id.as_str())?;
+    let season = match matches.value("season") {
+        Some(season) => season.to_string(),
+        None => games
+            .iter()
+            .max_by_key(|game| game.start)
+            .map_or_else(String::new, |game| game.season.to_string()),
+    };
+    let games: Vec<Game> = games
+        .into_iter()
+        .filter(|game| is_season(&game.season, &season))
+        .collect();
+
+    let mut rules = league.rules();
+    if let Some(tie_breakers) = matches.value("tie-breakers") {
+        rules.tie_breakers = tie_breakers
+            .split(',')
+            .map(|name| {
+                name.trim().parse().map_err(|_| CLIError::InvalidChoice {
+                    option: "--tie-breakers".to_string(),
+                    value: name.to_string(),
+                    choices: TieBreaker::ALL
+                        .iter()
+                        .map(|tie_breaker| tie_breaker.name().to_string())
+                        .collect(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+    }
+
+    let teams = store.repo::<Team>().find("league", league.id.as_str())?;
+    let names: HashMap<&TeamId, &str> = teams
+        .iter()
+        .map(|team| (&team.id, team.name.as_str()))
+        .collect();
+    let mut table = Table::new(&[
+        "rank", "team", "gp", "w", "l", "d", "pts", "pf", "pa", "diff", "pct", "home", "away",
+        "streak",
+    ]);
+    table.set_title(&format!("{} {}", league.name, season));
+    for standing in standings(&games, &rules) {
+        let summary = &standing.summary;
+        let record = &summary.overall;
+        table.push(vec![
+            (standing.rank as i64).into(),
+            names
+                .get(&summary.team)
+                .map_or_else(|| summary.team.to_string(), |name| name.to_string())
+                .into(),
+            record.played().into(),
+            record.wins.into(),
+            record.losses.into(),
+            record.draws.into(),
+            standing.points.into(),
+            record.scored.into(),
+            record.conceded.into(),
+            record.differential().into(),
+            record.win_percentage().map(round).into(),
+            summary.home.to_string().into(),
+            summary.away.to_string().into(),
+            summary
+                .streak()
+                .map(|(outcome, length)| format!("{}{}", outcome.letter(), length))
+                .into(),
+        ]);
+    }
+    Ok(Box::new(table))
+}
+
+#[cfg(test)]
+mod standings_tests {
+    use crate::commands::{
+        mock::{db, run},
+        team::mock::seed,
+    };
+
+    #[test]
+    fn test_standings() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        seed(&db);
+
+        assert_eq!(
+            run(&[
+                "standings",
+                "--league",
+                "nba",
+                "--db",
+                &db,
+                "--format",
+                "csv"
+            ])
+            .unwrap(),
+            "rank,team,gp,w,l,d,pts,pf,pa,diff,pct,home,away,streak\n\
+             1,Nuggets,3,2,1,0,2,313,290,23,0.67,1-0-0,1-1-0,W1\n\
+             2,Lakers,3,2,1,0,2,310,318,-8,0.67,2-0-0,0-1-0,W1\n\
+             3,Celtics,2,0,2,0,0,190,205,-15,0,0-1-0,0-1-0,L2\n"
+        );
+    }
+
+    #[test]
+    fn test_standings_as_of() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        seed(&db);
+
+        // Only the first two games had started on the morning of January 3rd
+        assert_eq!(
+            run(&[
+                "standings",
+                "--league",
+                "nba",
+                "--as-of",
+                "2025-01-03",
+                "--db",
+                &db,
+                "--format",
+                "csv"
+            ])
+            .unwrap(),
+            "rank,team,gp,w,l,d,pts,pf,pa,diff,pct,home,away,streak\n\
+             1,Nuggets,1,1,0,0,1,120,101,19,1,1-0-0,0-0-0,W1\n\
+             2,Lakers,2,1,1,0,1,211,220,-9,0.5,1-0-0,0-1-0,L1\n\
+             3,Celtics,1,0,1,0,0,100,110,-10,0,0-0-0,0-1-0,L1\n"
+        );
+        assert_eq!(
+            run(&[
+                "fav",
+                "add",
+                "team",
+                "lakers",
+                "--as-of",
+                "2025-01-03T12:00:00Z",
+                "--db",
+                &db
+            ])
+            .unwrap_err()
+            .to_string(),
+            "the database is read-only as of 2025-01-03T12:00:00+00:00, drop --as-of to change it"
+        );
+        assert!(run(&[
+            "standings",
+            "--league",
+            "nba",
+            "--as-of",
+            "yesterday",
+            "--db",
+            &db
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_standings_tie_breakers() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        seed(&db);
+
+        let table = run(&[
+            "standings",
+            "--league",
+            "NBA",
+            "--season",
+            "2024-25",
+            "--tie-breakers",
+            "wins,scored-for",
+            "--db",
+            &db,
+            "--format",
+            "csv",
+        ])
+        .unwrap();
+        assert!(table.lines().nth(1).unwrap().starts_with("1,Nuggets"));
+        assert_eq!(
+            run(&[
+                "standings",
+                "--league",
+                "nba",
+                "--tie-breakers",
+                "coin",
+                "--db",
+                &db
+            ])
+            .unwrap_err()
+            .to_string(),
+            "invalid value 'coin' for '--tie-breakers', expected one of: points, \
+             win-percentage, wins, differential, scored-for, head-to-head"
+        );
+    }
+}
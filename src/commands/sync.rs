@@ -0,0 +1,208 @@
+use super::{provider, store};
+use crate::{
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        matches::ArgMatches,
+        option::CommandOptionKwargs,
+        output::{Output, Table},
+    },
+    model::{
+        game::{Game, GameStatus},
+        id::LeagueId,
+    },
+    provider::{error::ProviderError, BoxScore},
+    store::repository::Repository,
+};
+use chrono::{NaiveDate, Utc};
+use std::error::Error;
+
+/// Registers `statpack sync --league <id> [--date <YYYY-MM-DD>] [--fixtures <dir>]`
+pub(super) fn register(cli: &mut CLI<'static>) {
+    cli.create_command("sync")
+        .set_handler(run)
+        .create_option_kwargs(
+            "league",
+            "League whose games are fetched",
+            &CommandOptionKwargs::REQUIRED,
+        )
+        .create_option("date", "Day of the games, YYYY-MM-DD (defaults to today)")
+        .create_option(
+            "fixtures",
+            "Replay recorded responses from the directory instead of the API",
+        );
+}
+
+fn run(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let league = LeagueId::new(matches.value("league").unwrap_or_default());
+    let date = matches
+        .value_of::<NaiveDate>("date")?
+        .unwrap_or_else(|| Utc::now().date_naive());
+    let provider = provider(matches)?;
+    let mut store = store(matches)?;
+
+    let games = provider.schedule(&league, date)?;
+    let mut box_scores = vec![];
+    for game in &games {
+        if game.status != GameStatus::Final {
+            continue;
+        }
+        match provider.box_score(&game.id) {
+            Ok(box_score) => box_scores.push(box_score),
+            // Feeds publish box scores some time after the final whistle
+            Err(ProviderError::NotFound(_)) => {}
+            Err(error) => return Err(error.into()),
+        }
+    }
+
+    store.transaction(|tx| {
+        Repository::new(tx).upsert_all(&games)?;
+        for box_score in &box_scores {
+            Repository::new(tx).upsert(&box_score.game)?;
+            Repository::new(tx).upsert_all(&box_score.teams)?;
+            Repository::new(tx).upsert_all(&box_score.players)?;
+            Repository::new(tx).upsert_all(&box_score.stat_lines)?;
+            Repository::new(tx).upsert_all(&box_score.events)?;
+        }
+        Ok(())
+    })?;
+    Ok(Box::new(table(&games, &box_scores)))
+}
+
+/// Lists the synced games with the number of stat lines stored for each
+fn table(games: &[Game], box_scores: &[BoxScore]) -> Table {
+    let mut table = Table::new(&["game", "status", "home", "away", "score", "stat_lines"]);
+    for game in games {
+        let box_score = box_scores
+            .iter()
+            .find(|box_score| box_score.game.id == game.id);
+        let game = box_score.map_or(game, |box_score| &box_score.game);
+        let score = game
+            .home_score
+            .zip(game.away_score)
+            .map(|(home, away)| format!("{}-{}", home, away));
+        table.push(vec![
+            game.id.to_string().into(),
+            game.status.name().into(),
+            game.home.to_string().into(),
+            game.away.to_string().into(),
+            score.into(),
+            box_score
+                .map(|box_score| box_score.stat_lines.len() as i64)
+                .into(),
+        ]);
+    }
+    table
+}
+
+#[cfg(test)]
+mod sync_tests {
+    use crate::{
+        commands::mock::{db, run},
+        model::{
+            game::mock::game,
+            id::{LeagueId, PlayerId, TeamId},
+            stat::StatLine,
+        },
+        provider::{fixture::FixtureProvider, BoxScore, Resource},
+        store::Store,
+    };
+    use chrono::NaiveDate;
+    use std::{io, path::Path, thread};
+    use tiny_http::{Response, Server};
+
+    #[test]
+    fn test_sync_fixtures() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        let fixtures = FixtureProvider::new(dir.path().join("fixtures"));
+        let league = LeagueId::new("nba");
+        let played = game("nba:1", "lal", "bos", Some((110, 102)));
+        let published = game("nba:2", "den", "mia", Some((99, 90)));
+        let scheduled = game("nba:3", "nyk", "phi", None);
+        fixtures
+            .record(
+                &Resource::Schedule {
+                    league: &league,
+                    date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                },
+                &[&played, &published, &scheduled],
+            )
+            .unwrap();
+        fixtures
+            .record(
+                &Resource::BoxScore(&published.id),
+                &BoxScore {
+                    game: published.clone(),
+                    teams: vec![],
+                    players: vec![],
+                    stat_lines: vec![StatLine::new(
+                        published.id.clone(),
+                        PlayerId::new("nba:jokic"),
+                        TeamId::new("den"),
+                    )],
+                    events: vec![],
+                },
+            )
+            .unwrap();
+
+        let output = run(&[
+            "sync",
+            "--league",
+            "nba",
+            "--date",
+            "2025-01-01",
+            "--fixtures",
+            fixtures.root().to_str().unwrap(),
+            "--db",
+            &db,
+            "--format",
+            "csv",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            output,
+            "game,status,home,away,score,stat_lines\n\
+             nba:1,final,lal,bos,110-102,\n\
+             nba:2,final,den,mia,99-90,1\n\
+             nba:3,scheduled,nyk,phi,,\n"
+        );
+        let store = Store::open(Path::new(&db)).unwrap();
+        assert_eq!(store.repo::<StatLine>().count().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_sync_api_url() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr());
+        let api = thread::spawn(move || {
+            let request = server.recv().unwrap();
+            let path = request.url().to_string();
+            request
+                .respond(Response::empty(404).with_data(io::empty(), Some(0)))
+                .unwrap();
+            path
+        });
+
+        assert_eq!(
+            run(&[
+                "sync",
+                "--league",
+                "nba",
+                "--date",
+                "2025-01-01",
+                "--api-url",
+                &url,
+                "--db",
+                &db
+            ])
+            .unwrap_err()
+            .to_string(),
+            "no data for 'leagues/nba/schedule/2025-01-01'"
+        );
+        assert_eq!(api.join().unwrap(), "/leagues/nba/schedule/2025-01-01");
+    }
+}
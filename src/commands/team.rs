@@ -0,0 +1,195 @@
+use super::{round, store};
+use crate::{
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        error::CLIError,
+        matches::ArgMatches,
+        option::{CommandOptionKwargs, CommandOptionKwargsBuilder, CommandOptionType},
+        output::{Cell, Output, Table},
+    },
+    model::{game::Game, team::Team},
+    stats::{
+        is_season,
+        resolve::resolve,
+        team::{TeamRecord, TeamSummary},
+    },
+};
+use std::{error::Error, sync::LazyLock};
+
+static COUNT: CommandOptionType = CommandOptionType::Int(0);
+static COUNT_KWARGS: LazyLock<CommandOptionKwargs<'static>> =
+    LazyLock::new(|| CommandOptionKwargsBuilder::new().set_kind(&COUNT).build());
+
+/// Registers `statpack team stats <name> [--season] [--last]`
+pub(super) fn register(cli: &mut CLI<'static>) {
+    cli.create_command("team")
+        .create_command("stats")
+        .set_handler(stats)
+        .create_option("season", "Season of the games, by name (2024-25) or ID")
+        .create_option_kwargs("last", "Only the most recent N games", &COUNT_KWARGS);
+}
+
+fn stats(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let store = store(matches)?;
+    let name = matches.positionals().join(" ");
+    if name.is_empty() {
+        return Err(CLIError::MissingRequired("<name>".to_string()).into());
+    }
+    let teams = store.repo::<Team>().all()?;
+    let team = resolve("team", &name, &teams, |team| team.names().collect())?;
+
+    let mut games: Vec<Game> = store
+        .repo::<Game>()
+        .for_team(&team.id)?
+        .into_iter()
+        .filter(|game| game.outcome_for(&team.id).is_some())
+        .filter(|game| {
+            matches
+                .value("season")
+                .is_none_or(|season| is_season(&game.season, season))
+        })
+        .collect();
+    if let Some(last) = matches.value_of::<usize>("last")? {
+        games.drain(..games.len().saturating_sub(last));
+    }
+    let summary = TeamSummary::new(&team.id, &games);
+
+    let mut splits = Table::new(&[
+        "split",
+        "gp",
+        "w",
+        "l",
+        "d",
+        "pf",
+        "pa",
+        "diff",
+        "pct",
+        "avg_for",
+        "avg_against",
+    ]);
+    splits.set_title(&team.name);
+    for (split, record) in [
+        ("overall", &summary.overall),
+        ("home", &summary.home),
+        ("away", &summary.away),
+    ] {
+        splits.push(record_row(split, record));
+    }
+
+    let mut form = Table::new(&["streak", "last_5"]);
+    form.set_title("form").push(vec![
+        summary
+            .streak()
+            .map(|(outcome, length)| format!("{}{}", outcome.letter(), length))
+            .into(),
+        summary
+            .form(5)
+            .iter()
+            .map(|outcome| outcome.letter())
+            .collect::<String>()
+            .into(),
+    ]);
+    Ok(Box::new(vec![splits, form]))
+}
+
+/// Lays out a record as `split, gp, w, l, d, pf, pa, diff, pct, avg_for, avg_against`
+fn record_row(split: &str, record: &TeamRecord) -> Vec<Cell> {
+    let played = record.played();
+    let average = |total: u32| (played > 0).then(|| round(f64::from(total) / f64::from(played)));
+    vec![
+        split.into(),
+        played.into(),
+        record.wins.into(),
+        record.losses.into(),
+        record.draws.into(),
+        record.scored.into(),
+        record.conceded.into(),
+        record.differential().into(),
+        record.win_percentage().map(round).into(),
+        average(record.scored).into(),
+        average(record.conceded).into(),
+    ]
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use crate::{
+        model::{
+            game::mock::game,
+            id::{LeagueId, TeamId},
+            sport::{League, Sport},
+            team::Team,
+        },
+        store::Store,
+    };
+    use std::path::Path;
+
+    /// Stores an NBA league with lal, bos and den and four games between them, a day apart
+    pub(crate) fn seed(db: &str) {
+        let store = Store::open(Path::new(db)).unwrap();
+        store
+            .repo()
+            .upsert(&League {
+                id: LeagueId::new("nba"),
+                sport: Sport::Basketball,
+                name: "NBA".to_string(),
+                country: None,
+                rules: None,
+            })
+            .unwrap();
+        for (id, name) in [("lal", "Lakers"), ("bos", "Celtics"), ("den", "Nuggets")] {
+            store
+                .repo()
+                .upsert(&Team {
+                    id: TeamId::new(id),
+                    league: LeagueId::new("nba"),
+                    name: name.to_string(),
+                    abbreviation: Some(id.to_uppercase()),
+                    venue: None,
+                    aliases: vec![],
+                })
+                .unwrap();
+        }
+        let games = [
+            game("1", "lal", "bos", Some((110, 100))),
+            game("2", "den", "lal", Some((120, 101))),
+            game("3", "lal", "den", Some((99, 98))),
+            game("4", "bos", "den", Some((90, 95))),
+            game("5", "lal", "bos", None),
+        ];
+        for (day, mut game) in games.into_iter().enumerate() {
+            game.start += chrono::Duration::days(day as i64);
+            store.repo().upsert(&game).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod team_stats_tests {
+    use super::mock::seed;
+    use crate::commands::mock::{db, run};
+
+    #[test]
+    fn test_team_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        seed(&db);
+
+        assert_eq!(
+            run(&["team", "stats", "lakers", "--db", &db, "--format", "csv"]).unwrap(),
+            "split,gp,w,l,d,pf,pa,diff,pct,avg_for,avg_against\n\
+             overall,3,2,1,0,310,318,-8,0.67,103.33,106\n\
+             home,2,2,0,0,209,198,11,1,104.5,99\n\
+             away,1,0,1,0,101,120,-19,0,101,120\n\
+             \n\
+             streak,last_5\n\
+             W1,WLW\n"
+        );
+        assert!(
+            run(&["team", "stats", "lakers", "--db", &db, "--last", "1", "--format", "csv"])
+                .unwrap()
+                .contains("overall,1,1,0,0,99,98,1,1,99,98\n")
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use super::{now, round, store};
+use crate::{
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        matches::ArgMatches,
+        option::{CommandOptionKwargs, CommandOptionKwargsBuilder, CommandOptionType},
+        output::{Output, Table},
+    },
+    model::{
+        favorite::{Favorite, FavoriteKind},
+        game::{Game, GameStatus},
+        id::{LeagueId, TeamId},
+        sport::League,
+        team::{Player, Team},
+    },
+    stats::{
+        player::{game_lines, Aggregate},
+        team::TeamSummary,
+    },
+};
+use chrono::{Duration, NaiveDate};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    sync::LazyLock,
+};
+
+static DAYS: CommandOptionType = CommandOptionType::Int(1);
+static LAST: CommandOptionType = CommandOptionType::Int(5);
+static DAYS_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_kind(&DAYS)
+        .set_default(&DAYS)
+        .build()
+});
+static LAST_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_kind(&LAST)
+        .set_default(&LAST)
+        .build()
+});
+
+/// Registers `statpack today [--date <YYYY-MM-DD>] [--days] [--last]`
+pub(super) fn register(cli: &mut CLI<'static>) {
+    cli.create_command("today")
+        .set_handler(run)
+        .create_option(
+            "date",
+            "First day of the dashboard, YYYY-MM-DD (defaults to today)",
+        )
+        .create_option_kwargs("days", "Number of days of upcoming games", &DAYS_KWARGS)
+        .create_option_kwargs("last", "Number of recent games summarized", &LAST_KWARGS);
+}
+
+/// Summarizes upcoming games and recent form of every favorite
+fn run(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let store = store(matches)?;
+    let date = matches
+        .value_of::<NaiveDate>("date")?
+        .unwrap_or_else(|| now(&store).date_naive());
+    let days: i64 = matches.value_of("days")?.unwrap_or(1);
+    let last: usize = matches.value_of("last")?.unwrap_or(5);
+    let start = date.and_time(Default::default()).and_utc();
+    let end = start + Duration::days(days);
+
+    let favorites = store.repo::<Favorite>().all()?;
+    let ids = |kind: FavoriteKind| -> HashSet<&str> {
+        favorites
+            .iter()
+            .filter(|favorite| favorite.kind == kind)
+            .map(|favorite| favorite.id.as_str())
+            .collect()
+    };
+    let (sports, leagues) = (ids(FavoriteKind::Sport), ids(FavoriteKind::League));
+    let leagues: HashMap<LeagueId, String> = store
+        .repo::<League>()
+        .all()?
+        .into_iter()
+        .filter(|league| {
+            leagues.contains(league.id.as_str()) || sports.contains(league.sport.name())
+        })
+        .map(|league| (league.id, league.name))
+        .collect();
+    let teams: HashMap<TeamId, Team> = store
+        .repo::<Team>()
+        .all()?
+        .into_iter()
+        .map(|team| (team.id.clone(), team))
+        .collect();
+    let followed_teams: Vec<&Team> = ids(FavoriteKind::Team)
+        .into_iter()
+        .filter_map(|id| teams.get(&TeamId::new(id)))
+        .collect();
+    let mut players = vec![];
+    for id in ids(FavoriteKind::Player) {
+        players.extend(store.repo::<Player>().get(id)?);
+    }
+    players.sort_by(|a, b| a.name.cmp(&b.name));
+    let name = |team: &TeamId| {
+        teams
+            .get(team)
+            .map_or_else(|| team.to_string(), |team| team.name.clone())
+    };
+
+    let mut upcoming = Table::new(&["start", "league", "home", "away", "status", "following"]);
+    upcoming.set_title("upcoming");
+    for game in store.repo::<Game>().between(start, end)? {
+        if game.status == GameStatus::Final {
+            continue;
+        }
+        let mut following = vec![];
+        if let Some(league) = leagues.get(&game.league) {
+            following.push(league.clone());
+        }
+        for team in [&game.home, &game.away] {
+            if followed_teams.iter().any(|followed| followed.id == *team) {
+                following.push(name(team));
+            }
+            for player in players
+                .iter()
+                .filter(|player| player.team.as_ref() == Some(team))
+            {
+                following.push(player.name.clone());
+            }
+        }
+        if following.is_empty() {
+            continue;
+        }
+        upcoming.push(vec![
+            game.start.format("%Y-%m-%d %H:%M").to_string().into(),
+            game.league.to_string().into(),
+            name(&game.home).into(),
+            name(&game.away).into(),
+            game.status.name().into(),
+            following.join(", ").into(),
+        ]);
+    }
+
+    let mut form = Table::new(&["team", "gp", "record", "streak", "form", "next"]);
+    form.set_title("teams");
+    let mut followed_teams = followed_teams;
+    followed_teams.sort_by(|a, b| a.name.cmp(&b.name));
+    for team in followed_teams {
+        let games = store.repo::<Game>().for_team(&team.id)?;
+        let played: Vec<&Game> = games
+            .iter()
+            .filter(|game| game.status == GameStatus::Final && game.start < start)
+            .collect();
+        let summary = TeamSummary::new(
+            &team.id,
+            played[played.len().saturating_sub(last)..].iter().copied(),
+        );
+        let next = games
+            .iter()
+            .find(|game| game.status != GameStatus::Final && game.start >= start)
+            .map(|game| {
+                format!(
+                    "{} vs {}",
+                    game.start.date_naive(),
+                    game.opponent_of(&team.id).map_or_else(String::new, name)
+                )
+            });
+        form.push(vec![
+            team.name.as_str().into(),
+            summary.overall.played().into(),
+            summary.overall.to_string().into(),
+            summary
+                .streak()
+                .map(|(outcome, length)| format!("{}{}", outcome.letter(), length))
+                .into(),
+            summary
+                .outcomes
+                .iter()
+                .map(|outcome| outcome.letter())
+                .collect::<String>()
+                .into(),
+            next.into(),
+        ]);
+    }
+
+    let mut recent = Table::new(&["player", "team", "gp", "min", "averages"]);
+    recent.set_title("players");
+    for player in &players {
+        let mut lines = game_lines(&store, &player.id)?;
+        lines.retain(|line| line.game.start < start);
+        let lines = &lines[lines.len().saturating_sub(last)..];
+        let aggregate = Aggregate::new(lines.iter().map(|line| &line.line));
+        let averages: Vec<String> = aggregate
+            .totals
+            .keys()
+            .filter_map(|key| {
+                aggregate
+                    .average(key)
+                    .map(|average| format!("{} {}", key, round(average)))
+            })
+            .collect();
+        recent.push(vec![
+            player.name.as_str().into(),
+            player.team.as_ref().map(name).into(),
+            (aggregate.games as i64).into(),
+            (aggregate.timed_games > 0)
+                .then(|| round(aggregate.minutes / aggregate.timed_games as f64))
+                .into(),
+            averages.join(", ").into(),
+        ]);
+    }
+    Ok(Box::new(vec![upcoming, form, recent]))
+}
+
+#[cfg(test)]
+mod today_tests {
+    use crate::{
+        commands::{
+            mock::{db, run},
+            team::mock::seed,
+        },
+        model::{
+            id::{GameId, PlayerId, TeamId},
+            stat::StatLine,
+            team::Player,
+        },
+        store::Store,
+    };
+    use std::path::Path;
+
+    #[test]
+    fn test_today() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        seed(&db);
+        let store = Store::open(Path::new(&db)).unwrap();
+        store
+            .repo()
+            .upsert(&Player {
+                id: PlayerId::new("tatum"),
+                name: "Jayson Tatum".to_string(),
+                team: Some(TeamId::new("bos")),
+                position: None,
+                birth_date: None,
+            })
+            .unwrap();
+        let mut line = StatLine::new(GameId::new("4"), PlayerId::new("tatum"), TeamId::new("bos"));
+        line.minutes = Some(38.0);
+        line.set("pts", 31.0).set("reb", 9.0);
+        store.repo().upsert(&line).unwrap();
+
+        run(&["fav", "add", "team", "lakers", "--db", &db]).unwrap();
+        run(&["fav", "add", "player", "tatum", "--db", &db]).unwrap();
+        let output = run(&[
+            "today",
+            "--db",
+            &db,
+            "--date",
+            "2025-01-05",
+            "--format",
+            "csv",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            output,
+            "start,league,home,away,status,following\n\
+             2025-01-05 00:00,nba,Lakers,Celtics,scheduled,\"Lakers, Jayson Tatum\"\n\
+             \n\
+             team,gp,record,streak,form,next\n\
+             Lakers,3,2-1-0,W1,WLW,2025-01-05 vs Celtics\n\
+             \n\
+             player,team,gp,min,averages\n\
+             Jayson Tatum,Celtics,1,38,\"pts 31, reb 9\"\n"
+        );
+        assert!(!run(&[
+            "today",
+            "--db",
+            &db,
+            "--date",
+            "2025-01-06",
+            "--format",
+            "csv"
+        ])
+        .unwrap()
+        .contains("scheduled"));
+    }
+}
@@ -0,0 +1,374 @@
+use super::{game_odds, round, store};
+use crate::{
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        error::CLIError,
+        matches::ArgMatches,
+        option::{CommandOptionKwargs, CommandOptionKwargsBuilder, CommandOptionType},
+        output::{Cell, Output, Table},
+    },
+    model::{
+        game::{Game, Outcome},
+        team::Team,
+    },
+    stats::{
+        is_season,
+        resolve::resolve,
+        trends::{contexts, rolling_average, streak, GameContext, Situation, TrendSummary},
+        HomeAway,
+    },
+};
+use std::{collections::HashMap, error::Error, sync::LazyLock};
+
+static COUNT: CommandOptionType = CommandOptionType::Int(0);
+static WINDOW: CommandOptionType = CommandOptionType::Int(5);
+static COUNT_KWARGS: LazyLock<CommandOptionKwargs<'static>> =
+    LazyLock::new(|| CommandOptionKwargsBuilder::new().set_kind(&COUNT).build());
+static WINDOW_KWARGS: LazyLock<CommandOptionKwargs<'static>> = LazyLock::new(|| {
+    CommandOptionKwargsBuilder::new()
+        .set_kind(&WINDOW)
+        .set_default(&WINDOW)
+        .build()
+});
+
+/// Picks the games of a split
+type SplitFilter = fn(&GameContext) -> bool;
+
+/// Registers `statpack trends --team <name> [--situation] [--season] [--last] [--window]`
+pub(super) fn register(cli: &mut CLI<'static>) {
+    cli.create_command("trends")
+        .set_handler(run)
+        .create_option_kwargs(
+            "team",
+            "Team whose games are analyzed, by name",
+            &CommandOptionKwargs::REQUIRED,
+        )
+        .create_option(
+            "situation",
+            "Only games in the situation, e.g. \"away & rest<2\" or \"home & !b2b\"",
+        )
+        .create_option("season", "Season of the games, by name (2024-25) or ID")
+        .create_option_kwargs(
+            "last",
+            "Only the most recent N games in the situation",
+            &COUNT_KWARGS,
+        )
+        .create_option_kwargs(
+            "window",
+            "Number of games rolling averages span",
+            &WINDOW_KWARGS,
+        );
+}
+
+/// Breaks down a team's results, against the spread and on totals, in a situation
+fn run(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    let store = store(matches)?;
+    let situation: Option<Situation> = matches.value("situation").map(str::parse).transpose()?;
+    let window: usize = matches.value_of("window")?.unwrap_or(5);
+    if window == 0 {
+        return Err(CLIError::InvalidValue {
+            option: "--window".to_string(),
+            value: window.to_string(),
+            expected: "at least 1 game".to_string(),
+        }
+        .into());
+    }
+    let teams = store.repo::<Team>().all()?;
+    let team = resolve(
+        "team",
+        matches.value("team").unwrap_or_default(),
+        &teams,
+        |team| team.names().collect(),
+    )?;
+    let short: HashMap<&str, &str> = teams
+        .iter()
+        .map(|team| {
+            (
+                team.id.as_str(),
+                team.abbreviation.as_deref().unwrap_or(team.id.as_str()),
+            )
+        })
+        .collect();
+
+    let games: Vec<_> = store
+        .repo::<Game>()
+        .for_team(&team.id)?
+        .into_iter()
+        .filter(|game| {
+            matches
+                .value("season")
+                .is_none_or(|season| is_season(&game.season, season))
+        })
+        .collect();
+    let odds = game_odds(&store, &games)?;
+    let all = contexts(&team.id, &games, &odds);
+    let mut found: Vec<&GameContext> = all
+        .iter()
+        .filter(|context| {
+            situation
+                .as_ref()
+                .is_none_or(|situation| situation.matches(context))
+        })
+        .collect();
+    if let Some(last) = matches.value_of::<usize>("last")? {
+        found.drain(..found.len().saturating_sub(last));
+    }
+
+    let mut splits = Table::new(&[
+        "split",
+        "gp",
+        "w",
+        "l",
+        "d",
+        "pct",
+        "avg_for",
+        "avg_against",
+        "avg_margin",
+        "ats",
+        "o/u",
+    ]);
+    splits.set_title(&match &situation {
+        Some(situation) => format!("{} trends: {}", team.name, situation),
+        None => format!("{} trends", team.name),
+    });
+    splits.push(summary_row("all games", all.iter()));
+    if let Some(situation) = &situation {
+        splits.push(summary_row(&situation.to_string(), found.iter().copied()));
+    }
+    let split_filters: [(&str, SplitFilter); 7] = [
+        ("home", |context| context.side == HomeAway::Home),
+        ("away", |context| context.side == HomeAway::Away),
+        ("b2b", |context| context.rest == Some(0)),
+        ("1 day rest", |context| context.rest == Some(1)),
+        ("2+ days rest", |context| {
+            context.rest.is_some_and(|rest| rest >= 2)
+        }),
+        ("after win", |context| {
+            context.previous == Some(Outcome::Win)
+        }),
+        ("after loss", |context| {
+            context.previous == Some(Outcome::Loss)
+        }),
+    ];
+    for (split, filter) in split_filters {
+        let games: Vec<&GameContext> = found
+            .iter()
+            .copied()
+            .filter(|context| filter(context))
+            .collect();
+        if !games.is_empty() {
+            splits.push(summary_row(split, games));
+        }
+    }
+
+    let mut streaks = Table::new(&["streak", "current", "longest"]);
+    streaks.set_title("streaks");
+    let outcome: fn(&GameContext) -> Option<Outcome> = |context| Some(context.outcome);
+    let ats: fn(&GameContext) -> Option<Outcome> = |context| context.ats;
+    let over_under: fn(&GameContext) -> Option<Outcome> = |context| context.over_under;
+    for (name, graded, wanted) in [
+        ("win", outcome, Outcome::Win),
+        ("loss", outcome, Outcome::Loss),
+        ("cover", ats, Outcome::Win),
+        ("no cover", ats, Outcome::Loss),
+        ("over", over_under, Outcome::Win),
+        ("under", over_under, Outcome::Loss),
+    ] {
+        let run = streak(found.iter().map(|context| graded(context)), &wanted);
+        streaks.push(vec![
+            name.into(),
+            (run.current as i64).into(),
+            (run.longest as i64).into(),
+        ]);
+    }
+
+    let rolling = |values: Vec<f64>| rolling_average(&values, window);
+    let scored = rolling(
+        found
+            .iter()
+            .map(|context| f64::from(context.scored))
+            .collect(),
+    );
+    let conceded = rolling(
+        found
+            .iter()
+            .map(|context| f64::from(context.conceded))
+            .collect(),
+    );
+    let mut games = Table::new(&[
+        "date",
+        "opponent",
+        "side",
+        "rest",
+        "result",
+        "score",
+        "spread",
+        "ats",
+        "total",
+        "o/u",
+        "avg_for",
+        "avg_against",
+        "avg_margin",
+    ]);
+    games.set_title(&format!("rolling {} games", window));
+    for (index, context) in found.iter().enumerate() {
+        games.push(vec![
+            context.game.start.date_naive().to_string().into(),
+            short
+                .get(context.opponent.as_str())
+                .copied()
+                .unwrap_or(context.opponent.as_str())
+                .into(),
+            match context.side {
+                HomeAway::Home => "home",
+                HomeAway::Away => "away",
+            }
+            .into(),
+            context.rest.into(),
+            context.outcome.letter().to_string().into(),
+            format!("{}-{}", context.scored, context.conceded).into(),
+            context.spread.into(),
+            letter(context.ats, "W", "L"),
+            context.total.into(),
+            letter(context.over_under, "O", "U"),
+            round(scored[index]).into(),
+            round(conceded[index]).into(),
+            round(scored[index] - conceded[index]).into(),
+        ]);
+    }
+    Ok(Box::new(vec![splits, streaks, games]))
+}
+
+/// Lays out how the team fared in games as
+/// `split, gp, w, l, d, pct, avg_for, avg_against, avg_margin, ats, o/u`
+fn summary_row<'c, 'g: 'c>(
+    split: &str,
+    games: impl IntoIterator<Item = &'c GameContext<'g>>,
+) -> Vec<Cell> {
+    let summary = TrendSummary::new(games);
+    let record = summary.record;
+    let played = record.played();
+    let average = |total: f64| (played > 0).then(|| round(total / f64::from(played)));
+    vec![
+        split.into(),
+        played.into(),
+        record.wins.into(),
+        record.losses.into(),
+        record.draws.into(),
+        record.win_percentage().map(round).into(),
+        average(f64::from(record.scored)).into(),
+        average(f64::from(record.conceded)).into(),
+        average(record.differential() as f64).into(),
+        (summary.ats.graded() > 0)
+            .then(|| summary.ats.to_string())
+            .into(),
+        (summary.over_under.graded() > 0)
+            .then(|| summary.over_under.to_string())
+            .into(),
+    ]
+}
+
+/// Abbreviates a graded bet, `P` for a push
+fn letter(outcome: Option<Outcome>, win: &str, loss: &str) -> Cell {
+    outcome
+        .map(|outcome| match outcome {
+            Outcome::Win => win,
+            Outcome::Loss => loss,
+            Outcome::Draw => "P",
+        })
+        .into()
+}
+
+#[cfg(test)]
+mod trends_tests {
+    use crate::{
+        commands::{
+            mock::{db, run},
+            team::mock::seed,
+        },
+        model::{game::mock::game, odds::Market},
+        stats::h2h::mock::line,
+        store::Store,
+    };
+    use std::path::Path;
+
+    #[test]
+    fn test_trends() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = db(&dir);
+        seed(&db);
+        // lal gave 5.5 points at home to bos and won by 10
+        Store::open(Path::new(&db))
+            .unwrap()
+            .repo()
+            .upsert(&line(
+                &game("1", "lal", "bos", None),
+                "pinnacle",
+                Market::Spread,
+                -5.5,
+                1,
+            ))
+            .unwrap();
+
+        assert_eq!(
+            run(&[
+                "trends",
+                "--team",
+                "lakers",
+                "--situation",
+                "home",
+                "--window",
+                "2",
+                "--db",
+                &db,
+                "--format",
+                "csv",
+            ])
+            .unwrap(),
+            "split,gp,w,l,d,pct,avg_for,avg_against,avg_margin,ats,o/u\n\
+             all games,3,2,1,0,0.67,103.33,106,-2.67,1-0-0,\n\
+             home,2,2,0,0,1,104.5,99,5.5,1-0-0,\n\
+             home,2,2,0,0,1,104.5,99,5.5,1-0-0,\n\
+             b2b,1,1,0,0,1,99,98,1,,\n\
+             after loss,1,1,0,0,1,99,98,1,,\n\
+             \n\
+             streak,current,longest\n\
+             win,2,2\n\
+             loss,0,0\n\
+             cover,1,1\n\
+             no cover,0,0\n\
+             over,0,0\n\
+             under,0,0\n\
+             \n\
+             date,opponent,side,rest,result,score,spread,ats,total,o/u,avg_for,avg_against,\
+             avg_margin\n\
+             2025-01-01,BOS,home,,W,110-100,-5.5,W,,,110,100,10\n\
+             2025-01-03,DEN,home,0,W,99-98,,,,,104.5,99,5.5\n"
+        );
+        assert!(run(&[
+            "trends",
+            "--team",
+            "lakers",
+            "--situation",
+            "away & rest<2",
+            "--db",
+            &db
+        ])
+        .is_ok());
+        assert_eq!(
+            run(&[
+                "trends",
+                "--team",
+                "lakers",
+                "--situation",
+                "road",
+                "--db",
+                &db
+            ])
+            .unwrap_err()
+            .to_string(),
+            "invalid query 'road': unknown condition 'road'"
+        );
+    }
+}
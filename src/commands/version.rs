@@ -0,0 +1,71 @@
+use crate::{
+    build_info,
+    cli_toolkit::{
+        cli::CLI,
+        command::CLICommand,
+        matches::ArgMatches,
+        option::CommandOptionKwargs,
+        output::{Output, Table},
+    },
+};
+use std::error::Error;
+
+/// Registers `statpack version [--verbose]`
+pub(super) fn register(cli: &mut CLI<'static>) {
+    cli.create_command("version")
+        .set_handler(run)
+        .create_option_kwargs(
+            "verbose",
+            "Include build metadata for bug reports",
+            &CommandOptionKwargs::FLAG,
+        );
+}
+
+fn run(matches: &ArgMatches) -> Result<Box<dyn Output>, Box<dyn Error>> {
+    Ok(Box::new(report(matches.flag("verbose"))))
+}
+
+/// Describes the running build, with every piece of build metadata when `verbose`
+fn report(verbose: bool) -> Table {
+    if !verbose {
+        let mut table = Table::new(&["version"]);
+        table.push(vec![build_info::VERSION.into()]);
+        return table;
+    }
+
+    let mut table = Table::new(&["version", "commit", "build_date", "features", "data_schema"]);
+    table.push(vec![
+        build_info::VERSION.into(),
+        build_info::GIT_COMMIT.into(),
+        build_info::BUILD_DATE.into(),
+        build_info::features().join(",").into(),
+        build_info::DATA_SCHEMA_VERSION.into(),
+    ]);
+    table
+}
+
+#[cfg(test)]
+mod report_tests {
+    use super::*;
+    use crate::cli_toolkit::output::Cell;
+
+    #[test]
+    fn test_report() {
+        assert_eq!(report(false).rows, [[Cell::from(build_info::VERSION)]]);
+    }
+
+    #[test]
+    fn test_report_verbose() {
+        let report = report(true);
+
+        assert_eq!(
+            report.columns,
+            ["version", "commit", "build_date", "features", "data_schema"]
+        );
+        assert_eq!(report.rows[0][1], Cell::from(build_info::GIT_COMMIT));
+        assert_eq!(
+            report.rows[0][4],
+            Cell::from(build_info::DATA_SCHEMA_VERSION)
+        );
+    }
+}
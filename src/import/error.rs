@@ -0,0 +1,61 @@
+use std::{error::Error, fmt};
+
+/// Represents the errors that abort an import as a whole
+///
+/// Problems with single rows do not abort an import, they are reported as `RowError`s.
+#[derive(Debug)]
+pub enum ImportError {
+    Io(std::io::Error),
+    Csv(csv::Error),
+    Json(serde_json::Error),
+    /// The file is readable but not laid out the way imports expect
+    Format(String),
+}
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Io(error) => write!(f, "{}", error),
+            ImportError::Csv(error) => write!(f, "invalid CSV: {}", error),
+            ImportError::Json(error) => write!(f, "invalid JSON: {}", error),
+            ImportError::Format(message) => write!(f, "{}", message),
+        }
+    }
+}
+impl Error for ImportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ImportError::Io(error) => Some(error),
+            ImportError::Csv(error) => Some(error),
+            ImportError::Json(error) => Some(error),
+            ImportError::Format(_) => None,
+        }
+    }
+}
+impl From<std::io::Error> for ImportError {
+    fn from(error: std::io::Error) -> Self {
+        ImportError::Io(error)
+    }
+}
+impl From<csv::Error> for ImportError {
+    fn from(error: csv::Error) -> Self {
+        ImportError::Csv(error)
+    }
+}
+impl From<serde_json::Error> for ImportError {
+    fn from(error: serde_json::Error) -> Self {
+        ImportError::Json(error)
+    }
+}
+
+/// Represents a row that was skipped because its data is invalid
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowError {
+    /// Line of the row in a CSV file, or position of the object (from 1) in a JSON array
+    pub row: usize,
+    pub message: String,
+}
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "row {}: {}", self.row, self.message)
+    }
+}
@@ -0,0 +1,156 @@
+use self::{error::RowError, profile::Profile, reader::Row};
+use crate::{
+    model::{game::Game, sport::Sport},
+    store::{error::StoreError, record::Record, repository::Repository},
+};
+use rusqlite::Connection;
+use std::collections::HashSet;
+
+pub mod error;
+pub mod profile;
+pub mod reader;
+
+/// Represents the outcome of an import
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ImportReport {
+    /// Number of rows read from the file
+    pub rows: usize,
+    /// Games that were not stored yet
+    pub inserted: usize,
+    /// Games that were already stored and got replaced
+    pub updated: usize,
+    /// Rows repeating the natural key (league, date, home, away) of an earlier row
+    pub duplicates: usize,
+    /// Rows skipped because of invalid data
+    pub errors: Vec<RowError>,
+}
+
+/// Imports the games of `rows` with the profile of the sport.
+///
+/// Valid rows are stored even when others are invalid, leagues, seasons, teams and venues
+/// they refer to are created when missing. Run it inside `Store::transaction` to keep
+/// the import atomic, or `Store::dry_run` to only validate and count.
+///
+/// ## Returns
+///
+/// A report counting what was (or would have been) stored and listing the invalid rows.
+pub fn import(
+    conn: &Connection,
+    sport: Sport,
+    league: Option<&str>,
+    rows: &[Row],
+) -> Result<ImportReport, StoreError> {
+    let profile = Profile::for_sport(sport);
+    let games = Repository::<Game>::new(conn);
+    let mut report = ImportReport {
+        rows: rows.len(),
+        ..Default::default()
+    };
+    let mut seen = HashSet::new();
+
+    for row in rows {
+        let imported = match profile.parse(sport, row, league) {
+            Ok(imported) => imported,
+            Err(message) => {
+                report.errors.push(RowError {
+                    row: row.number,
+                    message,
+                });
+                continue;
+            }
+        };
+        if !seen.insert(imported.game.id.clone()) {
+            report.duplicates += 1;
+            continue;
+        }
+
+        insert_missing(conn, &imported.league)?;
+        insert_missing(conn, &imported.season)?;
+        for team in &imported.teams {
+            insert_missing(conn, team)?;
+        }
+        if let Some(venue) = &imported.venue {
+            insert_missing(conn, venue)?;
+        }
+        if games.get(imported.game.id.as_str())?.is_some() {
+            report.updated += 1;
+        } else {
+            report.inserted += 1;
+        }
+        games.upsert(&imported.game)?;
+    }
+    Ok(report)
+}
+
+/// Stores the record unless one with the same key exists, keeping edits made since
+fn insert_missing<T: Record>(conn: &Connection, record: &T) -> Result<(), StoreError> {
+    let repo = Repository::<T>::new(conn);
+    if repo.get(&record.key())?.is_none() {
+        repo.upsert(record)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod import_tests {
+    use super::{reader::read_csv, *};
+    use crate::{
+        model::{sport::Season, team::Team},
+        store::mock::store,
+    };
+
+    const CSV: &str = "\
+Div,Date,Time,HomeTeam,AwayTeam,FTHG,FTAG
+E0,16/08/2024,20:00,Man United,Fulham,1,0
+E0,17/08/2024,12:30,Ipswich,Liverpool,0,2
+E0,16/08/2024,20:00,Man United,Fulham,1,0
+E0,31/02/2024,15:00,Arsenal,Wolves,2,0
+E0,17/08/2024,15:00,Arsenal,Wolves,2,
+";
+
+    #[test]
+    fn test_import() {
+        let mut store = store();
+        let rows = read_csv(CSV.as_bytes()).unwrap();
+        let report = store
+            .transaction(|tx| import(tx, Sport::Soccer, None, &rows))
+            .unwrap();
+
+        assert_eq!(report.rows, 5);
+        assert_eq!((report.inserted, report.updated), (2, 0));
+        assert_eq!(report.duplicates, 1);
+        assert_eq!(
+            report.errors,
+            [
+                RowError {
+                    row: 5,
+                    message: "invalid date '31/02/2024' in 'Date'".to_string()
+                },
+                RowError {
+                    row: 6,
+                    message: "'FTHG' is given without 'FTAG'".to_string()
+                },
+            ]
+        );
+        assert_eq!(store.repo::<Game>().count().unwrap(), 2);
+        assert_eq!(store.repo::<Team>().count().unwrap(), 4);
+        assert_eq!(store.repo::<Season>().count().unwrap(), 1);
+
+        let again = store
+            .transaction(|tx| import(tx, Sport::Soccer, None, &rows))
+            .unwrap();
+        assert_eq!((again.inserted, again.updated), (0, 2));
+    }
+
+    #[test]
+    fn test_import_dry_run() {
+        let mut store = store();
+        let rows = read_csv(CSV.as_bytes()).unwrap();
+        let report = store
+            .dry_run(|tx| import(tx, Sport::Soccer, None, &rows))
+            .unwrap();
+
+        assert_eq!(report.inserted, 2);
+        assert_eq!(store.repo::<Game>().count().unwrap(), 0);
+    }
+}
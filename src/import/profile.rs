@@ -0,0 +1,447 @@
+use super::reader::Row;
+use crate::model::{
+    game::{Game, GameExtension, GameStatus},
+    id::{GameId, LeagueId, SeasonId, TeamId, VenueId},
+    sport::{League, Season, Sport},
+    team::{Team, Venue},
+};
+use chrono::{NaiveDate, NaiveTime};
+
+/// Represents the column layout of the import files of a sport
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    pub league: &'static str,
+    /// Season name column, seasons are derived from the game date when missing
+    pub season: Option<&'static str>,
+    pub date: &'static str,
+    /// Formats tried in order to parse the date column
+    pub date_formats: &'static [&'static str],
+    /// Kick-off time column (`HH:MM`, UTC), games start at midnight when missing
+    pub time: Option<&'static str>,
+    pub home: &'static str,
+    pub away: &'static str,
+    pub home_score: &'static str,
+    pub away_score: &'static str,
+    pub venue: Option<&'static str>,
+    /// `(home, away)` half time score columns, read for soccer
+    pub half_time: Option<(&'static str, &'static str)>,
+    /// Number of overtimes column, read for basketball and hockey
+    pub overtimes: Option<&'static str>,
+    /// Column telling if a hockey game was decided by a shoot-out
+    pub shootout: Option<&'static str>,
+    /// Number of innings column, read for baseball
+    pub innings: Option<&'static str>,
+}
+impl Profile {
+    /// The football-data.co.uk layout most soccer dumps come in
+    pub const SOCCER: Profile = Profile {
+        league: "Div",
+        season: None,
+        date: "Date",
+        date_formats: &["%d/%m/%y", "%d/%m/%Y"],
+        time: Some("Time"),
+        home: "HomeTeam",
+        away: "AwayTeam",
+        home_score: "FTHG",
+        away_score: "FTAG",
+        venue: None,
+        half_time: Some(("HTHG", "HTAG")),
+        overtimes: None,
+        shootout: None,
+        innings: None,
+    };
+    /// The snake_case layout used for every other sport
+    pub const GENERIC: Profile = Profile {
+        league: "league",
+        season: Some("season"),
+        date: "date",
+        date_formats: &["%Y-%m-%d", "%m/%d/%Y"],
+        time: Some("time"),
+        home: "home",
+        away: "away",
+        home_score: "home_score",
+        away_score: "away_score",
+        venue: Some("venue"),
+        half_time: None,
+        overtimes: None,
+        shootout: None,
+        innings: None,
+    };
+    /// The snake_case layout, with an `overtimes` column
+    pub const BASKETBALL: Profile = Profile {
+        overtimes: Some("overtimes"),
+        ..Profile::GENERIC
+    };
+    /// The snake_case layout, with `overtimes` and `shootout` columns
+    pub const HOCKEY: Profile = Profile {
+        overtimes: Some("overtimes"),
+        shootout: Some("shootout"),
+        ..Profile::GENERIC
+    };
+    /// The snake_case layout, with an `innings` column
+    pub const BASEBALL: Profile = Profile {
+        innings: Some("innings"),
+        ..Profile::GENERIC
+    };
+
+    /// Returns the profile import files of the sport are read with
+    ///
+    /// Sports without game details to import (cricket, tennis, golf...) use `GENERIC`.
+    pub fn for_sport(sport: Sport) -> &'static Profile {
+        match sport {
+            Sport::Soccer => &Profile::SOCCER,
+            Sport::Basketball => &Profile::BASKETBALL,
+            Sport::Hockey => &Profile::HOCKEY,
+            Sport::Baseball => &Profile::BASEBALL,
+            _ => &Profile::GENERIC,
+        }
+    }
+    /// Validates a row and converts it into a game along with the records it refers to
+    ///
+    /// ## Returns
+    ///
+    /// The imported game, or a message describing the first problem found in the row.
+    pub fn parse(
+        &self,
+        sport: Sport,
+        row: &Row,
+        league: Option<&str>,
+    ) -> Result<ImportedGame, String> {
+        let required = |column: &str| {
+            row.get(column)
+                .ok_or_else(|| format!("missing value for '{}'", column))
+        };
+
+        let league_name = match league {
+            Some(league) => league,
+            None => required(self.league)?,
+        };
+        let date = required(self.date)?;
+        let date = self
+            .date_formats
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(date, format).ok())
+            .ok_or_else(|| format!("invalid date '{}' in '{}'", date, self.date))?;
+        let time = match self.time.and_then(|column| row.get(column)) {
+            Some(time) => NaiveTime::parse_from_str(time, "%H:%M")
+                .map_err(|_| format!("invalid time '{}'", time))?,
+            None => NaiveTime::MIN,
+        };
+        let (home_name, away_name) = (required(self.home)?, required(self.away)?);
+        if home_name.eq_ignore_ascii_case(away_name) {
+            return Err(format!("'{}' can not play itself", home_name));
+        }
+        let score = read_score(row, self.home_score, self.away_score)?;
+
+        let league = League {
+            id: LeagueId::derive(&[league_name]),
+            sport,
+            name: league_name.to_string(),
+            country: None,
+            rules: None,
+        };
+        let mut season = Season::for_date(&league.id, sport, date);
+        if let Some(name) = self.season.and_then(|column| row.get(column)) {
+            season.id = SeasonId::derive(&[league.id.as_str(), name]);
+            season.name = name.to_string();
+        }
+        let team = |name: &str| Team {
+            id: TeamId::derive(&[league.id.as_str(), name]),
+            league: league.id.clone(),
+            name: name.to_string(),
+            abbreviation: None,
+            venue: None,
+            aliases: vec![],
+        };
+        let (home, away) = (team(home_name), team(away_name));
+        let venue = self
+            .venue
+            .and_then(|column| row.get(column))
+            .map(|name| Venue {
+                id: VenueId::derive(&[name]),
+                name: name.to_string(),
+                city: None,
+                country: None,
+                capacity: None,
+            });
+        let count = |column: Option<&str>| read_count(row, column);
+        let extension = match sport {
+            Sport::Soccer => match self.half_time {
+                Some((home_column, away_column)) => Some(GameExtension::Soccer {
+                    half_time: read_score(row, home_column, away_column)?,
+                    extra_time: false,
+                    penalties: None,
+                }),
+                None => None,
+            },
+            Sport::Basketball => {
+                count(self.overtimes)?.map(|overtimes| GameExtension::Basketball { overtimes })
+            }
+            Sport::Hockey => match (count(self.overtimes)?, read_flag(row, self.shootout)?) {
+                (None, None) => None,
+                (overtimes, shootout) => Some(GameExtension::Hockey {
+                    overtimes: overtimes.unwrap_or_default(),
+                    shootout: shootout.unwrap_or_default(),
+                }),
+            },
+            Sport::Baseball => {
+                count(self.innings)?.map(|innings| GameExtension::Baseball { innings })
+            }
+            _ => None,
+        };
+
+        let game = Game {
+            id: GameId::derive(&[league.id.as_str(), &date.to_string(), home_name, away_name]),
+            league: league.id.clone(),
+            season: season.id.clone(),
+            start: date.and_time(time).and_utc(),
+            home: home.id.clone(),
+            away: away.id.clone(),
+            venue: venue.as_ref().map(|venue| venue.id.clone()),
+            neutral: false,
+            status: if score.is_some() {
+                GameStatus::Final
+            } else {
+                GameStatus::Scheduled
+            },
+            home_score: score.map(|(home, _)| home),
+            away_score: score.map(|(_, away)| away),
+            extension,
+        };
+        Ok(ImportedGame {
+            league,
+            season,
+            teams: [home, away],
+            venue,
+            game,
+        })
+    }
+}
+
+/// Reads a `(home, away)` score, which is either fully given or fully missing
+fn read_score(row: &Row, home: &str, away: &str) -> Result<Option<(u32, u32)>, String> {
+    let parse = |column: &str| {
+        row.get(column)
+            .map(|value| {
+                value
+                    .parse::<u32>()
+                    .map_err(|_| format!("invalid score '{}' in '{}'", value, column))
+            })
+            .transpose()
+    };
+    match (parse(home)?, parse(away)?) {
+        (Some(home), Some(away)) => Ok(Some((home, away))),
+        (None, None) => Ok(None),
+        (Some(_), None) => Err(format!("'{}' is given without '{}'", home, away)),
+        (None, Some(_)) => Err(format!("'{}' is given without '{}'", away, home)),
+    }
+}
+
+/// Reads a whole number column, which is missing when the profile or the row lacks it
+fn read_count(row: &Row, column: Option<&str>) -> Result<Option<u32>, String> {
+    let Some(column) = column else {
+        return Ok(None);
+    };
+    row.get(column)
+        .map(|value| {
+            value
+                .parse::<u32>()
+                .map_err(|_| format!("invalid number '{}' in '{}'", value, column))
+        })
+        .transpose()
+}
+
+/// Reads a yes/no column the way spreadsheets write it, missing like `read_count`
+fn read_flag(row: &Row, column: Option<&str>) -> Result<Option<bool>, String> {
+    let Some(column) = column else {
+        return Ok(None);
+    };
+    row.get(column)
+        .map(|value| match value.to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "y" | "x" => Ok(true),
+            "0" | "false" | "no" | "n" => Ok(false),
+            _ => Err(format!("invalid flag '{}' in '{}'", value, column)),
+        })
+        .transpose()
+}
+
+/// Represents a game read from an import file and the records it refers to
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedGame {
+    pub league: League,
+    pub season: Season,
+    /// `[home, away]`
+    pub teams: [Team; 2],
+    pub venue: Option<Venue>,
+    pub game: Game,
+}
+
+#[cfg(test)]
+mod parse_tests {
+    use super::*;
+
+    fn row(fields: &[(&str, &str)]) -> Row {
+        Row {
+            number: 2,
+            fields: fields
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_soccer() {
+        let row = row(&[
+            ("Div", "E0"),
+            ("Date", "16/08/2024"),
+            ("Time", "20:00"),
+            ("HomeTeam", "Man United"),
+            ("AwayTeam", "Fulham"),
+            ("FTHG", "1"),
+            ("FTAG", "0"),
+            ("HTHG", "0"),
+            ("HTAG", "0"),
+        ]);
+        let imported = Profile::SOCCER.parse(Sport::Soccer, &row, None).unwrap();
+
+        assert_eq!(imported.game.id.as_str(), "e0:2024-08-16:man-united:fulham");
+        assert_eq!(imported.game.season.as_str(), "e0:2024-25");
+        assert_eq!(
+            imported.game.start.to_rfc3339(),
+            "2024-08-16T20:00:00+00:00"
+        );
+        assert_eq!(imported.game.status, GameStatus::Final);
+        assert_eq!(imported.teams[0].id.as_str(), "e0:man-united");
+        assert_eq!(
+            imported.game.extension,
+            Some(GameExtension::Soccer {
+                half_time: Some((0, 0)),
+                extra_time: false,
+                penalties: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_generic() {
+        let row = row(&[
+            ("date", "2025-01-01"),
+            ("home", "Lakers"),
+            ("away", "Celtics"),
+            ("season", "2024-25"),
+            ("venue", "Crypto.com Arena"),
+        ]);
+        let imported = Profile::GENERIC
+            .parse(Sport::Basketball, &row, Some("NBA"))
+            .unwrap();
+
+        assert_eq!(imported.league.id.as_str(), "nba");
+        assert_eq!(imported.season.id.as_str(), "nba:2024-25");
+        assert_eq!(imported.game.status, GameStatus::Scheduled);
+        assert_eq!(
+            imported.game.venue.as_ref().map(VenueId::as_str),
+            Some("crypto-com-arena")
+        );
+    }
+
+    #[test]
+    fn test_parse_sport_columns() {
+        let parse = |profile: &Profile, sport, fields: &[(&str, &str)]| {
+            let mut fields = fields.to_vec();
+            fields.extend([("date", "2025-01-01"), ("home", "Home"), ("away", "Away")]);
+            profile.parse(sport, &row(&fields), Some("League"))
+        };
+
+        assert_eq!(Profile::for_sport(Sport::Hockey), &Profile::HOCKEY);
+        assert_eq!(Profile::for_sport(Sport::Tennis), &Profile::GENERIC);
+        assert_eq!(
+            parse(
+                &Profile::BASKETBALL,
+                Sport::Basketball,
+                &[("overtimes", "2")]
+            )
+            .unwrap()
+            .game
+            .extension,
+            Some(GameExtension::Basketball { overtimes: 2 })
+        );
+        assert_eq!(
+            parse(&Profile::HOCKEY, Sport::Hockey, &[("shootout", "Yes")])
+                .unwrap()
+                .game
+                .extension,
+            Some(GameExtension::Hockey {
+                overtimes: 0,
+                shootout: true
+            })
+        );
+        assert_eq!(
+            parse(&Profile::BASEBALL, Sport::Baseball, &[("innings", "10")])
+                .unwrap()
+                .game
+                .extension,
+            Some(GameExtension::Baseball { innings: 10 })
+        );
+        assert_eq!(
+            parse(&Profile::BASEBALL, Sport::Baseball, &[])
+                .unwrap()
+                .game
+                .extension,
+            None
+        );
+        assert_eq!(
+            parse(&Profile::HOCKEY, Sport::Hockey, &[("shootout", "maybe")]).unwrap_err(),
+            "invalid flag 'maybe' in 'shootout'"
+        );
+        assert_eq!(
+            parse(
+                &Profile::BASKETBALL,
+                Sport::Basketball,
+                &[("overtimes", "one")]
+            )
+            .unwrap_err(),
+            "invalid number 'one' in 'overtimes'"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let base = [
+            ("league", "nba"),
+            ("date", "2025-01-01"),
+            ("home", "Lakers"),
+            ("away", "Celtics"),
+        ];
+        let parse = |fields: &[(&str, &str)]| {
+            let mut row = row(&base);
+            row.fields.extend(
+                fields
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string())),
+            );
+            Profile::GENERIC.parse(Sport::Basketball, &row, None)
+        };
+
+        assert_eq!(
+            parse(&[("date", "01.01.2025")]).unwrap_err(),
+            "invalid date '01.01.2025' in 'date'"
+        );
+        assert_eq!(
+            parse(&[("home", " ")]).unwrap_err(),
+            "missing value for 'home'"
+        );
+        assert_eq!(
+            parse(&[("home_score", "110")]).unwrap_err(),
+            "'home_score' is given without 'away_score'"
+        );
+        assert_eq!(
+            parse(&[("home_score", "110"), ("away_score", "-1")]).unwrap_err(),
+            "invalid score '-1' in 'away_score'"
+        );
+        assert_eq!(
+            parse(&[("away", "lakers")]).unwrap_err(),
+            "'Lakers' can not play itself"
+        );
+    }
+}
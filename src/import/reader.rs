@@ -0,0 +1,140 @@
+use super::error::ImportError;
+use serde_json::Value;
+use std::{collections::HashMap, fs::File, io::Read, path::Path};
+
+/// Represents a single record of an import file, keyed by column name
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Row {
+    /// Line of the row in a CSV file, or position of the object (from 1) in a JSON array
+    pub number: usize,
+    pub fields: HashMap<String, String>,
+}
+impl Row {
+    /// Returns the trimmed value of a column, treating blank values as missing
+    pub fn get(&self, column: &str) -> Option<&str> {
+        self.fields
+            .get(column)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+/// Reads every row of a `.csv` or `.json` file
+pub fn read_file(path: &Path) -> Result<Vec<Row>, ImportError> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase);
+    match extension.as_deref() {
+        Some("csv") => read_csv(File::open(path)?),
+        Some("json") => read_json(File::open(path)?),
+        _ => Err(ImportError::Format(format!(
+            "unsupported file '{}', expected a .csv or .json file",
+            path.display()
+        ))),
+    }
+}
+
+/// Reads CSV rows, the first line holding the column names
+pub fn read_csv(mut reader: impl Read) -> Result<Vec<Row>, ImportError> {
+    // Line numbers are counted from byte offsets, the reader's own count skips blank
+    // lines, and a record's offset points at the blank lines preceding it. Offsets only
+    // grow, so only the bytes since the previous record are scanned for newlines
+    let mut data = vec![];
+    reader.read_to_end(&mut data)?;
+    let (mut scanned, mut newlines) = (0, 0);
+    let mut line = |mut byte: usize| {
+        while matches!(data.get(byte), Some(b'\r' | b'\n')) {
+            byte += 1;
+        }
+        newlines += data[scanned..byte]
+            .iter()
+            .filter(|&&char| char == b'\n')
+            .count();
+        scanned = byte;
+        1 + newlines
+    };
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::Headers)
+        .from_reader(data.as_slice());
+    let headers = reader.headers()?.clone();
+
+    let mut rows = vec![];
+    for record in reader.records() {
+        let record = record?;
+        rows.push(Row {
+            number: record
+                .position()
+                .map_or(0, |position| line(position.byte() as usize)),
+            fields: headers
+                .iter()
+                .zip(record.iter())
+                .map(|(header, value)| (header.to_string(), value.to_string()))
+                .collect(),
+        });
+    }
+    Ok(rows)
+}
+
+/// Reads a JSON array of flat objects, numbers and booleans are kept as their JSON text
+pub fn read_json(reader: impl Read) -> Result<Vec<Row>, ImportError> {
+    let Value::Array(items) = serde_json::from_reader(reader)? else {
+        return Err(ImportError::Format(
+            "expected a JSON array of objects".to_string(),
+        ));
+    };
+
+    let mut rows = vec![];
+    for (index, item) in items.into_iter().enumerate() {
+        let Value::Object(object) = item else {
+            return Err(ImportError::Format(format!(
+                "item {} is not a JSON object",
+                index + 1
+            )));
+        };
+        let fields = object
+            .into_iter()
+            .filter_map(|(key, value)| match value {
+                Value::Null => None,
+                Value::String(value) => Some((key, value)),
+                value => Some((key, value.to_string())),
+            })
+            .collect();
+        rows.push(Row {
+            number: index + 1,
+            fields,
+        });
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod read_tests {
+    use super::*;
+
+    #[test]
+    fn test_read_csv() {
+        let rows = read_csv("home, away\nArsenal,Chelsea\n\nLeeds,\"Spurs, London\"\n".as_bytes())
+            .unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].number, 2);
+        assert_eq!(rows[0].get("away"), Some("Chelsea"));
+        assert_eq!(rows[1].number, 4);
+        assert_eq!(rows[1].get("away"), Some("Spurs, London"));
+        assert_eq!(rows[1].get("venue"), None);
+    }
+
+    #[test]
+    fn test_read_json() {
+        let rows =
+            read_json(r#"[{"home":"Lakers","home_score":110,"venue":null}]"#.as_bytes()).unwrap();
+
+        assert_eq!(rows[0].number, 1);
+        assert_eq!(rows[0].get("home_score"), Some("110"));
+        assert_eq!(rows[0].get("venue"), None);
+        assert!(read_json(r#"{"home":"Lakers"}"#.as_bytes()).is_err());
+        assert!(read_json(r#"[1]"#.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,10 @@
+pub mod build_info;
+pub mod cli_toolkit;
+pub mod commands;
+pub mod import;
+pub mod model;
+pub mod odds;
+pub mod provider;
+pub mod stats;
+pub mod store;
+pub mod utils;
@@ -1,8 +1,9 @@
-use crate::cli_toolkit::command::CLICommand;
-use cli_toolkit::cli::CLI;
-
-pub mod cli_toolkit;
-pub mod utils;
+use statpack::commands;
 
 #[doc(hidden)]
-fn main() {}
+fn main() {
+    if let Err(error) = commands::cli().run(std::env::args().skip(1)) {
+        eprintln!("error: {}", error);
+        std::process::exit(1);
+    }
+}
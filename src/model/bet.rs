@@ -0,0 +1,126 @@
+use super::{
+    id::GameId,
+    odds::{Market, Selection},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Represents how a bet was settled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BetResult {
+    Won,
+    Lost,
+    /// Landed on the line, the stake is returned
+    Push,
+    /// Cancelled by the book, the stake is returned
+    Void,
+}
+impl BetResult {
+    pub const ALL: [BetResult; 4] = [
+        BetResult::Won,
+        BetResult::Lost,
+        BetResult::Push,
+        BetResult::Void,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BetResult::Won => "won",
+            BetResult::Lost => "lost",
+            BetResult::Push => "push",
+            BetResult::Void => "void",
+        }
+    }
+}
+impl fmt::Display for BetResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+impl FromStr for BetResult {
+    type Err = String;
+
+    fn from_str(result: &str) -> Result<Self, Self::Err> {
+        BetResult::ALL
+            .into_iter()
+            .find(|item| item.name() == result)
+            .ok_or_else(|| format!("unknown bet result '{}'", result))
+    }
+}
+
+/// Represents a bet placed on a selection, settled once its game is graded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bet {
+    /// Number of the bet in the ledger, from 1
+    pub id: u32,
+    pub placed_at: DateTime<Utc>,
+    pub game: GameId,
+    /// Bookmaker the bet was placed with
+    pub book: String,
+    pub market: Market,
+    pub selection: Selection,
+    /// Spread or total taken, from the point of view of the selection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<f64>,
+    /// Decimal odds taken
+    pub odds: f64,
+    pub stake: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<BetResult>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settled_at: Option<DateTime<Utc>>,
+}
+impl Bet {
+    /// Records the result of the bet
+    pub fn settle(&mut self, result: BetResult, at: DateTime<Utc>) -> &mut Self {
+        self.result = Some(result);
+        self.settled_at = Some(at);
+        self
+    }
+    /// Returns what a settled bet won, negative when it lost
+    pub fn profit(&self) -> Option<f64> {
+        Some(match self.result? {
+            BetResult::Won => self.stake * (self.odds - 1.0),
+            BetResult::Lost => -self.stake,
+            BetResult::Push | BetResult::Void => 0.0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod bet_tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_bet() {
+        let at = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let mut bet = Bet {
+            id: 1,
+            placed_at: at,
+            game: GameId::new("1"),
+            book: "pinnacle".to_string(),
+            market: Market::Spread,
+            selection: Selection::Home,
+            line: Some(-3.5),
+            odds: 1.91,
+            stake: 100.0,
+            result: None,
+            settled_at: None,
+        };
+
+        assert_eq!(bet.profit(), None);
+        assert!((bet.settle(BetResult::Won, at).profit().unwrap() - 91.0).abs() < 1e-9);
+        assert_eq!(bet.settle(BetResult::Lost, at).profit(), Some(-100.0));
+        assert_eq!(bet.settle(BetResult::Void, at).profit(), Some(0.0));
+        assert_eq!(
+            serde_json::from_str::<Bet>(&serde_json::to_string(&bet).unwrap()).unwrap(),
+            bet
+        );
+        for result in BetResult::ALL {
+            assert_eq!(result.name().parse::<BetResult>(), Ok(result));
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Represents what a favorite refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FavoriteKind {
+    Sport,
+    League,
+    Team,
+    Player,
+}
+impl FavoriteKind {
+    pub const ALL: [FavoriteKind; 4] = [
+        FavoriteKind::Sport,
+        FavoriteKind::League,
+        FavoriteKind::Team,
+        FavoriteKind::Player,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FavoriteKind::Sport => "sport",
+            FavoriteKind::League => "league",
+            FavoriteKind::Team => "team",
+            FavoriteKind::Player => "player",
+        }
+    }
+}
+impl fmt::Display for FavoriteKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+impl FromStr for FavoriteKind {
+    type Err = String;
+
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        FavoriteKind::ALL
+            .into_iter()
+            .find(|item| item.name() == kind)
+            .ok_or_else(|| format!("unknown favorite kind '{}'", kind))
+    }
+}
+
+/// Represents a sport, league, team or player the user follows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Favorite {
+    pub kind: FavoriteKind,
+    /// ID of the followed record, or the sport name
+    pub id: String,
+    /// Name of the followed record when it was added
+    pub name: String,
+    pub added_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod favorite_tests {
+    use super::*;
+
+    #[test]
+    fn test_favorite_kind() {
+        for kind in FavoriteKind::ALL {
+            assert_eq!(kind.name().parse::<FavoriteKind>(), Ok(kind));
+            assert_eq!(
+                serde_json::to_string(&kind).unwrap(),
+                format!("\"{}\"", kind)
+            );
+        }
+        assert!("coach".parse::<FavoriteKind>().is_err());
+    }
+}
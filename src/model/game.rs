@@ -0,0 +1,234 @@
+use super::{
+    id::{EventId, GameId, LeagueId, PlayerId, SeasonId, TeamId, VenueId},
+    sport::Sport,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How long after its start a game whose sport is not known is taken to be over
+pub const UNKNOWN_SPORT_DURATION: Duration = Duration::hours(6);
+
+/// Represents where a game stands
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GameStatus {
+    #[default]
+    Scheduled,
+    Live,
+    Final,
+    Postponed,
+    Cancelled,
+}
+impl GameStatus {
+    /// Returns the kebab-case name used in output and storage
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameStatus::Scheduled => "scheduled",
+            GameStatus::Live => "live",
+            GameStatus::Final => "final",
+            GameStatus::Postponed => "postponed",
+            GameStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// Represents the result of a game from one team's point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+impl Outcome {
+    /// Returns the letter the outcome is abbreviated to in results and form ("W", "L", "D")
+    pub fn letter(&self) -> char {
+        match self {
+            Outcome::Win => 'W',
+            Outcome::Loss => 'L',
+            Outcome::Draw => 'D',
+        }
+    }
+}
+
+/// Represents sport specific details of a game that do not fit the shared model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "sport", rename_all = "kebab-case")]
+pub enum GameExtension {
+    Soccer {
+        /// `(home, away)` goals at half time
+        half_time: Option<(u32, u32)>,
+        extra_time: bool,
+        /// `(home, away)` penalty shoot-out score
+        penalties: Option<(u32, u32)>,
+    },
+    Basketball {
+        overtimes: u32,
+    },
+    Hockey {
+        overtimes: u32,
+        shootout: bool,
+    },
+    Baseball {
+        innings: u32,
+    },
+    Tennis {
+        /// `(home, away)` games per set
+        sets: Vec<(u32, u32)>,
+    },
+    Cricket {
+        /// `(runs, wickets)` per innings for `(home, away)`
+        innings: Vec<((u32, u32), (u32, u32))>,
+    },
+}
+
+/// Represents a single match/game between two teams
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Game {
+    pub id: GameId,
+    pub league: LeagueId,
+    pub season: SeasonId,
+    pub start: DateTime<Utc>,
+    pub home: TeamId,
+    pub away: TeamId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub venue: Option<VenueId>,
+    /// Played at a venue neither team calls home
+    #[serde(default)]
+    pub neutral: bool,
+    #[serde(default)]
+    pub status: GameStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub home_score: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub away_score: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extension: Option<GameExtension>,
+}
+impl Game {
+    /// Returns the `(team, opponent)` score for the given team, if it played and the game has a score
+    pub fn score_for(&self, team: &TeamId) -> Option<(u32, u32)> {
+        let (home, away) = (self.home_score?, self.away_score?);
+        if *team == self.home {
+            Some((home, away))
+        } else if *team == self.away {
+            Some((away, home))
+        } else {
+            None
+        }
+    }
+    /// Returns the outcome of a final game for the given team
+    pub fn outcome_for(&self, team: &TeamId) -> Option<Outcome> {
+        if self.status != GameStatus::Final {
+            return None;
+        }
+        let (score, opponent) = self.score_for(team)?;
+        Some(match score.cmp(&opponent) {
+            std::cmp::Ordering::Greater => Outcome::Win,
+            std::cmp::Ordering::Less => Outcome::Loss,
+            std::cmp::Ordering::Equal => Outcome::Draw,
+        })
+    }
+    /// Returns the time the game is sure to be over by, given the sport of its league
+    pub fn over_by(&self, sport: Option<Sport>) -> DateTime<Utc> {
+        self.start + sport.map_or(UNKNOWN_SPORT_DURATION, |sport| sport.max_duration())
+    }
+    /// Returns the team playing against the given team
+    pub fn opponent_of(&self, team: &TeamId) -> Option<&TeamId> {
+        if *team == self.home {
+            Some(&self.away)
+        } else if *team == self.away {
+            Some(&self.home)
+        } else {
+            None
+        }
+    }
+}
+
+/// Represents a notable moment of a game (goal, card, substitution, ...)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    pub id: EventId,
+    pub game: GameId,
+    /// Sport specific kind ("goal", "yellow-card", "touchdown")
+    pub kind: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period: Option<u32>,
+    /// Game clock when the event happened ("45+2", "11:32")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clock: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team: Option<TeamId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player: Option<PlayerId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// A final game with the given teams and score
+    pub(crate) fn game(id: &str, home: &str, away: &str, score: Option<(u32, u32)>) -> Game {
+        Game {
+            id: GameId::new(id),
+            league: LeagueId::new("nba"),
+            season: SeasonId::new("nba:2024-25"),
+            start: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            home: TeamId::new(home),
+            away: TeamId::new(away),
+            venue: None,
+            neutral: false,
+            status: if score.is_some() {
+                GameStatus::Final
+            } else {
+                GameStatus::Scheduled
+            },
+            home_score: score.map(|(home, _)| home),
+            away_score: score.map(|(_, away)| away),
+            extension: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod game_tests {
+    use super::{mock::game, *};
+
+    #[test]
+    fn test_outcome_for() {
+        let game = game("1", "lal", "bos", Some((110, 102)));
+
+        assert_eq!(game.outcome_for(&TeamId::new("lal")), Some(Outcome::Win));
+        assert_eq!(game.outcome_for(&TeamId::new("bos")), Some(Outcome::Loss));
+        assert_eq!(game.outcome_for(&TeamId::new("den")), None);
+        assert_eq!(game.score_for(&TeamId::new("bos")), Some((102, 110)));
+        assert_eq!(
+            game.opponent_of(&TeamId::new("bos")),
+            Some(&TeamId::new("lal"))
+        );
+    }
+
+    #[test]
+    fn test_outcome_for_unplayed() {
+        let game = game("1", "lal", "bos", None);
+
+        assert_eq!(game.outcome_for(&TeamId::new("lal")), None);
+    }
+
+    #[test]
+    fn test_game_extension_serde() {
+        let mut game = game("1", "ars", "che", Some((1, 1)));
+        game.extension = Some(GameExtension::Soccer {
+            half_time: Some((0, 1)),
+            extra_time: false,
+            penalties: None,
+        });
+        let json = serde_json::to_string(&game).unwrap();
+
+        assert!(json.contains(r#""extension":{"sport":"soccer","half_time":[0,1]"#));
+        assert_eq!(serde_json::from_str::<Game>(&json).unwrap(), game);
+    }
+}
@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use std::{convert::Infallible, fmt, str::FromStr};
+
+/// Lowercases a name and joins its alphanumeric words with `-` (e.g. "Man. United" -> "man-united")
+///
+/// ## Examples
+///
+/// ```
+/// # use statpack::model::id::slug;
+/// assert_eq!(slug("Luka Dončić"), "luka-dončić");
+/// ```
+pub fn slug(name: &str) -> String {
+    name.split(|char: char| !char.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Declares a stable string identifier for a model type.
+///
+/// IDs are plain strings in storage and JSON, but distinct types in Rust so a `TeamId`
+/// can never be passed where a `PlayerId` is expected.
+macro_rules! id_type {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+        impl $name {
+            pub fn new(id: impl Into<String>) -> Self {
+                Self(id.into())
+            }
+            /// Derives a stable ID from the natural key of a record, e.g.
+            /// `["nba", "Los Angeles Lakers"]` -> `nba:los-angeles-lakers`
+            pub fn derive(parts: &[&str]) -> Self {
+                Self(parts.iter().map(|part| slug(part)).collect::<Vec<_>>().join(":"))
+            }
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+        impl FromStr for $name {
+            type Err = Infallible;
+
+            fn from_str(id: &str) -> Result<Self, Self::Err> {
+                Ok(Self::new(id))
+            }
+        }
+        impl From<&str> for $name {
+            fn from(id: &str) -> Self {
+                Self::new(id)
+            }
+        }
+    };
+}
+
+id_type!(
+    /// Identifies a `League`
+    LeagueId
+);
+id_type!(
+    /// Identifies a `Season` of a league
+    SeasonId
+);
+id_type!(
+    /// Identifies a `Team`
+    TeamId
+);
+id_type!(
+    /// Identifies a `Player`
+    PlayerId
+);
+id_type!(
+    /// Identifies a `Venue`
+    VenueId
+);
+id_type!(
+    /// Identifies a `Game`
+    GameId
+);
+id_type!(
+    /// Identifies an `Event` within a game
+    EventId
+);
+
+#[cfg(test)]
+mod slug_tests {
+    use super::*;
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(slug("Manchester United"), "manchester-united");
+        assert_eq!(slug("  Man. Utd  "), "man-utd");
+        assert_eq!(slug("St. Louis Blues"), "st-louis-blues");
+        assert_eq!(slug(""), "");
+    }
+
+    #[test]
+    fn test_derive_id() {
+        let id = TeamId::derive(&["EPL", "Manchester United"]);
+
+        assert_eq!(id.as_str(), "epl:manchester-united");
+        assert_eq!(id, TeamId::derive(&["epl", "manchester  united"]));
+        assert_eq!(
+            serde_json::to_string(&id).unwrap(),
+            "\"epl:manchester-united\""
+        );
+    }
+}
@@ -0,0 +1,8 @@
+pub mod bet;
+pub mod favorite;
+pub mod game;
+pub mod id;
+pub mod odds;
+pub mod sport;
+pub mod stat;
+pub mod team;
@@ -0,0 +1,182 @@
+use super::id::{GameId, PlayerId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Represents the kind of bet a price is offered on
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Market {
+    Moneyline,
+    /// Handicap on the home team (point spread, puck line, run line)
+    Spread,
+    /// Combined score of both teams
+    Total,
+    /// Over/under on a player's stat, keyed by the names in `stat::keys`
+    Prop {
+        player: PlayerId,
+        stat: String,
+    },
+}
+
+impl Market {
+    /// Returns the kind of market, props of every player and stat sharing `prop`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Market::Moneyline => "moneyline",
+            Market::Spread => "spread",
+            Market::Total => "total",
+            Market::Prop { .. } => "prop",
+        }
+    }
+}
+
+impl fmt::Display for Market {
+    /// Formats the market as its name, props as `prop:<player>:<stat>`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Market::Moneyline => write!(f, "moneyline"),
+            Market::Spread => write!(f, "spread"),
+            Market::Total => write!(f, "total"),
+            Market::Prop { player, stat } => write!(f, "prop:{}:{}", player, stat),
+        }
+    }
+}
+
+impl FromStr for Market {
+    type Err = String;
+
+    /// Parses the names `Display` writes, player IDs of props may contain `:`
+    fn from_str(market: &str) -> Result<Self, Self::Err> {
+        match market {
+            "moneyline" => Ok(Market::Moneyline),
+            "spread" => Ok(Market::Spread),
+            "total" => Ok(Market::Total),
+            _ => market
+                .strip_prefix("prop:")
+                .and_then(|prop| prop.rsplit_once(':'))
+                .filter(|(player, stat)| !player.is_empty() && !stat.is_empty())
+                .map(|(player, stat)| Market::Prop {
+                    player: PlayerId::new(player),
+                    stat: stat.to_string(),
+                })
+                .ok_or_else(|| {
+                    format!(
+                        "unknown market '{}', expected moneyline, spread, total or \
+                         prop:<player>:<stat>",
+                        market
+                    )
+                }),
+        }
+    }
+}
+
+/// Represents the side of a market a price is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Selection {
+    Home,
+    Away,
+    Draw,
+    Over,
+    Under,
+}
+impl Selection {
+    pub const ALL: [Selection; 5] = [
+        Selection::Home,
+        Selection::Away,
+        Selection::Draw,
+        Selection::Over,
+        Selection::Under,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Selection::Home => "home",
+            Selection::Away => "away",
+            Selection::Draw => "draw",
+            Selection::Over => "over",
+            Selection::Under => "under",
+        }
+    }
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(selection: &str) -> Result<Self, Self::Err> {
+        Selection::ALL
+            .into_iter()
+            .find(|item| item.name() == selection)
+            .ok_or_else(|| format!("unknown selection '{}'", selection))
+    }
+}
+
+/// Represents the price a bookmaker offered on one selection at a point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OddsSnapshot {
+    pub game: GameId,
+    /// Bookmaker offering the price ("pinnacle", "draftkings")
+    pub book: String,
+    pub market: Market,
+    pub selection: Selection,
+    /// Spread or total the price applies to, from the point of view of the selection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<f64>,
+    /// Decimal odds, the payout per unit staked including the stake
+    pub price: f64,
+    pub taken_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod odds_snapshot_tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_odds_snapshot_serde() {
+        let snapshot = OddsSnapshot {
+            game: GameId::new("1"),
+            book: "pinnacle".to_string(),
+            market: Market::Prop {
+                player: PlayerId::new("nba:lebron-james"),
+                stat: "pts".to_string(),
+            },
+            selection: Selection::Over,
+            line: Some(25.5),
+            price: 1.91,
+            taken_at: Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap(),
+        };
+        let json = serde_json::to_string(&snapshot).unwrap();
+
+        assert!(json.contains(
+            r#""market":{"prop":{"player":"nba:lebron-james","stat":"pts"}},"selection":"over""#
+        ));
+        assert_eq!(
+            serde_json::from_str::<OddsSnapshot>(&json).unwrap(),
+            snapshot
+        );
+    }
+
+    #[test]
+    fn test_market_and_selection_from_str() {
+        for market in [
+            Market::Moneyline,
+            Market::Spread,
+            Market::Total,
+            Market::Prop {
+                player: PlayerId::new("nba:lebron-james"),
+                stat: "pts".to_string(),
+            },
+        ] {
+            assert_eq!(market.to_string().parse::<Market>(), Ok(market));
+        }
+        assert_eq!("prop:lebron:pts".parse::<Market>().unwrap().kind(), "prop");
+        assert!("prop:pts".parse::<Market>().is_err());
+        assert!("parlay".parse::<Market>().is_err());
+        for selection in Selection::ALL {
+            assert_eq!(selection.name().parse::<Selection>(), Ok(selection));
+        }
+        assert!("yes".parse::<Selection>().is_err());
+    }
+}
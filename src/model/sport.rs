@@ -0,0 +1,336 @@
+use super::id::{LeagueId, SeasonId};
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Represents the sports statpack tracks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Sport {
+    Soccer,
+    Cricket,
+    Hockey,
+    Tennis,
+    Volleyball,
+    TableTennis,
+    Basketball,
+    Rugby,
+    Baseball,
+    Golf,
+    Football,
+}
+impl Sport {
+    pub const ALL: [Sport; 11] = [
+        Sport::Soccer,
+        Sport::Cricket,
+        Sport::Hockey,
+        Sport::Tennis,
+        Sport::Volleyball,
+        Sport::TableTennis,
+        Sport::Basketball,
+        Sport::Rugby,
+        Sport::Baseball,
+        Sport::Golf,
+        Sport::Football,
+    ];
+
+    /// Returns the kebab-case name used on the command line and in storage
+    pub fn name(&self) -> &'static str {
+        match self {
+            Sport::Soccer => "soccer",
+            Sport::Cricket => "cricket",
+            Sport::Hockey => "hockey",
+            Sport::Tennis => "tennis",
+            Sport::Volleyball => "volleyball",
+            Sport::TableTennis => "table-tennis",
+            Sport::Basketball => "basketball",
+            Sport::Rugby => "rugby",
+            Sport::Baseball => "baseball",
+            Sport::Golf => "golf",
+            Sport::Football => "football",
+        }
+    }
+    /// Indicates if games of the sport can end in a draw
+    pub fn allows_draws(&self) -> bool {
+        matches!(
+            self,
+            Sport::Soccer | Sport::Cricket | Sport::Rugby | Sport::Football
+        )
+    }
+    /// Indicates if seasons run over the new year and are named after both years ("2024-25")
+    pub fn crosses_years(&self) -> bool {
+        matches!(
+            self,
+            Sport::Soccer | Sport::Hockey | Sport::Volleyball | Sport::Basketball | Sport::Rugby
+        )
+    }
+    /// Returns how long after its start a game of the sport is sure to be over, a generous
+    /// bound (overtimes, rain delays) for when the actual end is not known
+    pub fn max_duration(&self) -> Duration {
+        match self {
+            Sport::TableTennis => Duration::hours(2),
+            Sport::Soccer | Sport::Basketball | Sport::Rugby | Sport::Volleyball => {
+                Duration::hours(3)
+            }
+            Sport::Hockey | Sport::Football => Duration::hours(4),
+            Sport::Baseball => Duration::hours(5),
+            Sport::Tennis => Duration::hours(6),
+            // Tournaments and test matches
+            Sport::Golf => Duration::days(4),
+            Sport::Cricket => Duration::days(5),
+        }
+    }
+    /// Returns the month seasons are considered to start in when no season is given
+    pub fn season_start_month(&self) -> u32 {
+        match self {
+            _ if self.crosses_years() => 7,
+            // Playoffs run into February, the season is still named after its start year
+            Sport::Football => 3,
+            _ => 1,
+        }
+    }
+}
+impl fmt::Display for Sport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+impl FromStr for Sport {
+    type Err = String;
+
+    fn from_str(sport: &str) -> Result<Self, Self::Err> {
+        Sport::ALL
+            .into_iter()
+            .find(|item| item.name() == sport)
+            .ok_or_else(|| format!("unknown sport '{}'", sport))
+    }
+}
+
+/// Represents a competition teams play in (e.g. NBA, Premier League)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct League {
+    pub id: LeagueId,
+    pub sport: Sport,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// How the league table is ranked, `StandingRules::for_sport` when missing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<StandingRules>,
+}
+impl League {
+    /// Returns the rules the league table is ranked by
+    pub fn rules(&self) -> StandingRules {
+        self.rules
+            .clone()
+            .unwrap_or_else(|| StandingRules::for_sport(self.sport))
+    }
+}
+
+/// Represents a criterion teams level on the ones before it are ranked by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TieBreaker {
+    /// League points from `StandingRules`
+    Points,
+    /// Wins over games played, draws counting half
+    WinPercentage,
+    Wins,
+    /// Scored minus conceded
+    Differential,
+    /// Scored
+    ScoredFor,
+    /// Results of the games the level teams played against each other
+    HeadToHead,
+}
+impl TieBreaker {
+    pub const ALL: [TieBreaker; 6] = [
+        TieBreaker::Points,
+        TieBreaker::WinPercentage,
+        TieBreaker::Wins,
+        TieBreaker::Differential,
+        TieBreaker::ScoredFor,
+        TieBreaker::HeadToHead,
+    ];
+
+    /// Returns the kebab-case name used on the command line and in storage
+    pub fn name(&self) -> &'static str {
+        match self {
+            TieBreaker::Points => "points",
+            TieBreaker::WinPercentage => "win-percentage",
+            TieBreaker::Wins => "wins",
+            TieBreaker::Differential => "differential",
+            TieBreaker::ScoredFor => "scored-for",
+            TieBreaker::HeadToHead => "head-to-head",
+        }
+    }
+}
+impl FromStr for TieBreaker {
+    type Err = String;
+
+    fn from_str(tie_breaker: &str) -> Result<Self, Self::Err> {
+        TieBreaker::ALL
+            .into_iter()
+            .find(|item| item.name() == tie_breaker)
+            .ok_or_else(|| format!("unknown tie-breaker '{}'", tie_breaker))
+    }
+}
+
+/// Represents how a league awards points and ranks its table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StandingRules {
+    pub win_points: u32,
+    pub draw_points: u32,
+    pub loss_points: u32,
+    /// Applied in order until two teams are no longer level
+    pub tie_breakers: Vec<TieBreaker>,
+}
+impl StandingRules {
+    /// Returns the usual rules of the sport
+    pub fn for_sport(sport: Sport) -> StandingRules {
+        let (win_points, draw_points, tie_breakers) = match sport {
+            Sport::Soccer => (
+                3,
+                1,
+                vec![
+                    TieBreaker::Points,
+                    TieBreaker::Differential,
+                    TieBreaker::ScoredFor,
+                    TieBreaker::HeadToHead,
+                ],
+            ),
+            Sport::Hockey | Sport::Rugby | Sport::Cricket => (
+                2,
+                1,
+                vec![
+                    TieBreaker::Points,
+                    TieBreaker::Wins,
+                    TieBreaker::Differential,
+                    TieBreaker::HeadToHead,
+                ],
+            ),
+            _ => (
+                1,
+                0,
+                vec![
+                    TieBreaker::WinPercentage,
+                    TieBreaker::HeadToHead,
+                    TieBreaker::Differential,
+                ],
+            ),
+        };
+        StandingRules {
+            win_points,
+            draw_points,
+            loss_points: 0,
+            tie_breakers,
+        }
+    }
+}
+
+/// Represents one edition of a league (e.g. "2024-25")
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Season {
+    pub id: SeasonId,
+    pub league: LeagueId,
+    pub name: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+impl Season {
+    /// Builds the season of a league a date falls in, spanning a whole year from the
+    /// `season_start_month` of the sport
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use chrono::NaiveDate;
+    /// # use statpack::model::{id::LeagueId, sport::{Season, Sport}};
+    /// let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+    /// let season = Season::for_date(&LeagueId::new("epl"), Sport::Soccer, date);
+    /// assert_eq!(season.id.as_str(), "epl:2024-25");
+    /// ```
+    pub fn for_date(league: &LeagueId, sport: Sport, date: NaiveDate) -> Season {
+        let month = sport.season_start_month();
+        let year = if date.month() >= month {
+            date.year()
+        } else {
+            date.year() - 1
+        };
+        let name = if sport.crosses_years() {
+            format!("{}-{:02}", year, (year + 1) % 100)
+        } else {
+            year.to_string()
+        };
+
+        Season {
+            id: SeasonId::derive(&[league.as_str(), &name]),
+            league: league.clone(),
+            name,
+            start: NaiveDate::from_ymd_opt(year, month, 1).unwrap(),
+            end: NaiveDate::from_ymd_opt(year + 1, month, 1)
+                .and_then(|next| next.pred_opt())
+                .unwrap(),
+        }
+    }
+    /// Indicates if the date falls within the season
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+#[cfg(test)]
+mod sport_tests {
+    use super::*;
+
+    #[test]
+    fn test_sport_round_trip() {
+        for sport in Sport::ALL {
+            assert_eq!(sport.name().parse(), Ok(sport));
+            assert_eq!(
+                serde_json::to_string(&sport).unwrap(),
+                format!("\"{}\"", sport)
+            );
+        }
+        assert!("curling".parse::<Sport>().is_err());
+    }
+
+    #[test]
+    fn test_season_contains() {
+        let season = Season {
+            id: SeasonId::derive(&["nba", "2024-25"]),
+            league: LeagueId::new("nba"),
+            name: "2024-25".to_string(),
+            start: NaiveDate::from_ymd_opt(2024, 10, 22).unwrap(),
+            end: NaiveDate::from_ymd_opt(2025, 6, 22).unwrap(),
+        };
+
+        assert!(season.contains(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()));
+        assert!(!season.contains(NaiveDate::from_ymd_opt(2025, 7, 1).unwrap()));
+    }
+
+    #[test]
+    fn test_season_for_date() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let epl = LeagueId::new("epl");
+
+        let season = Season::for_date(&epl, Sport::Soccer, date(2025, 3, 1));
+        assert_eq!(season.id.as_str(), "epl:2024-25");
+        assert_eq!(
+            (season.start, season.end),
+            (date(2024, 7, 1), date(2025, 6, 30))
+        );
+        assert_eq!(
+            Season::for_date(&epl, Sport::Soccer, date(2025, 8, 16)).name,
+            "2025-26"
+        );
+        assert_eq!(
+            Season::for_date(&LeagueId::new("nfl"), Sport::Football, date(2025, 2, 9)).name,
+            "2024"
+        );
+        assert_eq!(
+            Season::for_date(&LeagueId::new("mlb"), Sport::Baseball, date(2025, 2, 9)).name,
+            "2025"
+        );
+    }
+}
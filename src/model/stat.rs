@@ -0,0 +1,110 @@
+use super::id::{GameId, PlayerId, TeamId};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Stat keys shared by every source, grouped per sport.
+///
+/// `StatLine` stores stats by key so new sports only need new keys, not new fields.
+pub mod keys {
+    pub mod basketball {
+        pub const POINTS: &str = "pts";
+        pub const REBOUNDS: &str = "reb";
+        pub const ASSISTS: &str = "ast";
+        pub const STEALS: &str = "stl";
+        pub const BLOCKS: &str = "blk";
+        pub const TURNOVERS: &str = "tov";
+        pub const THREES_MADE: &str = "fg3m";
+        /// Possessions the player was on the court for, used to normalize per-100 numbers
+        pub const POSSESSIONS: &str = "poss";
+    }
+    pub mod soccer {
+        pub const GOALS: &str = "goals";
+        pub const ASSISTS: &str = "assists";
+        pub const SHOTS: &str = "shots";
+        pub const SHOTS_ON_TARGET: &str = "shots_on_target";
+        pub const YELLOW_CARDS: &str = "yellow_cards";
+        pub const RED_CARDS: &str = "red_cards";
+    }
+    pub mod football {
+        pub const PASSING_YARDS: &str = "pass_yds";
+        pub const RUSHING_YARDS: &str = "rush_yds";
+        pub const RECEIVING_YARDS: &str = "rec_yds";
+        pub const TOUCHDOWNS: &str = "td";
+    }
+    pub mod hockey {
+        pub const GOALS: &str = "goals";
+        pub const ASSISTS: &str = "assists";
+        pub const SHOTS: &str = "shots";
+        pub const SAVES: &str = "saves";
+    }
+    pub mod baseball {
+        pub const HITS: &str = "h";
+        pub const HOME_RUNS: &str = "hr";
+        pub const RUNS_BATTED_IN: &str = "rbi";
+        pub const STRIKEOUTS: &str = "so";
+    }
+
+    /// Stats where the lower value is the better one
+    pub const LOWER_IS_BETTER: &[&str] = &[
+        basketball::TURNOVERS,
+        soccer::YELLOW_CARDS,
+        soccer::RED_CARDS,
+    ];
+}
+
+/// Represents a player's box score line for a single game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatLine {
+    pub game: GameId,
+    pub player: PlayerId,
+    pub team: TeamId,
+    /// Minutes played, used to normalize per-36/per-90 numbers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minutes: Option<f64>,
+    /// Stat values keyed by the names in `keys`
+    #[serde(default)]
+    pub stats: BTreeMap<String, f64>,
+}
+impl StatLine {
+    pub fn new(game: GameId, player: PlayerId, team: TeamId) -> Self {
+        Self {
+            game,
+            player,
+            team,
+            minutes: None,
+            stats: BTreeMap::new(),
+        }
+    }
+    /// Returns a stat of the line, missing stats count as `0`
+    pub fn get(&self, key: &str) -> f64 {
+        self.stats.get(key).copied().unwrap_or(0.0)
+    }
+    /// Sets a stat of the line
+    pub fn set(&mut self, key: &str, value: f64) -> &mut Self {
+        self.stats.insert(key.to_string(), value);
+        self
+    }
+}
+
+#[cfg(test)]
+mod stat_line_tests {
+    use super::{keys::basketball, *};
+
+    #[test]
+    fn test_stat_line() {
+        let mut line = StatLine::new(
+            GameId::new("1"),
+            PlayerId::new("nba:lebron-james"),
+            TeamId::new("nba:los-angeles-lakers"),
+        );
+        line.set(basketball::POINTS, 31.0)
+            .set(basketball::ASSISTS, 9.0);
+
+        assert_eq!(line.get(basketball::POINTS), 31.0);
+        assert_eq!(line.get(basketball::REBOUNDS), 0.0);
+        assert_eq!(
+            serde_json::to_string(&line).unwrap(),
+            r#"{"game":"1","player":"nba:lebron-james","team":"nba:los-angeles-lakers","stats":{"ast":9.0,"pts":31.0}}"#
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use super::id::{LeagueId, PlayerId, TeamId, VenueId};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Represents a stadium or arena games are played at
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Venue {
+    pub id: VenueId,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capacity: Option<u32>,
+}
+
+/// Represents a club, franchise or national side (or a lone competitor in individual sports)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Team {
+    pub id: TeamId,
+    pub league: LeagueId,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abbreviation: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub venue: Option<VenueId>,
+    /// Other names the team goes by ("Man Utd", "Lakers"), used for name lookups
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+}
+impl Team {
+    /// Returns the name and every alias of the team
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.name.as_str()).chain(self.aliases.iter().map(String::as_str))
+    }
+}
+
+/// Represents an athlete
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Player {
+    pub id: PlayerId,
+    pub name: String,
+    /// The team the player currently plays for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team: Option<TeamId>,
+    /// Sport specific position ("PG", "GK", "QB")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub birth_date: Option<NaiveDate>,
+}
+
+#[cfg(test)]
+mod team_tests {
+    use super::*;
+
+    #[test]
+    fn test_team_names() {
+        let team = Team {
+            id: TeamId::derive(&["epl", "Manchester United"]),
+            league: LeagueId::new("epl"),
+            name: "Manchester United".to_string(),
+            abbreviation: Some("MUN".to_string()),
+            venue: None,
+            aliases: vec!["Man Utd".to_string()],
+        };
+
+        assert_eq!(
+            team.names().collect::<Vec<_>>(),
+            ["Manchester United", "Man Utd"]
+        );
+    }
+
+    #[test]
+    fn test_player_optional_fields() {
+        let player: Player =
+            serde_json::from_str(r#"{"id":"nba:lebron-james","name":"LeBron James"}"#).unwrap();
+
+        assert_eq!(player.team, None);
+        assert_eq!(
+            serde_json::to_string(&player).unwrap(),
+            r#"{"id":"nba:lebron-james","name":"LeBron James"}"#
+        );
+    }
+}
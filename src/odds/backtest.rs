@@ -0,0 +1,669 @@
+use super::{
+    error::OddsError,
+    ledger::{closing_price, grade, LedgerSummary},
+    market::{compare_books, BestPrice, MarketComparison},
+    value::ValueBet,
+};
+use crate::{
+    model::{
+        bet::{Bet, BetResult},
+        game::{Game, GameStatus},
+        id::{GameId, LeagueId, TeamId},
+        odds::{Market, OddsSnapshot, Selection},
+        sport::{League, Sport},
+    },
+    stats::{
+        is_season,
+        trends::{contexts, Situation},
+    },
+};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{de, Deserialize, Deserializer};
+use std::collections::{HashMap, HashSet};
+
+/// Represents a betting strategy: which games to bet on, what to pick and how much to
+/// stake, read from TOML
+///
+/// ## Examples
+///
+/// ```toml
+/// name = "Home favorites after a loss"
+/// league = "nba"
+/// season = "2024-25"
+/// situation = "home & fav & after-loss"
+/// hours_before = 1
+///
+/// [pick]
+/// market = "spread"
+/// side = "favorite"
+/// min_edge = 0.01
+///
+/// [stake]
+/// rule = "kelly"
+/// fraction = 0.25
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Strategy {
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Only games of the league, by ID
+    #[serde(default)]
+    pub league: Option<String>,
+    /// Only games of the season, by name (2024-25) or ID
+    #[serde(default)]
+    pub season: Option<String>,
+    /// First day of the games bet on
+    #[serde(default, deserialize_with = "date")]
+    pub from: Option<NaiveDate>,
+    /// Last day of the games bet on
+    #[serde(default, deserialize_with = "date")]
+    pub to: Option<NaiveDate>,
+    /// Only games in the trend situation, judged from the team picked (the home team on
+    /// totals)
+    #[serde(default, deserialize_with = "situation")]
+    pub situation: Option<Situation>,
+    /// Hours before the start bets are placed, only the prices known by then are used
+    #[serde(default)]
+    pub hours_before: i64,
+    #[serde(default = "bankroll")]
+    pub bankroll: f64,
+    pub pick: Pick,
+    pub stake: Staking,
+}
+impl Strategy {
+    /// Reads a strategy from TOML and checks it describes bets that can be placed
+    pub fn from_toml(text: &str) -> Result<Self, OddsError> {
+        let strategy: Strategy =
+            toml::from_str(text).map_err(|error| invalid(error.to_string().trim_end()))?;
+        let pick = &strategy.pick;
+        match (&pick.market, pick.side) {
+            (Market::Prop { .. }, _) => {
+                return Err(invalid(
+                    "only moneyline, spread and total markets can be bet",
+                ))
+            }
+            (Market::Total, Side::Over | Side::Under)
+            | (Market::Moneyline, Side::Draw)
+            | (
+                Market::Moneyline | Market::Spread,
+                Side::Home | Side::Away | Side::Favorite | Side::Underdog,
+            ) => (),
+            (market, side) => {
+                return Err(invalid(&format!(
+                    "cannot pick the {} side of the {} market",
+                    side.name(),
+                    market
+                )))
+            }
+        }
+        let positive = match strategy.stake {
+            Staking::Flat { amount } => amount > 0.0,
+            Staking::Percent { share } => share > 0.0 && share <= 1.0,
+            Staking::Kelly { fraction } => fraction > 0.0,
+        };
+        if !positive || strategy.bankroll <= 0.0 || strategy.hours_before < 0 {
+            return Err(invalid(
+                "bankroll, stakes and hours_before must be positive, and shares at most 1",
+            ));
+        }
+        Ok(strategy)
+    }
+}
+
+fn invalid(message: &str) -> OddsError {
+    OddsError::InvalidStrategy {
+        message: message.to_string(),
+    }
+}
+
+fn bankroll() -> f64 {
+    100.0
+}
+
+/// Reads a date written either as a TOML date or as a `YYYY-MM-DD` string
+fn date<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<NaiveDate>, D::Error> {
+    let date = match Option::<toml::Value>::deserialize(deserializer)? {
+        Some(toml::Value::Datetime(date)) => date.to_string(),
+        Some(toml::Value::String(date)) => date,
+        Some(_) => return Err(de::Error::custom("expected a date (YYYY-MM-DD)")),
+        None => return Ok(None),
+    };
+    date.parse().map(Some).map_err(de::Error::custom)
+}
+
+fn situation<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Situation>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map(|query| query.parse().map_err(de::Error::custom))
+        .transpose()
+}
+
+/// Represents what a strategy bets on in the games it picks
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Pick {
+    pub market: Market,
+    pub side: Side,
+    /// Only prices of the book, the best price across books otherwise
+    #[serde(default)]
+    pub book: Option<String>,
+    /// Lowest decimal odds taken
+    #[serde(default)]
+    pub min_odds: Option<f64>,
+    /// Highest decimal odds taken
+    #[serde(default)]
+    pub max_odds: Option<f64>,
+    /// Lowest edge over the no-vig consensus probability taken
+    #[serde(default)]
+    pub min_edge: Option<f64>,
+}
+
+/// Represents the side of a market a strategy picks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Side {
+    Home,
+    Away,
+    Draw,
+    Over,
+    Under,
+    /// The team giving points on the spread, or with the shorter moneyline price
+    Favorite,
+    Underdog,
+}
+impl Side {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Side::Home => "home",
+            Side::Away => "away",
+            Side::Draw => "draw",
+            Side::Over => "over",
+            Side::Under => "under",
+            Side::Favorite => "favorite",
+            Side::Underdog => "underdog",
+        }
+    }
+    /// Returns the best price on the side in a market, `None` when it is not quoted or
+    /// neither team is favored
+    fn pick<'c>(&self, comparison: &'c MarketComparison) -> Option<&'c BestPrice> {
+        let best = |selection| {
+            comparison
+                .best
+                .iter()
+                .find(|best| best.selection == selection)
+        };
+        let selection = match self {
+            Side::Home => Selection::Home,
+            Side::Away => Selection::Away,
+            Side::Draw => Selection::Draw,
+            Side::Over => Selection::Over,
+            Side::Under => Selection::Under,
+            Side::Favorite | Side::Underdog => {
+                // Lines are from the home point of view, negative when home gives points
+                let home_favored = match comparison.line {
+                    Some(line) if line != 0.0 => line < 0.0,
+                    _ => {
+                        let (home, away) = (best(Selection::Home)?, best(Selection::Away)?);
+                        if home.price == away.price {
+                            return None;
+                        }
+                        home.price < away.price
+                    }
+                };
+                if home_favored == (*self == Side::Favorite) {
+                    Selection::Home
+                } else {
+                    Selection::Away
+                }
+            }
+        };
+        best(selection)
+    }
+}
+
+/// Represents how much a strategy stakes on each bet
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(tag = "rule", rename_all = "kebab-case")]
+pub enum Staking {
+    /// The same amount on every bet
+    Flat { amount: f64 },
+    /// A share of the current bankroll
+    Percent { share: f64 },
+    /// A fraction of the Kelly stake, given the no-vig consensus probability
+    Kelly {
+        #[serde(default = "full_kelly")]
+        fraction: f64,
+    },
+}
+
+fn full_kelly() -> f64 {
+    1.0
+}
+
+/// Represents a bet placed while replaying a strategy
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestBet {
+    /// The bet, settled from the result of its game
+    pub bet: Bet,
+    /// No-vig consensus probability of the selection when the bet was placed
+    pub probability: Option<f64>,
+    pub closing: Option<f64>,
+    /// Bankroll once the bet is settled
+    pub bankroll: f64,
+}
+
+/// Represents the replay of a strategy over past games
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backtest {
+    /// Bankroll the replay started with
+    pub bankroll: f64,
+    pub bets: Vec<BacktestBet>,
+    pub summary: LedgerSummary,
+}
+impl Backtest {
+    /// Returns the bankroll after the last bet
+    pub fn final_bankroll(&self) -> f64 {
+        self.bets.last().map_or(self.bankroll, |bet| bet.bankroll)
+    }
+    /// Returns the largest fall of the bankroll from a previous high, as an amount and as
+    /// a share of that high
+    pub fn max_drawdown(&self) -> (f64, f64) {
+        let mut peak = self.bankroll;
+        let mut drawdown: (f64, f64) = (0.0, 0.0);
+        for bet in &self.bets {
+            peak = peak.max(bet.bankroll);
+            if peak - bet.bankroll > drawdown.0 {
+                drawdown = (peak - bet.bankroll, (peak - bet.bankroll) / peak);
+            }
+        }
+        drawdown
+    }
+    /// Returns the average return per unit staked over its standard deviation, a Sharpe
+    /// ratio per bet without a risk-free rate
+    ///
+    /// ## Returns
+    ///
+    /// `None` with fewer than two graded bets or when every bet returned the same.
+    pub fn sharpe(&self) -> Option<f64> {
+        let returns: Vec<f64> = self
+            .bets
+            .iter()
+            .filter(|bet| bet.bet.result != Some(BetResult::Void))
+            .filter_map(|bet| Some(bet.bet.profit()? / bet.bet.stake))
+            .collect();
+        if returns.len() < 2 {
+            return None;
+        }
+        let count = returns.len() as f64;
+        let mean = returns.iter().sum::<f64>() / count;
+        let variance = returns
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>()
+            / (count - 1.0);
+        (variance > 0.0).then(|| mean / variance.sqrt())
+    }
+}
+
+/// Represents a bet placed on a game that is not over yet while replaying a strategy
+struct OpenBet {
+    bet: Bet,
+    /// Result the bet is settled with once its game is over
+    result: BetResult,
+    over_by: DateTime<Utc>,
+    probability: Option<f64>,
+    closing: Option<f64>,
+}
+
+/// Replays a strategy over past games, betting on each as it would have then
+///
+/// Each game is bet on `hours_before` its start, from the prices taken by then and with
+/// trend situations read from the lines known by then, so no later information leaks
+/// in. Bets are settled once their game is over (see `Game::over_by`, with the sport of
+/// the league), so stakes are sized from the bankroll left after the bets still open.
+/// Closing prices only serve to measure the closing line value of the bets.
+///
+/// `games` must be ordered oldest first.
+pub fn backtest(
+    strategy: &Strategy,
+    games: &[Game],
+    leagues: &[League],
+    odds: &[OddsSnapshot],
+) -> Backtest {
+    let bet_time = |game: &Game| game.start - Duration::hours(strategy.hours_before);
+    let sports: HashMap<&LeagueId, Sport> = leagues
+        .iter()
+        .map(|league| (&league.id, league.sport))
+        .collect();
+    let over_by = |game: &Game| game.over_by(sports.get(&game.league).copied());
+    let in_league = |game: &Game| {
+        strategy
+            .league
+            .as_ref()
+            .is_none_or(|league| game.league.as_str() == league)
+    };
+    let starts: HashMap<&GameId, &Game> = games.iter().map(|game| (&game.id, game)).collect();
+    let known: Vec<OddsSnapshot> = odds
+        .iter()
+        .filter(|snapshot| {
+            starts
+                .get(&snapshot.game)
+                .is_some_and(|game| snapshot.taken_at <= bet_time(game))
+        })
+        .cloned()
+        .collect();
+
+    // Games each team played in the situation
+    let mut in_situation: HashSet<(&GameId, &TeamId)> = HashSet::new();
+    if let Some(situation) = &strategy.situation {
+        let mut teams: Vec<&TeamId> = games
+            .iter()
+            .filter(|game| in_league(game))
+            .flat_map(|game| [&game.home, &game.away])
+            .collect();
+        teams.sort();
+        teams.dedup();
+        for team in teams {
+            let schedule: Vec<Game> = games
+                .iter()
+                .filter(|game| in_league(game) && (game.home == *team || game.away == *team))
+                .cloned()
+                .collect();
+            for context in contexts(team, &schedule, &known) {
+                if situation.matches(&context) {
+                    in_situation.insert((&starts[&context.game.id].id, team));
+                }
+            }
+        }
+    }
+
+    let pick = &strategy.pick;
+    let mut bankroll = strategy.bankroll;
+    let mut open: Vec<OpenBet> = vec![];
+    let mut placed = 0;
+    let mut bets = vec![];
+    let mut summary = LedgerSummary::default();
+    // Settles the open bets whose game is over by `until`, every one without it
+    let mut settle = |open: &mut Vec<OpenBet>, bankroll: &mut f64, until: Option<DateTime<Utc>>| {
+        open.sort_by_key(|open| (open.over_by, open.bet.id));
+        let count = open
+            .iter()
+            .take_while(|open| until.is_none_or(|until| open.over_by <= until))
+            .count();
+        for mut open in open.drain(..count) {
+            open.bet.settle(open.result, open.over_by);
+            *bankroll += open.bet.profit().unwrap_or_default();
+            summary.add(&open.bet, open.closing);
+            bets.push(BacktestBet {
+                bet: open.bet,
+                probability: open.probability,
+                closing: open.closing,
+                bankroll: *bankroll,
+            });
+        }
+    };
+    for game in games.iter().filter(|game| {
+        game.status == GameStatus::Final
+            && in_league(game)
+            && strategy
+                .season
+                .as_ref()
+                .is_none_or(|season| is_season(&game.season, season))
+            && strategy
+                .from
+                .is_none_or(|from| game.start.date_naive() >= from)
+            && strategy.to.is_none_or(|to| game.start.date_naive() <= to)
+    }) {
+        settle(&mut open, &mut bankroll, Some(bet_time(game)));
+        let snapshots: Vec<OddsSnapshot> = known
+            .iter()
+            .filter(|snapshot| {
+                snapshot.game == game.id
+                    && snapshot.market == pick.market
+                    && pick.book.as_ref().is_none_or(|book| snapshot.book == *book)
+            })
+            .cloned()
+            .collect();
+        // The main line is the one most books quote, the first of them on a tie
+        let Some(best) = compare_books(&snapshots)
+            .into_iter()
+            .rev()
+            .max_by_key(|comparison| comparison.books)
+            .and_then(|comparison| pick.side.pick(&comparison).cloned())
+        else {
+            continue;
+        };
+        let team = match best.selection {
+            Selection::Away => &game.away,
+            _ => &game.home,
+        };
+        if strategy.situation.is_some() && !in_situation.contains(&(&game.id, team)) {
+            continue;
+        }
+        if pick.min_odds.is_some_and(|min| best.price < min)
+            || pick.max_odds.is_some_and(|max| best.price > max)
+        {
+            continue;
+        }
+        let value = best
+            .consensus
+            .map(|probability| ValueBet::new(probability, best.price));
+        if pick
+            .min_edge
+            .is_some_and(|min| !value.is_some_and(|value| value.edge() >= min))
+        {
+            continue;
+        }
+        // Money staked on open bets is not available until they are settled
+        let available = bankroll - open.iter().map(|open| open.bet.stake).sum::<f64>();
+        let stake = match strategy.stake {
+            Staking::Flat { amount } => amount,
+            Staking::Percent { share } => available * share,
+            Staking::Kelly { fraction } => match value {
+                Some(value) => value.stake(available, fraction),
+                None => continue,
+            },
+        }
+        .min(available);
+        if stake <= 0.0 {
+            continue;
+        }
+
+        let bet = Bet {
+            id: placed + 1,
+            placed_at: bet_time(game),
+            game: game.id.clone(),
+            book: best.book,
+            market: pick.market.clone(),
+            selection: best.selection,
+            line: best.line,
+            odds: best.price,
+            stake,
+            result: None,
+            settled_at: None,
+        };
+        let Some(result) = grade(&bet, game, None) else {
+            continue;
+        };
+        placed += 1;
+        open.push(OpenBet {
+            closing: closing_price(&bet, game, odds),
+            bet,
+            result,
+            over_by: over_by(game),
+            probability: best.consensus,
+        });
+    }
+    settle(&mut open, &mut bankroll, None);
+
+    Backtest {
+        bankroll: strategy.bankroll,
+        bets,
+        summary,
+    }
+}
+
+#[cfg(test)]
+mod backtest_tests {
+    use super::*;
+    use crate::model::{game::mock::game, id::LeagueId};
+
+    const STRATEGY: &str = r#"
+        name = "Home favorites"
+        from = 2025-01-01
+        to = "2025-01-31"
+        hours_before = 2
+
+        [pick]
+        market = "moneyline"
+        side = "favorite"
+
+        [stake]
+        rule = "flat"
+        amount = 10
+    "#;
+
+    fn snapshot(
+        game: &Game,
+        book: &str,
+        selection: Selection,
+        price: f64,
+        hours: i64,
+    ) -> OddsSnapshot {
+        OddsSnapshot {
+            game: game.id.clone(),
+            book: book.to_string(),
+            market: Market::Moneyline,
+            selection,
+            line: None,
+            price,
+            taken_at: game.start - Duration::hours(hours),
+        }
+    }
+
+    #[test]
+    fn test_strategy_from_toml() {
+        let strategy = Strategy::from_toml(STRATEGY).unwrap();
+
+        assert_eq!(strategy.name.as_deref(), Some("Home favorites"));
+        assert_eq!(strategy.from, NaiveDate::from_ymd_opt(2025, 1, 1));
+        assert_eq!(strategy.to, NaiveDate::from_ymd_opt(2025, 1, 31));
+        assert_eq!(strategy.bankroll, 100.0);
+        assert_eq!(strategy.pick.side, Side::Favorite);
+        assert_eq!(strategy.stake, Staking::Flat { amount: 10.0 });
+
+        assert_eq!(
+            Strategy::from_toml(&STRATEGY.replace("\"favorite\"", "\"over\""))
+                .unwrap_err()
+                .to_string(),
+            "invalid strategy: cannot pick the over side of the moneyline market"
+        );
+        assert!(Strategy::from_toml(&STRATEGY.replace("amount = 10", "amount = 0")).is_err());
+        assert!(Strategy::from_toml(&STRATEGY.replace("hours_before", "hours")).is_err());
+        assert!(Strategy::from_toml(&format!("situation = \"road\"\n{}", STRATEGY)).is_err());
+    }
+
+    #[test]
+    fn test_backtest() {
+        let mut strategy = Strategy::from_toml(STRATEGY).unwrap();
+        // lal won as the favorite, then lost as the favorite at bos
+        let mut games = vec![
+            game("1", "lal", "bos", Some((100, 90))),
+            game("2", "bos", "lal", Some((80, 100))),
+            game("3", "lal", "bos", Some((90, 100))),
+        ];
+        for (day, game) in games.iter_mut().enumerate() {
+            game.start += Duration::days(day as i64);
+        }
+        let odds = [
+            snapshot(&games[0], "pinnacle", Selection::Home, 1.5, 3),
+            snapshot(&games[0], "pinnacle", Selection::Away, 2.8, 3),
+            // Too late to bet on, but it is the closing price
+            snapshot(&games[0], "pinnacle", Selection::Home, 1.4, 1),
+            snapshot(&games[0], "pinnacle", Selection::Away, 3.0, 1),
+            // The home team is favored and loses
+            snapshot(&games[2], "pinnacle", Selection::Home, 1.8, 5),
+            snapshot(&games[2], "pinnacle", Selection::Away, 2.0, 5),
+        ];
+        let backtest = backtest(&strategy, &games, &[], &odds);
+
+        assert_eq!(
+            backtest
+                .bets
+                .iter()
+                .map(|bet| (bet.bet.game.as_str(), bet.bet.odds, bet.bet.result))
+                .collect::<Vec<_>>(),
+            [
+                ("1", 1.5, Some(BetResult::Won)),
+                ("3", 1.8, Some(BetResult::Lost))
+            ]
+        );
+        assert_eq!(backtest.bets[0].closing, Some(1.4));
+        assert_eq!(backtest.final_bankroll(), 95.0);
+        assert_eq!(backtest.max_drawdown(), (10.0, 10.0 / 105.0));
+        assert!((backtest.sharpe().unwrap() - -0.25 / 1.125f64.sqrt()).abs() < 1e-9);
+        assert_eq!(backtest.summary.record.to_string(), "1-1-0");
+
+        strategy.pick.side = Side::Underdog;
+        strategy.stake = Staking::Percent { share: 0.5 };
+        let backtest = super::backtest(&strategy, &games, &[], &odds);
+        assert_eq!(backtest.final_bankroll(), 75.0);
+        assert_eq!(backtest.max_drawdown(), (50.0, 0.5));
+        assert_eq!(backtest.sharpe(), Some(0.0));
+        assert_eq!(
+            Backtest {
+                bets: vec![],
+                ..backtest
+            }
+            .sharpe(),
+            None
+        );
+    }
+    #[test]
+    fn test_backtest_overlapping() {
+        let mut strategy = Strategy::from_toml(STRATEGY).unwrap();
+        strategy.stake = Staking::Percent { share: 0.1 };
+        // Both home favorites win, the second game starts before the first one is over
+        let mut games = vec![
+            game("1", "lal", "bos", Some((100, 90))),
+            game("2", "den", "mia", Some((100, 90))),
+        ];
+        games[1].start += Duration::hours(1);
+        let odds: Vec<OddsSnapshot> = games
+            .iter()
+            .flat_map(|game| {
+                [
+                    snapshot(game, "pinnacle", Selection::Home, 1.5, 3),
+                    snapshot(game, "pinnacle", Selection::Away, 2.8, 3),
+                ]
+            })
+            .collect();
+        let leagues = [League {
+            id: LeagueId::new("nba"),
+            sport: Sport::Basketball,
+            name: "NBA".to_string(),
+            country: None,
+            rules: None,
+        }];
+        let backtest = backtest(&strategy, &games, &leagues, &odds);
+
+        // The first stake is still open when the second bet is placed
+        assert_eq!(
+            backtest
+                .bets
+                .iter()
+                .map(|bet| (bet.bet.stake, bet.bankroll))
+                .collect::<Vec<_>>(),
+            [(10.0, 105.0), (9.0, 109.5)]
+        );
+        assert_eq!(
+            backtest.bets[0].bet.settled_at,
+            Some(games[0].start + Duration::hours(3))
+        );
+
+        // Once the first game is over, its winnings can be staked again
+        games[1].start += Duration::hours(4);
+        let backtest = super::backtest(&strategy, &games, &leagues, &odds);
+        assert_eq!(backtest.bets[1].bet.stake, 10.5);
+    }
+}
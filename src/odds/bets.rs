@@ -0,0 +1,295 @@
+use std::{fmt, str::FromStr};
+
+/// Represents what a bet pays when it wins
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Payout {
+    pub stake: f64,
+    /// Decimal odds of the whole bet
+    pub odds: f64,
+}
+impl Payout {
+    /// Returns the amount paid back, stake included
+    pub fn payout(&self) -> f64 {
+        self.stake * self.odds
+    }
+    pub fn profit(&self) -> f64 {
+        self.payout() - self.stake
+    }
+    /// Returns the chance of winning the odds imply, which is also the win rate needed to
+    /// break even
+    pub fn implied_probability(&self) -> f64 {
+        1.0 / self.odds
+    }
+    /// Returns the stake to put on the opposite side at `odds` so the bet returns the same
+    /// whichever side wins, along with that guaranteed profit
+    pub fn hedge(&self, odds: f64) -> (f64, f64) {
+        let hedge = self.payout() / odds;
+        (hedge, self.payout() - self.stake - hedge)
+    }
+}
+
+/// Returns a straight bet on one selection
+pub fn single(stake: f64, odds: f64) -> Payout {
+    Payout { stake, odds }
+}
+
+/// Returns a bet on several selections that wins only if all of them do
+pub fn parlay(stake: f64, legs: &[f64]) -> Payout {
+    Payout {
+        stake,
+        odds: legs.iter().product(),
+    }
+}
+
+/// Returns the rate each of `legs` independent selections has to win at for a bet paying
+/// `odds` to break even
+pub fn break_even_per_leg(odds: f64, legs: usize) -> f64 {
+    (1.0 / odds).powf(1.0 / legs as f64)
+}
+
+/// Represents several bets placed together, like a round robin or a system bet
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultipleBet {
+    /// Selections of every bet, as positions in the legs
+    pub bets: Vec<Vec<usize>>,
+    /// Stake of each bet
+    pub stake: f64,
+}
+impl MultipleBet {
+    pub fn total_stake(&self) -> f64 {
+        self.bets.len() as f64 * self.stake
+    }
+    /// Returns the amount paid back when the legs at the given positions win
+    pub fn payout(&self, legs: &[f64], winners: &[usize]) -> f64 {
+        self.bets
+            .iter()
+            .filter(|bet| bet.iter().all(|leg| winners.contains(leg)))
+            .map(|bet| self.stake * bet.iter().map(|leg| legs[*leg]).product::<f64>())
+            .sum()
+    }
+    /// Returns the amount paid back when every leg wins
+    pub fn max_payout(&self, legs: &[f64]) -> f64 {
+        self.payout(legs, &(0..legs.len()).collect::<Vec<_>>())
+    }
+}
+
+/// Returns every parlay of `size` legs out of `legs` selections
+pub fn round_robin(stake: f64, legs: usize, size: usize) -> MultipleBet {
+    MultipleBet {
+        bets: combinations(legs, size),
+        stake,
+    }
+}
+
+/// Returns every way of picking `size` positions out of `0..count`, in lexicographic order
+pub fn combinations(count: usize, size: usize) -> Vec<Vec<usize>> {
+    if size > count {
+        return vec![];
+    }
+    let mut combinations = vec![];
+    let mut current: Vec<usize> = (0..size).collect();
+    loop {
+        combinations.push(current.clone());
+        // Moves the rightmost position that can still move, resetting those after it
+        let Some(position) = (0..size).rev().find(|&i| current[i] < count - size + i) else {
+            return combinations;
+        };
+        current[position] += 1;
+        for i in position + 1..size {
+            current[i] = current[i - 1] + 1;
+        }
+    }
+}
+
+/// Represents the full-cover system bets, every combination of their selections
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum System {
+    /// 3 doubles and a treble
+    Trixie,
+    /// A trixie plus 3 singles
+    Patent,
+    /// 6 doubles, 4 trebles and a four-fold
+    Yankee,
+    /// A yankee plus 4 singles
+    Lucky15,
+    /// Every double and more of 5 selections, 26 bets
+    Canadian,
+    /// A canadian plus 5 singles
+    Lucky31,
+    /// Every double and more of 6 selections, 57 bets
+    Heinz,
+    /// A heinz plus 6 singles
+    Lucky63,
+    /// Every double and more of 7 selections, 120 bets
+    SuperHeinz,
+    /// Every double and more of 8 selections, 247 bets
+    Goliath,
+}
+impl System {
+    pub const ALL: [System; 10] = [
+        System::Trixie,
+        System::Patent,
+        System::Yankee,
+        System::Lucky15,
+        System::Canadian,
+        System::Lucky31,
+        System::Heinz,
+        System::Lucky63,
+        System::SuperHeinz,
+        System::Goliath,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            System::Trixie => "trixie",
+            System::Patent => "patent",
+            System::Yankee => "yankee",
+            System::Lucky15 => "lucky-15",
+            System::Canadian => "canadian",
+            System::Lucky31 => "lucky-31",
+            System::Heinz => "heinz",
+            System::Lucky63 => "lucky-63",
+            System::SuperHeinz => "super-heinz",
+            System::Goliath => "goliath",
+        }
+    }
+    /// Returns the number of selections the system is made of
+    pub fn selections(&self) -> usize {
+        match self {
+            System::Trixie | System::Patent => 3,
+            System::Yankee | System::Lucky15 => 4,
+            System::Canadian | System::Lucky31 => 5,
+            System::Heinz | System::Lucky63 => 6,
+            System::SuperHeinz => 7,
+            System::Goliath => 8,
+        }
+    }
+    /// Returns the bets of the system, each staked `stake`
+    pub fn bets(&self, stake: f64) -> MultipleBet {
+        let smallest = match self {
+            System::Patent | System::Lucky15 | System::Lucky31 | System::Lucky63 => 1,
+            _ => 2,
+        };
+        MultipleBet {
+            bets: (smallest..=self.selections())
+                .flat_map(|size| combinations(self.selections(), size))
+                .collect(),
+            stake,
+        }
+    }
+}
+impl fmt::Display for System {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+impl FromStr for System {
+    type Err = String;
+
+    fn from_str(system: &str) -> Result<Self, Self::Err> {
+        System::ALL
+            .into_iter()
+            .find(|item| item.name() == system)
+            .ok_or_else(|| format!("unknown system bet '{}'", system))
+    }
+}
+
+/// Represents an each-way bet, a win bet and a place bet of the same stake
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EachWay {
+    /// Stake of each part, the bet costs twice as much
+    pub stake: f64,
+    /// Decimal odds of the win part
+    pub odds: f64,
+    /// Share of the win odds the place part is paid at (`0.25` for 1/4 odds)
+    pub place_terms: f64,
+}
+impl EachWay {
+    /// Returns the decimal odds of the place part
+    pub fn place_odds(&self) -> f64 {
+        1.0 + (self.odds - 1.0) * self.place_terms
+    }
+    /// Returns the amount paid back when the selection wins, both parts winning
+    pub fn win_payout(&self) -> f64 {
+        self.stake * self.odds + self.place_payout()
+    }
+    /// Returns the amount paid back when the selection only places
+    pub fn place_payout(&self) -> f64 {
+        self.stake * self.place_odds()
+    }
+}
+
+#[cfg(test)]
+mod bets_tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_single_and_parlay() {
+        let bet = single(100.0, 1.91);
+        assert!(close(bet.payout(), 191.0));
+        assert!(close(bet.profit(), 91.0));
+        let (hedge, locked) = bet.hedge(2.5);
+        assert!(close(hedge, 76.4));
+        assert!(close(locked, 14.6));
+
+        let parlay = parlay(10.0, &[2.0, 1.5, 3.0]);
+        assert!(close(parlay.payout(), 90.0));
+        assert!(close(parlay.implied_probability(), 1.0 / 9.0));
+        assert!(close(break_even_per_leg(4.0, 2), 0.5));
+    }
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(
+            combinations(4, 2),
+            [[0, 1], [0, 2], [0, 3], [1, 2], [1, 3], [2, 3]]
+        );
+        assert_eq!(combinations(3, 3), [[0, 1, 2]]);
+        assert_eq!(combinations(2, 0), [Vec::<usize>::new()]);
+        assert!(combinations(2, 3).is_empty());
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let legs = [2.0, 2.0, 2.0];
+        let robin = round_robin(10.0, legs.len(), 2);
+
+        assert_eq!(robin.bets.len(), 3);
+        assert!(close(robin.total_stake(), 30.0));
+        assert!(close(robin.max_payout(&legs), 120.0));
+        assert!(close(robin.payout(&legs, &[0, 2]), 40.0));
+        assert!(close(robin.payout(&legs, &[1]), 0.0));
+    }
+
+    #[test]
+    fn test_systems() {
+        let counts: Vec<usize> = System::ALL
+            .iter()
+            .map(|system| system.bets(1.0).bets.len())
+            .collect();
+
+        assert_eq!(counts, [4, 7, 11, 15, 26, 31, 57, 63, 120, 247]);
+        assert_eq!("lucky-15".parse(), Ok(System::Lucky15));
+        assert!(close(
+            System::Trixie.bets(1.0).max_payout(&[2.0, 2.0, 2.0]),
+            3.0 * 4.0 + 8.0
+        ));
+    }
+
+    #[test]
+    fn test_each_way() {
+        let bet = EachWay {
+            stake: 10.0,
+            odds: 11.0,
+            place_terms: 0.25,
+        };
+
+        assert!(close(bet.place_odds(), 3.5));
+        assert!(close(bet.place_payout(), 35.0));
+        assert!(close(bet.win_payout(), 145.0));
+    }
+}
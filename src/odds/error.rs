@@ -0,0 +1,22 @@
+use super::OddsFormat;
+use std::{error::Error, fmt};
+
+/// Represents the errors raised while reading or converting odds
+#[derive(Debug, Clone, PartialEq)]
+pub enum OddsError {
+    /// The value is not a price in the format, or is out of its range
+    Invalid { format: OddsFormat, value: String },
+    /// A betting strategy file does not parse or describes bets that cannot be placed
+    InvalidStrategy { message: String },
+}
+impl fmt::Display for OddsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OddsError::Invalid { format, value } => {
+                write!(f, "'{}' is not valid {} odds", value, format)
+            }
+            OddsError::InvalidStrategy { message } => write!(f, "invalid strategy: {}", message),
+        }
+    }
+}
+impl Error for OddsError {}
@@ -0,0 +1,332 @@
+use crate::{
+    model::{
+        bet::{Bet, BetResult},
+        game::{Game, GameStatus, Outcome},
+        odds::{Market, OddsSnapshot, Selection},
+        stat::StatLine,
+    },
+    stats::h2h::{against_the_spread, over_under, BetRecord},
+};
+
+/// Settles a bet from the result of its game
+///
+/// Home and away moneylines lose on a draw, as in three-way markets. Props need the stat
+/// line of their player in the game.
+///
+/// ## Returns
+///
+/// `Void` when the game was cancelled, or `None` while the game is not final or the bet
+/// cannot be graded from what is stored.
+pub fn grade(bet: &Bet, game: &Game, line: Option<&StatLine>) -> Option<BetResult> {
+    match game.status {
+        GameStatus::Cancelled => return Some(BetResult::Void),
+        GameStatus::Final => (),
+        _ => return None,
+    }
+    let won = |won: bool| {
+        if won {
+            BetResult::Won
+        } else {
+            BetResult::Lost
+        }
+    };
+    match (&bet.market, bet.selection) {
+        (Market::Moneyline, selection @ (Selection::Home | Selection::Away | Selection::Draw)) => {
+            let outcome = game.outcome_for(&game.home)?;
+            Some(won(match selection {
+                Selection::Home => outcome == Outcome::Win,
+                Selection::Away => outcome == Outcome::Loss,
+                _ => outcome == Outcome::Draw,
+            }))
+        }
+        (Market::Spread, Selection::Home | Selection::Away) => {
+            // The line of an away bet is the away handicap, the opposite of the home one
+            let (team, home_spread) = match bet.selection {
+                Selection::Home => (&game.home, bet.line?),
+                _ => (&game.away, -bet.line?),
+            };
+            Some(match against_the_spread(game, team, home_spread)? {
+                Outcome::Win => BetResult::Won,
+                Outcome::Loss => BetResult::Lost,
+                Outcome::Draw => BetResult::Push,
+            })
+        }
+        (Market::Total, Selection::Over | Selection::Under) => {
+            Some(match over_under(game, bet.line?) {
+                Some(side) => won(side == bet.selection),
+                None => BetResult::Push,
+            })
+        }
+        (Market::Prop { player, stat }, Selection::Over | Selection::Under) => {
+            // A stat missing from the line was not recorded, which is not the same as zero
+            let (value, total) = (
+                line.filter(|line| line.game == game.id && line.player == *player)?
+                    .stats
+                    .get(stat)
+                    .copied()?,
+                bet.line?,
+            );
+            Some(if value == total {
+                BetResult::Push
+            } else {
+                won((value > total) == (bet.selection == Selection::Over))
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Returns the price the selection of a bet closed at, the last one offered on its line
+/// before the game started, from the book of the bet when it priced the selection
+pub fn closing_price(bet: &Bet, game: &Game, odds: &[OddsSnapshot]) -> Option<f64> {
+    let mut offered: Vec<&OddsSnapshot> = odds
+        .iter()
+        .filter(|snapshot| {
+            snapshot.game == bet.game
+                && snapshot.market == bet.market
+                && snapshot.selection == bet.selection
+                && snapshot.line == bet.line
+                && snapshot.taken_at <= game.start
+        })
+        .collect();
+    if offered.iter().any(|snapshot| snapshot.book == bet.book) {
+        offered.retain(|snapshot| snapshot.book == bet.book);
+    }
+    offered
+        .into_iter()
+        .max_by_key(|snapshot| snapshot.taken_at)
+        .map(|snapshot| snapshot.price)
+}
+
+/// Returns the closing line value of odds taken against the closing price, positive
+/// when the bet got a better price than the market settled on
+pub fn closing_line_value(odds: f64, closing: f64) -> f64 {
+    odds / closing - 1.0
+}
+
+/// Represents the results of a set of bets
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct LedgerSummary {
+    pub bets: usize,
+    /// Bets not settled yet
+    pub open: usize,
+    pub record: BetRecord,
+    pub voids: u32,
+    /// Amount staked on settled bets that were not voided
+    pub staked: f64,
+    pub profit: f64,
+    clv_total: f64,
+    clv_bets: usize,
+    beat_close: usize,
+}
+impl LedgerSummary {
+    /// Counts a bet, along with its closing price when it has one
+    pub fn add(&mut self, bet: &Bet, closing: Option<f64>) -> &mut Self {
+        self.bets += 1;
+        let (Some(result), Some(profit)) = (bet.result, bet.profit()) else {
+            self.open += 1;
+            return self;
+        };
+        match result {
+            BetResult::Won => self.record.wins += 1,
+            BetResult::Lost => self.record.losses += 1,
+            BetResult::Push => self.record.pushes += 1,
+            BetResult::Void => {
+                self.voids += 1;
+                return self;
+            }
+        }
+        self.staked += bet.stake;
+        self.profit += profit;
+        if let Some(closing) = closing {
+            let clv = closing_line_value(bet.odds, closing);
+            self.clv_total += clv;
+            self.clv_bets += 1;
+            if clv > 0.0 {
+                self.beat_close += 1;
+            }
+        }
+        self
+    }
+    /// Returns the profit per unit staked
+    pub fn roi(&self) -> Option<f64> {
+        (self.staked > 0.0).then(|| self.profit / self.staked)
+    }
+    /// Returns the share of bets won, pushes left out
+    pub fn win_rate(&self) -> Option<f64> {
+        let decided = self.record.wins + self.record.losses;
+        (decided > 0).then(|| f64::from(self.record.wins) / f64::from(decided))
+    }
+    /// Returns the profit in units of the given size
+    pub fn units(&self, unit: f64) -> f64 {
+        self.profit / unit
+    }
+    /// Returns the average closing line value of the graded bets with a closing price
+    pub fn closing_line_value(&self) -> Option<f64> {
+        (self.clv_bets > 0).then(|| self.clv_total / self.clv_bets as f64)
+    }
+    /// Returns the share of the graded bets with a closing price that beat it
+    pub fn beat_close_rate(&self) -> Option<f64> {
+        (self.clv_bets > 0).then(|| self.beat_close as f64 / self.clv_bets as f64)
+    }
+}
+
+#[cfg(test)]
+mod ledger_tests {
+    use super::*;
+    use crate::model::{
+        game::mock::game,
+        id::{GameId, PlayerId, TeamId},
+    };
+    use chrono::Duration;
+
+    fn bet(market: Market, selection: Selection, line: Option<f64>) -> Bet {
+        Bet {
+            id: 1,
+            placed_at: game("1", "lal", "bos", None).start - Duration::days(1),
+            game: GameId::new("1"),
+            book: "pinnacle".to_string(),
+            market,
+            selection,
+            line,
+            odds: 2.0,
+            stake: 10.0,
+            result: None,
+            settled_at: None,
+        }
+    }
+
+    #[test]
+    fn test_grade() {
+        // lal beat bos 100-90 at home
+        let played = game("1", "lal", "bos", Some((100, 90)));
+        let grade = |market, selection, line| grade(&bet(market, selection, line), &played, None);
+
+        assert_eq!(
+            grade(Market::Moneyline, Selection::Home, None),
+            Some(BetResult::Won)
+        );
+        assert_eq!(
+            grade(Market::Moneyline, Selection::Draw, None),
+            Some(BetResult::Lost)
+        );
+        assert_eq!(
+            grade(Market::Spread, Selection::Home, Some(-10.0)),
+            Some(BetResult::Push)
+        );
+        assert_eq!(
+            grade(Market::Spread, Selection::Away, Some(10.5)),
+            Some(BetResult::Won)
+        );
+        assert_eq!(
+            grade(Market::Total, Selection::Under, Some(185.5)),
+            Some(BetResult::Lost)
+        );
+        assert_eq!(grade(Market::Total, Selection::Home, Some(185.5)), None);
+        assert_eq!(grade(Market::Spread, Selection::Home, None), None);
+
+        let scheduled = game("1", "lal", "bos", None);
+        assert_eq!(
+            super::grade(
+                &bet(Market::Moneyline, Selection::Home, None),
+                &scheduled,
+                None
+            ),
+            None
+        );
+        let mut cancelled = scheduled;
+        cancelled.status = GameStatus::Cancelled;
+        assert_eq!(
+            super::grade(
+                &bet(Market::Moneyline, Selection::Home, None),
+                &cancelled,
+                None
+            ),
+            Some(BetResult::Void)
+        );
+    }
+
+    #[test]
+    fn test_grade_prop() {
+        let played = game("1", "lal", "bos", Some((100, 90)));
+        let prop = bet(
+            Market::Prop {
+                player: PlayerId::new("lebron"),
+                stat: "pts".to_string(),
+            },
+            Selection::Over,
+            Some(25.5),
+        );
+        let mut line = StatLine::new(
+            GameId::new("1"),
+            PlayerId::new("lebron"),
+            TeamId::new("lal"),
+        );
+        line.set("pts", 30.0);
+
+        assert_eq!(grade(&prop, &played, Some(&line)), Some(BetResult::Won));
+        assert_eq!(grade(&prop, &played, None), None);
+        line.stats.clear();
+        line.set("reb", 8.0);
+        assert_eq!(grade(&prop, &played, Some(&line)), None);
+        line.set("pts", 20.0);
+        assert_eq!(grade(&prop, &played, Some(&line)), Some(BetResult::Lost));
+        line.player = PlayerId::new("ad");
+        assert_eq!(grade(&prop, &played, Some(&line)), None);
+    }
+
+    #[test]
+    fn test_closing_price() {
+        let played = game("1", "lal", "bos", Some((100, 90)));
+        let bet = bet(Market::Spread, Selection::Home, Some(-3.5));
+        let snapshot = |book: &str, line, price, hours| OddsSnapshot {
+            game: GameId::new("1"),
+            book: book.to_string(),
+            market: Market::Spread,
+            selection: Selection::Home,
+            line: Some(line),
+            price,
+            taken_at: played.start - Duration::hours(hours),
+        };
+        let mut odds = vec![
+            snapshot("draftkings", -3.5, 1.8, 1),
+            snapshot("pinnacle", -3.5, 1.95, 5),
+            snapshot("pinnacle", -3.5, 1.9, 2),
+            snapshot("pinnacle", -4.5, 2.05, 1),
+            snapshot("pinnacle", -3.5, 1.5, -1),
+        ];
+
+        assert_eq!(closing_price(&bet, &played, &odds), Some(1.9));
+        odds.retain(|snapshot| snapshot.book != "pinnacle");
+        assert_eq!(closing_price(&bet, &played, &odds), Some(1.8));
+        assert!((closing_line_value(2.0, 1.6) - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ledger_summary() {
+        let at = game("1", "lal", "bos", None).start;
+        let mut summary = LedgerSummary::default();
+        let mut won = bet(Market::Moneyline, Selection::Home, None);
+        won.settle(BetResult::Won, at);
+        let mut lost = bet(Market::Moneyline, Selection::Away, None);
+        lost.settle(BetResult::Lost, at);
+        let mut void = bet(Market::Moneyline, Selection::Away, None);
+        void.settle(BetResult::Void, at);
+        summary
+            .add(&won, Some(1.6))
+            .add(&lost, Some(2.5))
+            .add(&void, None)
+            .add(&bet(Market::Total, Selection::Over, Some(200.5)), None);
+
+        assert_eq!((summary.bets, summary.open, summary.voids), (4, 1, 1));
+        assert_eq!(summary.record.to_string(), "1-1-0");
+        assert_eq!(summary.staked, 20.0);
+        assert_eq!(summary.profit, 0.0);
+        assert_eq!(summary.roi(), Some(0.0));
+        assert_eq!(summary.win_rate(), Some(0.5));
+        assert_eq!(summary.units(5.0), 0.0);
+        assert!((summary.closing_line_value().unwrap() - 0.025).abs() < 1e-9);
+        assert_eq!(summary.beat_close_rate(), Some(0.5));
+        assert_eq!(LedgerSummary::default().roi(), None);
+    }
+}
@@ -0,0 +1,267 @@
+use super::vig;
+use crate::model::odds::{Market, OddsSnapshot, Selection};
+use std::collections::HashMap;
+
+/// Represents the highest price any book offers on a selection
+#[derive(Debug, Clone, PartialEq)]
+pub struct BestPrice {
+    pub selection: Selection,
+    /// Line of the selection from its own point of view (`+3.5` for the away spread)
+    pub line: Option<f64>,
+    pub book: String,
+    pub price: f64,
+    /// No-vig probability of the selection, averaged over the books quoting every selection
+    pub consensus: Option<f64>,
+}
+
+/// Represents one market at one line across every book
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketComparison {
+    pub market: Market,
+    /// Line from the home or over point of view, `None` for markets without a line
+    pub line: Option<f64>,
+    pub best: Vec<BestPrice>,
+    /// Number of books quoting every selection
+    pub books: usize,
+    /// Guaranteed return on the total staked when backing every selection at its best
+    /// price, `None` unless that return is positive
+    pub arbitrage: Option<f64>,
+}
+impl MarketComparison {
+    /// Returns the share of the total stake to put on each best price so every outcome
+    /// pays the same
+    pub fn arbitrage_stakes(&self) -> Vec<f64> {
+        vig::multiplicative(
+            &self
+                .best
+                .iter()
+                .map(|best| 1.0 / best.price)
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+/// Returns the line of a snapshot from the home or over point of view, so both sides of
+/// a spread land on the same line
+fn market_line(snapshot: &OddsSnapshot) -> Option<f64> {
+    match (&snapshot.market, snapshot.selection) {
+        (Market::Spread, Selection::Away) => snapshot.line.map(|line| -line),
+        _ => snapshot.line,
+    }
+}
+
+/// Compares the latest price of every book, market by market
+///
+/// ## Returns
+///
+/// One comparison per market and line, ordered by market then line, with the best price
+/// of each selection.
+pub fn compare_books(snapshots: &[OddsSnapshot]) -> Vec<MarketComparison> {
+    // The latest price each book offers on each selection
+    let mut latest: HashMap<(&str, &Market, Selection), &OddsSnapshot> = HashMap::new();
+    for snapshot in snapshots {
+        latest
+            .entry((&snapshot.book, &snapshot.market, snapshot.selection))
+            .and_modify(|current| {
+                if snapshot.taken_at >= current.taken_at {
+                    *current = snapshot;
+                }
+            })
+            .or_insert(snapshot);
+    }
+    let mut groups: Vec<(&Market, Option<f64>, Vec<&OddsSnapshot>)> = vec![];
+    for snapshot in latest.into_values() {
+        let line = market_line(snapshot);
+        match groups
+            .iter_mut()
+            .find(|(market, other, _)| **market == snapshot.market && *other == line)
+        {
+            Some((_, _, group)) => group.push(snapshot),
+            None => groups.push((&snapshot.market, line, vec![snapshot])),
+        }
+    }
+    groups.sort_by(|(a, a_line, _), (b, b_line, _)| {
+        a.to_string()
+            .cmp(&b.to_string())
+            .then(a_line.unwrap_or(0.0).total_cmp(&b_line.unwrap_or(0.0)))
+    });
+
+    groups
+        .into_iter()
+        .map(|(market, line, group)| compare_market(market, line, group))
+        .collect()
+}
+
+fn compare_market(
+    market: &Market,
+    line: Option<f64>,
+    mut group: Vec<&OddsSnapshot>,
+) -> MarketComparison {
+    group.sort_by(|a, b| a.book.cmp(&b.book));
+    // Every side of the market, even those no book quotes at this line
+    let mut selections: Vec<Selection> = match market {
+        Market::Moneyline | Market::Spread => vec![Selection::Home, Selection::Away],
+        Market::Total | Market::Prop { .. } => vec![Selection::Over, Selection::Under],
+    };
+    selections.extend(group.iter().map(|snapshot| snapshot.selection));
+    selections.sort_by_key(|selection| *selection as u8);
+    selections.dedup();
+
+    // Books quoting every selection give a complete market to remove the margin from
+    let mut books: Vec<&str> = group
+        .iter()
+        .map(|snapshot| snapshot.book.as_str())
+        .collect();
+    books.dedup();
+    let mut fair = vec![0.0; selections.len()];
+    let mut complete = 0;
+    for book in books {
+        let prices: Option<Vec<f64>> = selections
+            .iter()
+            .map(|selection| {
+                group
+                    .iter()
+                    .find(|snapshot| snapshot.book == book && snapshot.selection == *selection)
+                    .map(|snapshot| snapshot.price)
+            })
+            .collect();
+        let Some(prices) = prices else {
+            continue;
+        };
+        let implied: Vec<f64> = prices.iter().map(|price| 1.0 / price).collect();
+        for (total, probability) in fair.iter_mut().zip(vig::multiplicative(&implied)) {
+            *total += probability;
+        }
+        complete += 1;
+    }
+
+    let best: Vec<BestPrice> = selections
+        .iter()
+        .zip(&fair)
+        .filter_map(|(selection, fair)| {
+            let best = group
+                .iter()
+                .filter(|snapshot| snapshot.selection == *selection)
+                .max_by(|a, b| a.price.total_cmp(&b.price))?;
+            Some(BestPrice {
+                selection: *selection,
+                line: best.line,
+                book: best.book.clone(),
+                price: best.price,
+                consensus: (complete > 0).then(|| fair / complete as f64),
+            })
+        })
+        .collect();
+    let implied: Vec<f64> = best.iter().map(|best| 1.0 / best.price).collect();
+    let margin = vig::margin(&implied);
+    MarketComparison {
+        market: market.clone(),
+        line,
+        books: complete,
+        arbitrage: (best.len() == selections.len() && margin < 0.0)
+            .then(|| 1.0 / (1.0 + margin) - 1.0),
+        best,
+    }
+}
+
+#[cfg(test)]
+mod compare_books_tests {
+    use super::*;
+    use crate::model::game::mock::game;
+    use chrono::Duration;
+
+    fn snapshot(
+        book: &str,
+        market: Market,
+        selection: Selection,
+        line: Option<f64>,
+        price: f64,
+    ) -> OddsSnapshot {
+        let game = game("1", "lal", "bos", None);
+        OddsSnapshot {
+            game: game.id,
+            book: book.to_string(),
+            market,
+            selection,
+            line,
+            price,
+            taken_at: game.start - Duration::hours(1),
+        }
+    }
+
+    #[test]
+    fn test_compare_books() {
+        let mut stale = snapshot("fanduel", Market::Moneyline, Selection::Home, None, 3.0);
+        stale.taken_at -= Duration::hours(1);
+        let snapshots = [
+            snapshot("pinnacle", Market::Moneyline, Selection::Home, None, 1.8),
+            snapshot("pinnacle", Market::Moneyline, Selection::Away, None, 2.05),
+            snapshot("fanduel", Market::Moneyline, Selection::Home, None, 1.85),
+            snapshot("fanduel", Market::Moneyline, Selection::Away, None, 1.95),
+            stale,
+            snapshot(
+                "pinnacle",
+                Market::Spread,
+                Selection::Home,
+                Some(-3.5),
+                1.91,
+            ),
+            snapshot("pinnacle", Market::Spread, Selection::Away, Some(3.5), 1.91),
+            snapshot("fanduel", Market::Spread, Selection::Away, Some(3.0), 1.87),
+        ];
+        let comparisons = compare_books(&snapshots);
+
+        assert_eq!(comparisons.len(), 3);
+        let moneyline = &comparisons[0];
+        assert_eq!(moneyline.books, 2);
+        assert_eq!(
+            moneyline
+                .best
+                .iter()
+                .map(|best| (best.book.as_str(), best.price))
+                .collect::<Vec<_>>(),
+            [("fanduel", 1.85), ("pinnacle", 2.05)]
+        );
+        let consensus: f64 = moneyline
+            .best
+            .iter()
+            .filter_map(|best| best.consensus)
+            .sum();
+        assert!((consensus - 1.0).abs() < 1e-12);
+        assert_eq!(moneyline.arbitrage, None);
+
+        assert_eq!(comparisons[1].line, Some(-3.5));
+        assert_eq!(comparisons[1].best[1].line, Some(3.5));
+        assert_eq!(comparisons[2].line, Some(-3.0));
+        assert_eq!(comparisons[2].books, 0);
+        assert_eq!(comparisons[2].best.len(), 1);
+        assert_eq!(comparisons[2].best[0].consensus, None);
+    }
+
+    #[test]
+    fn test_arbitrage() {
+        let comparison = &compare_books(&[
+            snapshot("pinnacle", Market::Total, Selection::Over, Some(220.5), 2.1),
+            snapshot(
+                "pinnacle",
+                Market::Total,
+                Selection::Under,
+                Some(220.5),
+                1.75,
+            ),
+            snapshot("fanduel", Market::Total, Selection::Over, Some(220.5), 1.8),
+            snapshot(
+                "fanduel",
+                Market::Total,
+                Selection::Under,
+                Some(220.5),
+                2.05,
+            ),
+        ])[0];
+        let stakes = comparison.arbitrage_stakes();
+
+        // 1 / (1/2.1 + 1/2.05) - 1
+        assert!((comparison.arbitrage.unwrap() - 0.03735).abs() < 1e-5);
+        assert!((stakes[0] * 2.1 - stakes[1] * 2.05).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,308 @@
+use self::{error::OddsError, ratio::Ratio};
+use std::{fmt, str::FromStr};
+
+pub mod backtest;
+pub mod bets;
+pub mod error;
+pub mod ledger;
+pub mod market;
+pub mod ratio;
+pub mod value;
+pub mod vig;
+
+/// Represents the ways bookmakers quote a price
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OddsFormat {
+    /// Profit on a 100 stake ("+150"), or the stake needed to profit 100 ("-110")
+    American,
+    /// Payout per unit staked, stake included ("2.50")
+    Decimal,
+    /// Profit per unit staked as a fraction ("3/2")
+    Fractional,
+    /// Profit per unit staked ("1.50")
+    HongKong,
+    /// American odds divided by 100 ("1.50", "-1.10")
+    Indonesian,
+    /// Profit per unit staked up to evens, `-1 / profit` beyond ("0.91", "-0.67")
+    Malay,
+    /// Implied probability ("40%")
+    Probability,
+}
+impl OddsFormat {
+    pub const ALL: [OddsFormat; 7] = [
+        OddsFormat::Decimal,
+        OddsFormat::American,
+        OddsFormat::Fractional,
+        OddsFormat::HongKong,
+        OddsFormat::Indonesian,
+        OddsFormat::Malay,
+        OddsFormat::Probability,
+    ];
+
+    /// Returns the kebab-case name used on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            OddsFormat::American => "american",
+            OddsFormat::Decimal => "decimal",
+            OddsFormat::Fractional => "fractional",
+            OddsFormat::HongKong => "hong-kong",
+            OddsFormat::Indonesian => "indonesian",
+            OddsFormat::Malay => "malay",
+            OddsFormat::Probability => "probability",
+        }
+    }
+}
+impl fmt::Display for OddsFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+impl FromStr for OddsFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        OddsFormat::ALL
+            .into_iter()
+            .find(|item| item.name() == format)
+            .ok_or_else(|| format!("unknown odds format '{}'", format))
+    }
+}
+
+/// Represents a price, held exactly as decimal odds
+///
+/// ## Examples
+///
+/// ```
+/// # use statpack::odds::{Odds, OddsFormat};
+/// # fn main() -> Result<(), statpack::odds::error::OddsError> {
+/// let odds = Odds::parse(OddsFormat::American, "-110")?;
+/// assert_eq!(odds.format(OddsFormat::Fractional), "10/11");
+/// assert_eq!(odds.format(OddsFormat::Decimal), "1.91");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Odds(Ratio);
+impl Odds {
+    /// Creates odds from a decimal price, which must pay more than the stake
+    pub fn from_decimal(decimal: Ratio) -> Option<Odds> {
+        (decimal > Ratio::ONE).then_some(Odds(decimal))
+    }
+    /// Creates odds from a decimal price as stored in `OddsSnapshot::price`
+    pub fn from_price(price: f64) -> Option<Odds> {
+        Ratio::from_f64(price).and_then(Odds::from_decimal)
+    }
+    /// Creates odds from an implied probability between `0` and `1` exclusive
+    pub fn from_probability(probability: Ratio) -> Option<Odds> {
+        (probability > Ratio::ZERO)
+            .then(|| probability.recip())
+            .flatten()
+            .and_then(Odds::from_decimal)
+    }
+    /// Reads a price quoted in the format
+    pub fn parse(format: OddsFormat, value: &str) -> Result<Odds, OddsError> {
+        let invalid = || OddsError::Invalid {
+            format,
+            value: value.to_string(),
+        };
+        let trimmed = value.trim();
+        let number = || trimmed.parse::<Ratio>().map_err(|_| invalid());
+        let hundred = Ratio::integer(100);
+        let odds = match format {
+            OddsFormat::Decimal => Odds::from_decimal(number()?),
+            OddsFormat::American => {
+                let american = number()?;
+                if american >= hundred {
+                    Odds::from_decimal(Ratio::ONE + american / hundred)
+                } else if american <= -hundred {
+                    Odds::from_decimal(Ratio::ONE - hundred / american)
+                } else {
+                    None
+                }
+            }
+            OddsFormat::Fractional => {
+                let profit = match trimmed.to_lowercase().as_str() {
+                    "evens" | "evs" => Ratio::ONE,
+                    _ => number()?,
+                };
+                Odds::from_decimal(Ratio::ONE + profit)
+            }
+            OddsFormat::HongKong => Odds::from_decimal(Ratio::ONE + number()?),
+            OddsFormat::Indonesian => {
+                let indonesian = number()?;
+                if indonesian >= Ratio::ONE {
+                    Odds::from_decimal(Ratio::ONE + indonesian)
+                } else if indonesian <= -Ratio::ONE {
+                    Odds::from_decimal(Ratio::ONE - Ratio::ONE / indonesian)
+                } else {
+                    None
+                }
+            }
+            OddsFormat::Malay => {
+                let malay = number()?;
+                if malay > Ratio::ZERO && malay <= Ratio::ONE {
+                    Odds::from_decimal(Ratio::ONE + malay)
+                } else if malay >= -Ratio::ONE && malay < Ratio::ZERO {
+                    Odds::from_decimal(Ratio::ONE - Ratio::ONE / malay)
+                } else {
+                    None
+                }
+            }
+            OddsFormat::Probability => {
+                let (number, percent) = match trimmed.strip_suffix('%') {
+                    Some(percent) => (percent.parse::<Ratio>().map_err(|_| invalid())?, true),
+                    None => (number()?, false),
+                };
+                let probability = if percent { number / hundred } else { number };
+                (probability < Ratio::ONE)
+                    .then(|| Odds::from_probability(probability))
+                    .flatten()
+            }
+        };
+        odds.ok_or_else(invalid)
+    }
+    /// Returns the payout per unit staked, stake included
+    pub fn decimal(&self) -> Ratio {
+        self.0
+    }
+    /// Returns the profit per unit staked
+    pub fn profit(&self) -> Ratio {
+        self.0 - Ratio::ONE
+    }
+    /// Returns the probability the price implies, ignoring the bookmaker's margin
+    pub fn implied_probability(&self) -> Ratio {
+        Ratio::ONE / self.0
+    }
+    /// Returns the American odds, fractional when the price has no exact American quote
+    pub fn american(&self) -> Ratio {
+        let hundred = Ratio::integer(100);
+        if self.0 >= Ratio::integer(2) {
+            self.profit() * hundred
+        } else {
+            -hundred / self.profit()
+        }
+    }
+    /// Returns the Malay odds
+    pub fn malay(&self) -> Ratio {
+        if self.0 <= Ratio::integer(2) {
+            self.profit()
+        } else {
+            -Ratio::ONE / self.profit()
+        }
+    }
+    /// Quotes the price in the format
+    ///
+    /// Fractional odds are exact, American odds are rounded to whole numbers, probabilities
+    /// to percentages with two decimals and the other formats to two decimals.
+    pub fn format(&self, format: OddsFormat) -> String {
+        match format {
+            OddsFormat::Decimal => self.0.to_decimal_string(2),
+            OddsFormat::American => {
+                let american = self.american().round();
+                if american > 0 {
+                    format!("+{}", american)
+                } else {
+                    american.to_string()
+                }
+            }
+            OddsFormat::Fractional => {
+                let profit = self.profit();
+                format!("{}/{}", profit.numerator(), profit.denominator())
+            }
+            OddsFormat::HongKong => self.profit().to_decimal_string(2),
+            OddsFormat::Indonesian => (self.american() / Ratio::integer(100)).to_decimal_string(2),
+            OddsFormat::Malay => self.malay().to_decimal_string(2),
+            OddsFormat::Probability => format!(
+                "{}%",
+                (self.implied_probability() * Ratio::integer(100)).to_decimal_string(2)
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod odds_tests {
+    use super::*;
+
+    fn convert(from: OddsFormat, value: &str) -> Vec<String> {
+        let odds = Odds::parse(from, value).unwrap();
+        OddsFormat::ALL
+            .iter()
+            .map(|format| odds.format(*format))
+            .collect()
+    }
+
+    #[test]
+    fn test_conversions() {
+        // decimal, american, fractional, hong-kong, indonesian, malay, probability
+        assert_eq!(
+            convert(OddsFormat::American, "-110"),
+            ["1.91", "-110", "10/11", "0.91", "-1.10", "0.91", "52.38%"]
+        );
+        assert_eq!(
+            convert(OddsFormat::Fractional, "3/2"),
+            ["2.50", "+150", "3/2", "1.50", "1.50", "-0.67", "40.00%"]
+        );
+        assert_eq!(
+            convert(OddsFormat::Decimal, "2"),
+            ["2.00", "+100", "1/1", "1.00", "1.00", "1.00", "50.00%"]
+        );
+        assert_eq!(
+            convert(OddsFormat::Malay, "-0.5"),
+            convert(OddsFormat::Fractional, "2/1")
+        );
+        for (format, value, decimal) in [
+            (OddsFormat::Indonesian, "-2", "1.5"),
+            (OddsFormat::HongKong, "0.8", "1.8"),
+            (OddsFormat::Probability, "25%", "4"),
+            (OddsFormat::Probability, "0.25", "4"),
+        ] {
+            assert_eq!(
+                Odds::parse(format, value),
+                Odds::parse(OddsFormat::Decimal, decimal)
+            );
+        }
+        assert_eq!(
+            Odds::parse(OddsFormat::Fractional, "evens"),
+            Odds::parse(OddsFormat::Decimal, "2.0")
+        );
+    }
+
+    #[test]
+    fn test_exact() {
+        let odds = Odds::parse(OddsFormat::American, "-110").unwrap();
+        assert_eq!(odds.decimal(), Ratio::new(21, 11));
+        assert_eq!(odds.implied_probability(), Ratio::new(11, 21));
+        assert_eq!(odds.american(), Ratio::integer(-110));
+        assert_eq!(
+            Odds::from_price(1.91).unwrap().profit(),
+            Ratio::new(91, 100)
+        );
+        assert_eq!(
+            Odds::from_price(1.91).unwrap().american(),
+            Ratio::new(-10000, 91)
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        for (format, value) in [
+            (OddsFormat::Decimal, "1"),
+            (OddsFormat::Decimal, "abc"),
+            (OddsFormat::American, "50"),
+            (OddsFormat::Fractional, "-1/2"),
+            (OddsFormat::Indonesian, "0.5"),
+            (OddsFormat::Malay, "1.5"),
+            (OddsFormat::Probability, "100%"),
+            (OddsFormat::Probability, "0"),
+        ] {
+            assert!(Odds::parse(format, value).is_err(), "{} {}", format, value);
+        }
+        assert_eq!(
+            Odds::parse(OddsFormat::American, "50")
+                .unwrap_err()
+                .to_string(),
+            "'50' is not valid american odds"
+        );
+    }
+}
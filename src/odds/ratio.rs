@@ -0,0 +1,242 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+/// Represents an exact fraction, always reduced and with a positive denominator
+///
+/// Odds quoted as "1.91", "-110" or "10/11" are exact fractions, so converting them
+/// through a `Ratio` round-trips without floating point drift.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio {
+    numerator: i128,
+    denominator: i128,
+}
+impl Ratio {
+    pub const ZERO: Ratio = Ratio::integer(0);
+    pub const ONE: Ratio = Ratio::integer(1);
+
+    /// Creates the reduced fraction `numerator / denominator`
+    ///
+    /// ## Panics
+    ///
+    /// When `denominator` is `0`.
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert!(denominator != 0, "denominator is zero");
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Self {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        }
+    }
+    pub const fn integer(value: i128) -> Self {
+        Self {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+    /// Converts a float through its shortest decimal representation, so `1.91` becomes
+    /// exactly `191/100`
+    pub fn from_f64(value: f64) -> Option<Self> {
+        value.is_finite().then(|| value.to_string().parse().ok())?
+    }
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+    /// Returns `1 / self`, `None` for zero
+    pub fn recip(&self) -> Option<Self> {
+        (self.numerator != 0).then(|| Self::new(self.denominator, self.numerator))
+    }
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+    /// Rounds to the nearest integer, halves away from zero
+    pub fn round(&self) -> i128 {
+        let doubled = 2 * self.numerator + self.numerator.signum() * self.denominator;
+        doubled / (2 * self.denominator)
+    }
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+    /// Formats the value with a fixed number of decimals, rounding halves away from zero
+    pub fn to_decimal_string(&self, decimals: u32) -> String {
+        let scaled = (*self * Ratio::integer(10_i128.pow(decimals))).round();
+        let sign = if scaled < 0 { "-" } else { "" };
+        let (whole, fraction) = (
+            scaled.abs() / 10_i128.pow(decimals),
+            scaled.abs() % 10_i128.pow(decimals),
+        );
+        if decimals == 0 {
+            format!("{}{}", sign, whole)
+        } else {
+            format!(
+                "{}{}.{:0width$}",
+                sign,
+                whole,
+                fraction,
+                width = decimals as usize
+            )
+        }
+    }
+}
+
+/// Returns the greatest common divisor, `1` when both are zero
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.max(1)
+}
+
+impl fmt::Display for Ratio {
+    /// Formats the value as `numerator/denominator`, or the integer alone
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+impl FromStr for Ratio {
+    type Err = String;
+
+    /// Parses an integer ("-110"), a decimal ("1.91") or a fraction ("10/11")
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("'{}' is not a number", value);
+        let value = value.trim();
+        if let Some((numerator, denominator)) = value.split_once('/') {
+            let numerator: Ratio = numerator.parse().map_err(|_| invalid())?;
+            let denominator: Ratio = denominator.parse().map_err(|_| invalid())?;
+            return denominator
+                .recip()
+                .map(|denominator| numerator * denominator)
+                .ok_or_else(invalid);
+        }
+        let unsigned = value.trim_start_matches(['+', '-']);
+        let (whole, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        let digits = format!("{}{}", whole, fraction);
+        // 30 digits keep every product of two values inside an i128
+        if digits.is_empty()
+            || digits.len() > 30
+            || !digits.bytes().all(|byte| byte.is_ascii_digit())
+            || value.len() - unsigned.len() > 1
+        {
+            return Err(invalid());
+        }
+        let numerator: i128 = digits.parse().map_err(|_| invalid())?;
+        let sign = if value.starts_with('-') { -1 } else { 1 };
+        Ok(Ratio::new(
+            sign * numerator,
+            10_i128.pow(fraction.len() as u32),
+        ))
+    }
+}
+
+impl Add for Ratio {
+    type Output = Ratio;
+
+    fn add(self, other: Ratio) -> Ratio {
+        Ratio::new(
+            self.numerator * other.denominator + other.numerator * self.denominator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+impl Sub for Ratio {
+    type Output = Ratio;
+
+    fn sub(self, other: Ratio) -> Ratio {
+        self + -other
+    }
+}
+impl Mul for Ratio {
+    type Output = Ratio;
+
+    fn mul(self, other: Ratio) -> Ratio {
+        Ratio::new(
+            self.numerator * other.numerator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+impl Div for Ratio {
+    type Output = Ratio;
+
+    /// ## Panics
+    ///
+    /// When dividing by zero.
+    fn div(self, other: Ratio) -> Ratio {
+        assert!(other.numerator != 0, "division by zero");
+        Ratio::new(
+            self.numerator * other.denominator,
+            self.denominator * other.numerator,
+        )
+    }
+}
+impl Neg for Ratio {
+    type Output = Ratio;
+
+    fn neg(self) -> Ratio {
+        Ratio::new(-self.numerator, self.denominator)
+    }
+}
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Ratio {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numerator * other.denominator).cmp(&(other.numerator * self.denominator))
+    }
+}
+
+#[cfg(test)]
+mod ratio_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("1.91".parse(), Ok(Ratio::new(191, 100)));
+        assert_eq!("-110".parse(), Ok(Ratio::integer(-110)));
+        assert_eq!("+2.50".parse(), Ok(Ratio::new(5, 2)));
+        assert_eq!("10/11".parse(), Ok(Ratio::new(10, 11)));
+        assert_eq!("1.5/2".parse(), Ok(Ratio::new(3, 4)));
+        for invalid in ["", "1/0", "abc", "1.2.3", "--1", "1e5"] {
+            assert!(invalid.parse::<Ratio>().is_err(), "{}", invalid);
+        }
+        assert_eq!(Ratio::from_f64(1.91), Some(Ratio::new(191, 100)));
+        assert_eq!(Ratio::from_f64(f64::NAN), None);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let (third, half) = (Ratio::new(1, 3), Ratio::new(-2, -4));
+
+        assert_eq!(third + half, Ratio::new(5, 6));
+        assert_eq!(third - half, Ratio::new(-1, 6));
+        assert_eq!(third * half, Ratio::new(1, 6));
+        assert_eq!(third / half, Ratio::new(2, 3));
+        assert_eq!(Ratio::ZERO.recip(), None);
+        assert!(third < half);
+        assert_eq!(Ratio::new(6, -4).to_string(), "-3/2");
+    }
+
+    #[test]
+    fn test_round() {
+        assert_eq!(Ratio::new(5, 2).round(), 3);
+        assert_eq!(Ratio::new(-5, 2).round(), -3);
+        assert_eq!(Ratio::new(-10989, 100).round(), -110);
+        assert_eq!(Ratio::new(2, 3).to_decimal_string(2), "0.67");
+        assert_eq!(Ratio::new(-1, 200).to_decimal_string(2), "-0.01");
+        assert_eq!(Ratio::integer(2).to_decimal_string(2), "2.00");
+        assert_eq!(Ratio::new(7, 2).to_decimal_string(0), "4");
+    }
+}
@@ -0,0 +1,104 @@
+/// Represents a price offered on a selection along with the chance a model gives it
+///
+/// ## Examples
+///
+/// ```
+/// # use statpack::odds::value::ValueBet;
+/// let bet = ValueBet::new(0.55, 2.0);
+/// let stake = bet.stake(1000.0, 0.5); // half Kelly
+/// assert!((stake - 50.0).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueBet {
+    /// Chance the selection wins, between `0` and `1`
+    pub probability: f64,
+    /// Decimal odds offered
+    pub odds: f64,
+}
+impl ValueBet {
+    pub fn new(probability: f64, odds: f64) -> Self {
+        Self { probability, odds }
+    }
+    /// Returns the chance of winning the odds imply
+    pub fn implied_probability(&self) -> f64 {
+        1.0 / self.odds
+    }
+    /// Returns the model probability minus the implied one
+    pub fn edge(&self) -> f64 {
+        self.probability - self.implied_probability()
+    }
+    /// Returns the expected profit per unit staked, negative when the bet loses in the long
+    /// run
+    pub fn expected_value(&self) -> f64 {
+        self.probability * self.odds - 1.0
+    }
+    /// Indicates if the bet is expected to make money
+    pub fn is_value(&self) -> bool {
+        self.expected_value() > 0.0
+    }
+    /// Returns the share of the bankroll the Kelly criterion stakes, `0` without an edge
+    pub fn kelly(&self) -> f64 {
+        (self.expected_value() / (self.odds - 1.0)).max(0.0)
+    }
+    /// Returns the amount to stake out of `bankroll` betting `fraction` of the Kelly stake
+    /// (`0.5` for half Kelly)
+    pub fn stake(&self, bankroll: f64, fraction: f64) -> f64 {
+        bankroll * self.kelly() * fraction
+    }
+    /// Returns the expected logarithmic growth of the bankroll per bet when staking `share`
+    /// of it, which the full Kelly stake maximizes
+    ///
+    /// ## Returns
+    ///
+    /// `-inf` when staking the whole bankroll on a bet that can lose.
+    pub fn growth(&self, share: f64) -> f64 {
+        let win = self.probability * (share * (self.odds - 1.0)).ln_1p();
+        let loss = 1.0 - self.probability;
+        if loss == 0.0 {
+            win
+        } else {
+            win + loss * (-share).ln_1p()
+        }
+    }
+}
+
+#[cfg(test)]
+mod value_bet_tests {
+    use super::*;
+
+    fn close(actual: f64, expected: f64) -> bool {
+        (actual - expected).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_value_bet() {
+        let bet = ValueBet::new(0.55, 2.0);
+
+        assert!(close(bet.edge(), 0.05));
+        assert!(close(bet.expected_value(), 0.1));
+        assert!(bet.is_value());
+        assert!(close(bet.kelly(), 0.1));
+        assert!(close(bet.stake(1000.0, 0.5), 50.0));
+        assert!(close(
+            bet.growth(0.1),
+            0.55 * 1.1f64.ln() + 0.45 * 0.9f64.ln()
+        ));
+        // Full Kelly grows the bankroll fastest, over-betting shrinks it
+        assert!(bet.growth(0.1) > bet.growth(0.05));
+        assert!(bet.growth(0.1) > bet.growth(0.15));
+        assert!(bet.growth(0.3) < 0.0);
+        assert_eq!(bet.growth(1.0), f64::NEG_INFINITY);
+        assert_eq!(bet.growth(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_value_bet_without_edge() {
+        let bet = ValueBet::new(0.5, 1.91);
+
+        assert!(!bet.is_value());
+        assert!(bet.expected_value() < 0.0);
+        assert_eq!(bet.kelly(), 0.0);
+        assert_eq!(bet.stake(1000.0, 1.0), 0.0);
+        assert!(close(ValueBet::new(1.0, 1.5).kelly(), 1.0));
+    }
+}
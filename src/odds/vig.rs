@@ -0,0 +1,208 @@
+use std::{fmt, str::FromStr};
+
+/// Most bisection steps taken solving for the power and Shin methods
+const ITERATIONS: usize = 100;
+
+/// Represents a way of removing the bookmaker's margin from implied probabilities
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Scales every probability by the same factor
+    Multiplicative,
+    /// Takes the same share of the margin off every probability
+    Additive,
+    /// Raises every probability to the power that makes them add up to `1`, taking more
+    /// margin off long shots
+    Power,
+    /// Models the margin as protection against insider bettors (Shin, 1993), taking more
+    /// margin off long shots
+    Shin,
+}
+impl Method {
+    pub const ALL: [Method; 4] = [
+        Method::Multiplicative,
+        Method::Additive,
+        Method::Power,
+        Method::Shin,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Method::Multiplicative => "multiplicative",
+            Method::Additive => "additive",
+            Method::Power => "power",
+            Method::Shin => "shin",
+        }
+    }
+    /// Returns the fair probabilities of every selection of a market
+    ///
+    /// `implied` holds the implied probability (`1 / decimal odds`) of every selection, a
+    /// market without margin is returned unchanged.
+    pub fn fair(&self, implied: &[f64]) -> Vec<f64> {
+        if margin(implied) <= 0.0 {
+            return implied.to_vec();
+        }
+        match self {
+            Method::Multiplicative => multiplicative(implied),
+            Method::Additive => additive(implied),
+            Method::Power => power(implied),
+            Method::Shin => shin(implied),
+        }
+    }
+}
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+impl FromStr for Method {
+    type Err = String;
+
+    fn from_str(method: &str) -> Result<Self, Self::Err> {
+        Method::ALL
+            .into_iter()
+            .find(|item| item.name() == method)
+            .ok_or_else(|| format!("unknown vig removal method '{}'", method))
+    }
+}
+
+/// Returns the bookmaker's margin (overround) of a market: how much its implied
+/// probabilities add up to over `1`
+pub fn margin(implied: &[f64]) -> f64 {
+    implied.iter().sum::<f64>() - 1.0
+}
+
+/// Removes the margin by scaling every implied probability by the same factor
+pub fn multiplicative(implied: &[f64]) -> Vec<f64> {
+    let total: f64 = implied.iter().sum();
+    implied
+        .iter()
+        .map(|probability| probability / total)
+        .collect()
+}
+
+/// Removes the margin by subtracting an equal share of it from every implied probability.
+/// Long shots that would fall below `0` get `0`, their share going to the others.
+pub fn additive(implied: &[f64]) -> Vec<f64> {
+    let mut fair = implied.to_vec();
+    let mut active: Vec<usize> = (0..implied.len()).collect();
+    loop {
+        let total: f64 = active.iter().map(|&i| implied[i]).sum();
+        let share = (total - 1.0) / active.len() as f64;
+        for &i in &active {
+            fair[i] = implied[i] - share;
+        }
+        let before = active.len();
+        active.retain(|&i| fair[i] > 0.0);
+        if active.len() == before {
+            return fair;
+        }
+        for probability in fair.iter_mut().filter(|probability| **probability <= 0.0) {
+            *probability = 0.0;
+        }
+    }
+}
+
+/// Removes the margin by raising every implied probability to the power `k` for which
+/// they add up to `1`
+pub fn power(implied: &[f64]) -> Vec<f64> {
+    let total = |k: f64| {
+        implied
+            .iter()
+            .map(|probability| probability.powf(k))
+            .sum::<f64>()
+    };
+    // The total falls as `k` grows, from above 1 at `k = 1`
+    let mut high = 2.0;
+    while total(high) > 1.0 {
+        high *= 2.0;
+    }
+    let k = bisect(1.0, high, |k| total(k) > 1.0);
+    implied
+        .iter()
+        .map(|probability| probability.powf(k))
+        .collect()
+}
+
+/// Removes the margin with Shin's model, solving for the share `z` of insider money that
+/// makes the fair probabilities add up to `1`
+pub fn shin(implied: &[f64]) -> Vec<f64> {
+    let sum: f64 = implied.iter().sum();
+    let fair = |z: f64| -> Vec<f64> {
+        implied
+            .iter()
+            .map(|probability| {
+                ((z * z + 4.0 * (1.0 - z) * probability * probability / sum).sqrt() - z)
+                    / (2.0 * (1.0 - z))
+            })
+            .collect()
+    };
+    // The total falls as `z` grows, from `sqrt(sum)` at `z = 0`
+    let z = bisect(0.0, 1.0 - f64::EPSILON, |z| {
+        fair(z).iter().sum::<f64>() > 1.0
+    });
+    fair(z)
+}
+
+/// Returns the point between `low` and `high` where `too_low` stops holding
+fn bisect(mut low: f64, mut high: f64, too_low: impl Fn(f64) -> bool) -> f64 {
+    for _ in 0..ITERATIONS {
+        let middle = (low + high) / 2.0;
+        if too_low(middle) {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    (low + high) / 2.0
+}
+
+#[cfg(test)]
+mod vig_tests {
+    use super::*;
+
+    fn close(actual: &[f64], expected: &[f64]) -> bool {
+        actual
+            .iter()
+            .zip(expected)
+            .all(|(actual, expected)| (actual - expected).abs() < 1e-4)
+    }
+
+    #[test]
+    fn test_multiplicative() {
+        let implied = [1.0 / 1.91, 1.0 / 1.91];
+
+        assert!((margin(&implied) - 0.0471).abs() < 1e-4);
+        assert_eq!(multiplicative(&implied), [0.5, 0.5]);
+        let fair = multiplicative(&[0.5, 0.3, 0.3]);
+        assert!((fair[0] - 0.4545).abs() < 1e-4);
+        assert!((fair.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_methods() {
+        // A favourite at 1.5 and an outsider at 2.6
+        let implied = [1.0 / 1.5, 1.0 / 2.6];
+
+        assert!(close(
+            &Method::Multiplicative.fair(&implied),
+            &[0.6341, 0.3659]
+        ));
+        assert!(close(&Method::Additive.fair(&implied), &[0.6410, 0.3590]));
+        assert!(close(&Method::Power.fair(&implied), &[0.6446, 0.3554]));
+        // Shin's model matches the additive method on two-way markets
+        assert!(close(&Method::Shin.fair(&implied), &[0.6410, 0.3590]));
+        let shin = Method::Shin.fair(&[0.5, 0.3, 0.25]);
+        assert!(shin[2] < multiplicative(&[0.5, 0.3, 0.25])[2]);
+        for method in Method::ALL {
+            let fair = method.fair(&[0.5, 0.3, 0.25]);
+            assert!((fair.iter().sum::<f64>() - 1.0).abs() < 1e-9, "{}", method);
+        }
+        assert_eq!(Method::Power.fair(&[0.5, 0.45]), [0.5, 0.45]);
+        assert_eq!("shin".parse(), Ok(Method::Shin));
+    }
+
+    #[test]
+    fn test_additive_clamps() {
+        assert!(close(&additive(&[0.9, 0.3, 0.01]), &[0.8, 0.2, 0.0]));
+    }
+}
@@ -0,0 +1,60 @@
+use super::{error::ProviderError, Resource};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+/// Represents a response kept on disk along with what is needed to revalidate it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    pub body: String,
+}
+
+/// Keeps the last response of every resource on disk
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseCache {
+    dir: PathBuf,
+}
+impl ResponseCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+    /// Returns the cached response of a resource, unreadable entries count as missing
+    pub fn get(&self, resource: &Resource) -> Option<CachedResponse> {
+        let data = fs::read_to_string(self.dir.join(resource.file())).ok()?;
+        serde_json::from_str(&data).ok()
+    }
+    /// Replaces the cached response of a resource
+    pub fn put(&self, resource: &Resource, response: &CachedResponse) -> Result<(), ProviderError> {
+        let file = self.dir.join(resource.file());
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(file, serde_json::to_string(response)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod response_cache_tests {
+    use super::*;
+    use crate::model::id::GameId;
+
+    #[test]
+    fn test_put_and_get() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResponseCache::new(dir.path());
+        let game = GameId::new("nba:1");
+        let response = CachedResponse {
+            etag: Some("\"v1\"".to_string()),
+            last_modified: None,
+            body: "[]".to_string(),
+        };
+
+        assert_eq!(cache.get(&Resource::Odds(&game)), None);
+        cache.put(&Resource::Odds(&game), &response).unwrap();
+        assert_eq!(cache.get(&Resource::Odds(&game)), Some(response));
+    }
+}
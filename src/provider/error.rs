@@ -0,0 +1,52 @@
+use std::{error::Error, fmt};
+
+/// Represents the errors raised while fetching data from a `DataProvider`
+#[derive(Debug)]
+pub enum ProviderError {
+    Io(std::io::Error),
+    /// The response could not be decoded into the requested type
+    Json(serde_json::Error),
+    /// The source has no data for the resource path
+    NotFound(String),
+    /// The server answered with an error status
+    Http {
+        url: String,
+        status: u16,
+    },
+    /// The server could not be reached
+    Transport(String),
+}
+impl fmt::Display for ProviderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProviderError::Io(error) => write!(f, "{}", error),
+            ProviderError::Json(error) => write!(f, "unexpected response: {}", error),
+            ProviderError::NotFound(path) => write!(f, "no data for '{}'", path),
+            ProviderError::Http { url, status } => {
+                write!(f, "request to {} failed with status {}", url, status)
+            }
+            ProviderError::Transport(message) => write!(f, "network error: {}", message),
+        }
+    }
+}
+impl Error for ProviderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ProviderError::Io(error) => Some(error),
+            ProviderError::Json(error) => Some(error),
+            ProviderError::NotFound(_)
+            | ProviderError::Http { .. }
+            | ProviderError::Transport(_) => None,
+        }
+    }
+}
+impl From<std::io::Error> for ProviderError {
+    fn from(error: std::io::Error) -> Self {
+        ProviderError::Io(error)
+    }
+}
+impl From<serde_json::Error> for ProviderError {
+    fn from(error: serde_json::Error) -> Self {
+        ProviderError::Json(error)
+    }
+}
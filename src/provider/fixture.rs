@@ -0,0 +1,127 @@
+use super::{error::ProviderError, DataProvider, Resource};
+use serde::Serialize;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// A `DataProvider` replaying JSON responses recorded on disk
+///
+/// Every resource is a file under the root directory named after its path, e.g.
+/// `games/nba_1/odds.json` (see `Resource::file`).
+///
+/// ## Examples
+///
+/// ```no_run
+/// # use chrono::NaiveDate;
+/// # use statpack::{model::id::LeagueId, provider::{fixture::FixtureProvider, DataProvider}};
+/// # fn main() -> Result<(), statpack::provider::error::ProviderError> {
+/// # let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+/// let provider = FixtureProvider::new("fixtures/nba");
+/// let games = provider.schedule(&LeagueId::new("nba"), date)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixtureProvider {
+    root: PathBuf,
+}
+impl FixtureProvider {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+    /// Returns the root directory fixtures are read from
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+    /// Returns the file a resource is recorded in
+    pub fn file(&self, resource: &Resource) -> PathBuf {
+        self.root.join(resource.file())
+    }
+    /// Records a response so later fetches of the resource replay it
+    pub fn record<T: Serialize>(
+        &self,
+        resource: &Resource,
+        value: &T,
+    ) -> Result<(), ProviderError> {
+        let file = self.file(resource);
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(file, serde_json::to_string_pretty(value)?)?;
+        Ok(())
+    }
+}
+impl DataProvider for FixtureProvider {
+    fn name(&self) -> &str {
+        "fixtures"
+    }
+    fn fetch(&self, resource: &Resource) -> Result<String, ProviderError> {
+        fs::read_to_string(self.file(resource)).map_err(|error| match error.kind() {
+            io::ErrorKind::NotFound => ProviderError::NotFound(resource.path()),
+            _ => ProviderError::Io(error),
+        })
+    }
+}
+
+#[cfg(test)]
+mod fixture_provider_tests {
+    use super::*;
+    use crate::{
+        model::{
+            game::mock::game,
+            id::{GameId, LeagueId},
+        },
+        provider::BoxScore,
+    };
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_record_and_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = FixtureProvider::new(dir.path());
+        let league = LeagueId::new("nba");
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let games = vec![game("nba:1", "lal", "bos", Some((110, 102)))];
+        let schedule = Resource::Schedule {
+            league: &league,
+            date,
+        };
+        provider.record(&schedule, &games).unwrap();
+        provider
+            .record(
+                &Resource::BoxScore(&games[0].id),
+                &BoxScore {
+                    game: games[0].clone(),
+                    teams: vec![],
+                    players: vec![],
+                    stat_lines: vec![],
+                    events: vec![],
+                },
+            )
+            .unwrap();
+
+        assert!(dir
+            .path()
+            .join("leagues/nba/schedule/2025-01-01.json")
+            .is_file());
+        assert!(dir.path().join("games/nba_1/box-score.json").is_file());
+        assert_eq!(provider.schedule(&league, date).unwrap(), games);
+        assert_eq!(provider.box_score(&games[0].id).unwrap().game, games[0]);
+    }
+
+    #[test]
+    fn test_fetch_missing_or_invalid() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = FixtureProvider::new(dir.path());
+        let game = GameId::new("nba:1");
+        fs::create_dir_all(dir.path().join("games/nba_1")).unwrap();
+        fs::write(dir.path().join("games/nba_1/odds.json"), "{}").unwrap();
+
+        assert!(matches!(
+            provider.box_score(&game),
+            Err(ProviderError::NotFound(path)) if path == "games/nba:1/box-score"
+        ));
+        assert!(matches!(provider.odds(&game), Err(ProviderError::Json(_))));
+    }
+}
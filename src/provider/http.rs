@@ -0,0 +1,336 @@
+use super::{
+    cache::{CachedResponse, ResponseCache},
+    error::ProviderError,
+    rate_limit::RateLimiter,
+    DataProvider, Resource,
+};
+use std::{path::PathBuf, thread, time::Duration};
+
+/// Header the API key is sent in
+const API_KEY_HEADER: &str = "X-Api-Key";
+/// Longest a `Retry-After` header is allowed to pause a fetch
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+/// Longest the exponential backoff pauses a fetch between two attempts
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// A `DataProvider` fetching resources from a JSON API at `{base_url}/{resource path}`
+///
+/// Requests are spaced out by a per-host `RateLimiter`, rate limited (429) and server
+/// (5xx) errors are retried with exponential backoff, and with a cache every response is
+/// kept on disk and revalidated with `If-None-Match`/`If-Modified-Since`.
+///
+/// ## Examples
+///
+/// ```no_run
+/// # use statpack::{model::id::GameId, provider::{http::HttpProvider, DataProvider}};
+/// # fn main() -> Result<(), statpack::provider::error::ProviderError> {
+/// # let game = GameId::new("nba:1");
+/// let mut provider = HttpProvider::new("https://api.example.com/v1");
+/// provider
+///     .set_api_key("secret")
+///     .set_cache("/home/me/.cache/statpack/http")
+///     .set_rate_limit("api.example.com", 2.0);
+/// let odds = provider.odds(&game)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct HttpProvider {
+    base_url: String,
+    api_key: Option<String>,
+    agent: ureq::Agent,
+    limiter: RateLimiter,
+    cache: Option<ResponseCache>,
+    retries: u32,
+    backoff: Duration,
+}
+impl HttpProvider {
+    pub fn new(base_url: &str) -> Self {
+        let mut limiter = RateLimiter::new();
+        limiter.set_default(5.0);
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: None,
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+            limiter,
+            cache: None,
+            retries: 3,
+            backoff: Duration::from_millis(500),
+        }
+    }
+    /// Specifies the key sent with every request in the `X-Api-Key` header
+    pub fn set_api_key(&mut self, api_key: &str) -> &mut Self {
+        self.api_key = Some(api_key.to_string());
+        self
+    }
+    /// Keeps responses in the directory, to revalidate them and to fall back on offline
+    pub fn set_cache(&mut self, dir: impl Into<PathBuf>) -> &mut Self {
+        self.cache = Some(ResponseCache::new(dir));
+        self
+    }
+    /// Specifies the requests per second allowed to a host (defaults to `5.0` for any host)
+    pub fn set_rate_limit(&mut self, host: &str, per_second: f64) -> &mut Self {
+        self.limiter.set_host(host, per_second);
+        self
+    }
+    /// Specifies how many times a failed request is retried (defaults to `3`)
+    pub fn set_retries(&mut self, retries: u32) -> &mut Self {
+        self.retries = retries;
+        self
+    }
+    /// Specifies the wait before the first retry, doubled on every further one up to a minute
+    /// (defaults to 500ms)
+    pub fn set_backoff(&mut self, backoff: Duration) -> &mut Self {
+        self.backoff = backoff;
+        self
+    }
+    /// Returns the URL a resource is fetched from
+    pub fn url(&self, resource: &Resource) -> String {
+        format!("{}/{}", self.base_url, resource.path())
+    }
+    /// Reads a successful response, keeping it in the cache
+    fn read(&self, resource: &Resource, response: ureq::Response) -> Result<String, ProviderError> {
+        let etag = response.header("ETag").map(str::to_string);
+        let last_modified = response.header("Last-Modified").map(str::to_string);
+        let body = response.into_string()?;
+
+        if let Some(cache) = &self.cache {
+            cache.put(
+                resource,
+                &CachedResponse {
+                    etag,
+                    last_modified,
+                    body: body.clone(),
+                },
+            )?;
+        }
+        Ok(body)
+    }
+    /// Returns the pause before retrying after the failed attempt, doubling every time
+    fn backoff(&self, attempt: u32) -> Duration {
+        2u32.checked_pow(attempt)
+            .and_then(|factor| self.backoff.checked_mul(factor))
+            .map_or(MAX_BACKOFF, |backoff| backoff.min(MAX_BACKOFF))
+    }
+}
+impl DataProvider for HttpProvider {
+    fn name(&self) -> &str {
+        &self.base_url
+    }
+    fn fetch(&self, resource: &Resource) -> Result<String, ProviderError> {
+        let url = self.url(resource);
+        let cached = self.cache.as_ref().and_then(|cache| cache.get(resource));
+        let mut attempt = 0;
+
+        loop {
+            self.limiter.wait(host(&url));
+            let mut request = self.agent.get(&url).set("Accept", "application/json");
+            if let Some(api_key) = &self.api_key {
+                request = request.set(API_KEY_HEADER, api_key);
+            }
+            if let Some(etag) = cached.as_ref().and_then(|cached| cached.etag.as_ref()) {
+                request = request.set("If-None-Match", etag);
+            }
+            if let Some(modified) = cached
+                .as_ref()
+                .and_then(|cached| cached.last_modified.as_ref())
+            {
+                request = request.set("If-Modified-Since", modified);
+            }
+
+            let retry_after = match request.call() {
+                Ok(response) if response.status() == 304 => {
+                    return cached
+                        .map(|cached| cached.body)
+                        .ok_or(ProviderError::Http { url, status: 304 });
+                }
+                Ok(response) => return self.read(resource, response),
+                Err(ureq::Error::Status(404, _)) => {
+                    return Err(ProviderError::NotFound(resource.path()))
+                }
+                Err(ureq::Error::Status(status, response))
+                    if (status == 429 || status >= 500) && attempt < self.retries =>
+                {
+                    response
+                        .header("Retry-After")
+                        .and_then(|seconds| seconds.parse().ok())
+                        .map(|seconds| Duration::from_secs(seconds).min(MAX_RETRY_AFTER))
+                }
+                Err(ureq::Error::Status(status, _)) => {
+                    return Err(ProviderError::Http { url, status })
+                }
+                Err(ureq::Error::Transport(_)) if attempt < self.retries => None,
+                // Offline-first, the last response is better than no data at all
+                Err(ureq::Error::Transport(error)) => {
+                    return cached
+                        .map(|cached| cached.body)
+                        .ok_or_else(|| ProviderError::Transport(error.to_string()));
+                }
+            };
+            thread::sleep(retry_after.unwrap_or_else(|| self.backoff(attempt)));
+            attempt += 1;
+        }
+    }
+}
+
+/// Returns the host (and port) of a URL
+fn host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split('/').next().unwrap_or(rest)
+}
+
+#[cfg(test)]
+mod http_provider_tests {
+    use super::*;
+    use crate::model::id::GameId;
+    use std::{
+        net::TcpListener,
+        sync::mpsc::{self, Receiver},
+    };
+    use tiny_http::{Header, Response, Server};
+
+    /// A response status, headers and body
+    type Scripted = (u16, &'static [(&'static str, &'static str)], &'static str);
+
+    /// Serves the responses in order and sends back the headers of every request
+    fn serve(responses: Vec<Scripted>) -> (String, Receiver<Vec<(String, String)>>) {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr());
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for (status, headers, body) in responses {
+                let request = server.recv().unwrap();
+                let received = request
+                    .headers()
+                    .iter()
+                    .map(|header| (header.field.to_string(), header.value.to_string()))
+                    .collect();
+                let mut response = Response::from_string(body).with_status_code(status);
+                for &(field, value) in headers {
+                    response.add_header(Header::from_bytes(field, value).unwrap());
+                }
+                request.respond(response).unwrap();
+                sender.send(received).unwrap();
+            }
+        });
+        (url, receiver)
+    }
+
+    fn header<'a>(headers: &'a [(String, String)], field: &str) -> Option<&'a str> {
+        headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(field))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn provider(url: &str) -> HttpProvider {
+        let mut provider = HttpProvider::new(url);
+        provider.set_backoff(Duration::from_millis(1));
+        provider
+    }
+
+    #[test]
+    fn test_backoff() {
+        let mut provider = HttpProvider::new("http://localhost");
+        assert_eq!(provider.backoff(0), Duration::from_millis(500));
+        assert_eq!(provider.backoff(3), Duration::from_secs(4));
+        assert_eq!(provider.backoff(40), MAX_BACKOFF);
+        provider.set_backoff(Duration::MAX);
+        assert_eq!(provider.backoff(1), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn test_fetch_retries() {
+        let (url, requests) = serve(vec![
+            (503, &[], "down"),
+            (429, &[("Retry-After", "0")], "slow down"),
+            (200, &[], "[]"),
+        ]);
+        let mut provider = provider(&url);
+        provider.set_api_key("secret");
+
+        assert_eq!(provider.odds(&GameId::new("nba:1")).unwrap(), []);
+        assert_eq!(requests.iter().count(), 3);
+    }
+
+    #[test]
+    fn test_fetch_gives_up() {
+        let (url, requests) = serve(vec![(500, &[], ""), (500, &[], ""), (401, &[], "")]);
+        let mut provider = provider(&url);
+        provider.set_retries(1);
+        let game = GameId::new("nba:1");
+
+        assert!(matches!(
+            provider.fetch(&Resource::Odds(&game)),
+            Err(ProviderError::Http { status: 500, .. })
+        ));
+        assert!(matches!(
+            provider.fetch(&Resource::Odds(&game)),
+            Err(ProviderError::Http { status: 401, .. })
+        ));
+        assert_eq!(requests.iter().count(), 3);
+    }
+
+    #[test]
+    fn test_fetch_conditional() {
+        let dir = tempfile::tempdir().unwrap();
+        let (url, requests) = serve(vec![
+            (200, &[("ETag", "\"v1\"")], "[]"),
+            (304, &[], ""),
+            (404, &[], ""),
+        ]);
+        let mut provider = provider(&url);
+        provider.set_api_key("secret").set_cache(dir.path());
+        let game = GameId::new("nba:1");
+
+        assert_eq!(provider.fetch(&Resource::Odds(&game)).unwrap(), "[]");
+        assert_eq!(provider.fetch(&Resource::Odds(&game)).unwrap(), "[]");
+        assert!(matches!(
+            provider.fetch(&Resource::BoxScore(&game)),
+            Err(ProviderError::NotFound(_))
+        ));
+
+        let first = requests.recv().unwrap();
+        let second = requests.recv().unwrap();
+        assert_eq!(header(&first, "X-Api-Key"), Some("secret"));
+        assert_eq!(header(&first, "If-None-Match"), None);
+        assert_eq!(header(&second, "If-None-Match"), Some("\"v1\""));
+    }
+
+    #[test]
+    fn test_fetch_offline_falls_back_to_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let mut provider = provider(&format!("http://127.0.0.1:{}", port));
+        provider.set_retries(0).set_cache(dir.path());
+        let game = GameId::new("nba:1");
+
+        assert!(matches!(
+            provider.fetch(&Resource::Odds(&game)),
+            Err(ProviderError::Transport(_))
+        ));
+        ResponseCache::new(dir.path())
+            .put(
+                &Resource::Odds(&game),
+                &CachedResponse {
+                    etag: None,
+                    last_modified: None,
+                    body: "[]".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(provider.fetch(&Resource::Odds(&game)).unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_host() {
+        assert_eq!(host("http://127.0.0.1:8080/games/1"), "127.0.0.1:8080");
+        assert_eq!(host("https://api.example.com"), "api.example.com");
+    }
+}
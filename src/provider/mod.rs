@@ -0,0 +1,177 @@
+use self::error::ProviderError;
+use crate::model::{
+    game::{Event, Game},
+    id::{GameId, LeagueId, PlayerId, SeasonId},
+    odds::OddsSnapshot,
+    stat::StatLine,
+    team::{Player, Team},
+};
+use chrono::NaiveDate;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::path::PathBuf;
+
+pub mod cache;
+pub mod error;
+pub mod fixture;
+pub mod http;
+pub mod rate_limit;
+
+/// Represents the data a `DataProvider` can be asked for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resource<'a> {
+    /// Games of a league on a day
+    Schedule {
+        league: &'a LeagueId,
+        date: NaiveDate,
+    },
+    BoxScore(&'a GameId),
+    /// Every stat line of a player in a season
+    PlayerStats {
+        player: &'a PlayerId,
+        season: &'a SeasonId,
+    },
+    /// Current prices of every book on a game
+    Odds(&'a GameId),
+}
+impl Resource<'_> {
+    /// Returns the path identifying the resource, relative to the root of a source
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use statpack::{model::id::GameId, provider::Resource};
+    /// assert_eq!(Resource::Odds(&GameId::new("nba:1")).path(), "games/nba:1/odds");
+    /// ```
+    pub fn path(&self) -> String {
+        self.path_with(ToString::to_string)
+    }
+    /// Returns the relative file a response to the resource is kept in on disk
+    ///
+    /// IDs come from sources and users, so each one is made a single safe file name: path
+    /// separators and `:` (not allowed on every platform) are stored as `_`, and names made
+    /// only of dots cannot point out of the directory.
+    pub fn file(&self) -> PathBuf {
+        PathBuf::from(format!("{}.json", self.path_with(file_name)))
+    }
+
+    fn path_with(&self, segment: impl Fn(&str) -> String) -> String {
+        match self {
+            Resource::Schedule { league, date } => {
+                format!("leagues/{}/schedule/{}", segment(league.as_str()), date)
+            }
+            Resource::BoxScore(game) => format!("games/{}/box-score", segment(game.as_str())),
+            Resource::PlayerStats { player, season } => format!(
+                "players/{}/stats/{}",
+                segment(player.as_str()),
+                segment(season.as_str())
+            ),
+            Resource::Odds(game) => format!("games/{}/odds", segment(game.as_str())),
+        }
+    }
+}
+
+/// Turns an ID into a name that stays a single component of a relative path
+fn file_name(id: &str) -> String {
+    let name: String = id
+        .chars()
+        .map(|char| match char {
+            '/' | '\\' | ':' | '\0' => '_',
+            char => char,
+        })
+        .collect();
+    if name.chars().all(|char| char == '.') {
+        "_".repeat(name.len().max(1))
+    } else {
+        name
+    }
+}
+
+/// Represents the full record of a played game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoxScore {
+    pub game: Game,
+    /// The two teams, so games can be stored along with what they refer to
+    #[serde(default)]
+    pub teams: Vec<Team>,
+    /// Every player with a stat line
+    #[serde(default)]
+    pub players: Vec<Player>,
+    #[serde(default)]
+    pub stat_lines: Vec<StatLine>,
+    #[serde(default)]
+    pub events: Vec<Event>,
+}
+
+/// The `DataProvider` trait is implemented by every source of sports data.
+///
+/// Sources only need to `fetch` the raw JSON of a resource, the typed methods decode it
+/// into the model and can be overridden by sources whose responses are shaped differently.
+pub trait DataProvider {
+    /// Returns the name the source is referred to by in messages
+    fn name(&self) -> &str;
+    /// Returns the raw JSON body of a resource
+    fn fetch(&self, resource: &Resource) -> Result<String, ProviderError>;
+
+    /// Returns the games of a league on a day
+    fn schedule(&self, league: &LeagueId, date: NaiveDate) -> Result<Vec<Game>, ProviderError> {
+        decode(self.fetch(&Resource::Schedule { league, date })?)
+    }
+    /// Returns the game along with its stat lines and events
+    fn box_score(&self, game: &GameId) -> Result<BoxScore, ProviderError> {
+        decode(self.fetch(&Resource::BoxScore(game))?)
+    }
+    /// Returns the stat lines of a player in a season
+    fn player_stats(
+        &self,
+        player: &PlayerId,
+        season: &SeasonId,
+    ) -> Result<Vec<StatLine>, ProviderError> {
+        decode(self.fetch(&Resource::PlayerStats { player, season })?)
+    }
+    /// Returns the current prices of every book on a game
+    fn odds(&self, game: &GameId) -> Result<Vec<OddsSnapshot>, ProviderError> {
+        decode(self.fetch(&Resource::Odds(game))?)
+    }
+}
+
+fn decode<T: DeserializeOwned>(body: String) -> Result<T, ProviderError> {
+    Ok(serde_json::from_str(&body)?)
+}
+
+#[cfg(test)]
+mod resource_tests {
+    use super::*;
+
+    #[test]
+    fn test_file() {
+        let game = GameId::new("nba:1");
+        assert_eq!(
+            Resource::Odds(&game).file(),
+            PathBuf::from("games/nba_1/odds.json")
+        );
+
+        for id in [
+            "..",
+            "../../etc/passwd",
+            "/etc/passwd",
+            "..\\..\\boot",
+            "c:\\x",
+            "",
+        ] {
+            let game = GameId::new(id);
+            let file = Resource::BoxScore(&game).file();
+            assert!(file.is_relative(), "{:?}", file);
+            assert_eq!(file.components().count(), 3, "{:?}", file);
+            assert!(
+                file.components()
+                    .all(|component| matches!(component, std::path::Component::Normal(_))),
+                "{:?}",
+                file
+            );
+        }
+        assert_eq!(
+            Resource::BoxScore(&GameId::new("../x")).file(),
+            PathBuf::from("games/.._x/box-score.json")
+        );
+    }
+}
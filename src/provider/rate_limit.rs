@@ -0,0 +1,90 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Spaces out requests to each host so feeds with request quotas are not exceeded
+///
+/// ## Examples
+///
+/// ```
+/// # use statpack::provider::rate_limit::RateLimiter;
+/// let mut limiter = RateLimiter::new();
+/// limiter.set_default(5.0).set_host("api.example.com", 1.0);
+/// limiter.wait("api.example.com");
+/// ```
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    default: Option<Duration>,
+    hosts: HashMap<String, Duration>,
+    last: Mutex<HashMap<String, Instant>>,
+}
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Specifies the requests per second allowed to hosts without a limit of their own
+    pub fn set_default(&mut self, per_second: f64) -> &mut Self {
+        self.default = Some(interval(per_second));
+        self
+    }
+    /// Specifies the requests per second allowed to a host
+    pub fn set_host(&mut self, host: &str, per_second: f64) -> &mut Self {
+        self.hosts.insert(host.to_string(), interval(per_second));
+        self
+    }
+    /// Blocks until a request to the host is allowed and books it
+    ///
+    /// ## Returns
+    ///
+    /// How long the call waited.
+    pub fn wait(&self, host: &str) -> Duration {
+        let Some(interval) = self.hosts.get(host).copied().or(self.default) else {
+            return Duration::ZERO;
+        };
+        // The lock is held while sleeping so concurrent callers queue up behind each other
+        let mut last = self.last.lock().unwrap_or_else(|error| error.into_inner());
+        let waited = last.get(host).map_or(Duration::ZERO, |previous| {
+            interval.saturating_sub(previous.elapsed())
+        });
+        if !waited.is_zero() {
+            thread::sleep(waited);
+        }
+        last.insert(host.to_string(), Instant::now());
+        waited
+    }
+}
+
+fn interval(per_second: f64) -> Duration {
+    assert!(per_second > 0.0, "rate limit must be positive");
+    Duration::from_secs_f64(1.0 / per_second)
+}
+
+#[cfg(test)]
+mod rate_limiter_tests {
+    use super::*;
+
+    #[test]
+    fn test_wait_per_host() {
+        let mut limiter = RateLimiter::new();
+        limiter.set_host("slow", 10.0);
+
+        assert_eq!(limiter.wait("slow"), Duration::ZERO);
+        assert!(limiter.wait("slow") > Duration::from_millis(50));
+        assert_eq!(limiter.wait("fast"), Duration::ZERO);
+        assert_eq!(limiter.wait("fast"), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_wait_default() {
+        let mut limiter = RateLimiter::new();
+        limiter.set_default(10.0);
+        limiter.wait("any");
+        let start = Instant::now();
+        limiter.wait("any");
+
+        assert!(start.elapsed() > Duration::from_millis(50));
+    }
+}
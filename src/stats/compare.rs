@@ -0,0 +1,224 @@
+use super::player::{Aggregate, GameLine};
+use crate::model::stat::keys::LOWER_IS_BETTER;
+use chrono::{DateTime, Utc};
+use std::{fmt, ops::RangeInclusive};
+
+/// Represents what stats are divided by before players are compared
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Basis {
+    /// Per-game averages
+    Game,
+    /// Per `n` minutes played (per-36, per-90)
+    Minutes(f64),
+    /// Per `n` possessions (per-100)
+    Possessions(f64),
+}
+impl Basis {
+    /// Returns a stat of the aggregate normalized to the basis
+    pub fn value(&self, aggregate: &Aggregate, key: &str) -> Option<f64> {
+        match self {
+            Basis::Game => aggregate.average(key),
+            Basis::Minutes(minutes) => aggregate.per_minutes(key, *minutes),
+            Basis::Possessions(possessions) => aggregate.per_possessions(key, *possessions),
+        }
+    }
+}
+impl fmt::Display for Basis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Basis::Game => write!(f, "per game"),
+            Basis::Minutes(minutes) => write!(f, "per {} minutes", minutes),
+            Basis::Possessions(possessions) => write!(f, "per {} possessions", possessions),
+        }
+    }
+}
+
+/// Returns the time range every player has games in, from the latest first game to the
+/// earliest last game, so no player is measured over games the others did not play in
+pub fn shared_window(players: &[Vec<GameLine>]) -> Option<RangeInclusive<DateTime<Utc>>> {
+    let mut start = None;
+    let mut end = None;
+    for lines in players {
+        let first = lines.iter().map(|line| line.game.start).min()?;
+        let last = lines.iter().map(|line| line.game.start).max()?;
+        start = start.max(Some(first));
+        end = Some(end.map_or(last, |end: DateTime<Utc>| end.min(last)));
+    }
+    let (start, end) = (start?, end?);
+    (start <= end).then_some(start..=end)
+}
+
+/// Returns the percentage of the population the value is better than or equal to
+///
+/// ## Examples
+///
+/// ```
+/// # use statpack::stats::compare::percentile;
+/// assert_eq!(percentile(20.0, &[10.0, 20.0, 30.0, 40.0], false), Some(50.0));
+/// ```
+pub fn percentile(value: f64, population: &[f64], lower_is_better: bool) -> Option<f64> {
+    if population.is_empty() {
+        return None;
+    }
+    let beaten = population
+        .iter()
+        .filter(|&&other| {
+            if lower_is_better {
+                value <= other
+            } else {
+                value >= other
+            }
+        })
+        .count();
+    Some(beaten as f64 / population.len() as f64 * 100.0)
+}
+
+/// Represents one stat of the compared players
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatComparison {
+    pub key: String,
+    /// Normalized values, aligned with the compared players
+    pub values: Vec<Option<f64>>,
+    /// Percentile ranks within each player's population, aligned with the compared players
+    pub percentiles: Vec<Option<f64>>,
+    /// Position of the player with the best value, `None` when tied or missing
+    pub leader: Option<usize>,
+}
+
+/// Compares players stat by stat.
+///
+/// `populations` holds, for every compared player, the aggregates of the players they are
+/// ranked against (usually everyone at the same position over the same window).
+pub fn compare(
+    players: &[Aggregate],
+    populations: &[Vec<Aggregate>],
+    keys: &[String],
+    basis: Basis,
+) -> Vec<StatComparison> {
+    keys.iter()
+        .map(|key| {
+            let lower_is_better = LOWER_IS_BETTER.contains(&key.as_str());
+            let values: Vec<Option<f64>> = players
+                .iter()
+                .map(|aggregate| basis.value(aggregate, key))
+                .collect();
+            let percentiles = values
+                .iter()
+                .zip(populations)
+                .map(|(value, population)| {
+                    let population: Vec<f64> = population
+                        .iter()
+                        .filter_map(|aggregate| basis.value(aggregate, key))
+                        .collect();
+                    percentile((*value)?, &population, lower_is_better)
+                })
+                .collect();
+            StatComparison {
+                key: key.clone(),
+                leader: leader(&values, lower_is_better),
+                values,
+                percentiles,
+            }
+        })
+        .collect()
+}
+
+/// Returns the position of the single best value
+fn leader(values: &[Option<f64>], lower_is_better: bool) -> Option<usize> {
+    let best = values.iter().flatten().copied().reduce(|a, b| {
+        if lower_is_better {
+            a.min(b)
+        } else {
+            a.max(b)
+        }
+    })?;
+    let mut leaders = values
+        .iter()
+        .enumerate()
+        .filter(|(_, value)| **value == Some(best));
+    let (position, _) = leaders.next()?;
+    leaders.next().is_none().then_some(position)
+}
+
+#[cfg(test)]
+mod compare_tests {
+    use super::*;
+    use crate::stats::player::mock::game_line;
+
+    fn aggregate(lines: &[GameLine]) -> Aggregate {
+        Aggregate::new(lines.iter().map(|line| &line.line))
+    }
+
+    #[test]
+    fn test_compare() {
+        let a = aggregate(&[game_line(
+            "1",
+            "lal",
+            "bos",
+            Some(36.0),
+            &[("pts", 30.0), ("tov", 5.0)],
+        )]);
+        let b = aggregate(&[game_line(
+            "2",
+            "lal",
+            "bos",
+            Some(18.0),
+            &[("pts", 20.0), ("tov", 2.0)],
+        )]);
+        let population = vec![a.clone(), b.clone()];
+        let keys = ["pts".to_string(), "tov".to_string(), "ast".to_string()];
+
+        let per_game = compare(
+            &[a.clone(), b.clone()],
+            &[population.clone(), population.clone()],
+            &keys,
+            Basis::Game,
+        );
+        assert_eq!(per_game[0].values, [Some(30.0), Some(20.0)]);
+        assert_eq!(per_game[0].leader, Some(0));
+        assert_eq!(per_game[0].percentiles, [Some(100.0), Some(50.0)]);
+        assert_eq!(per_game[1].leader, Some(1));
+        assert_eq!(per_game[2].leader, None);
+
+        let per_36 = compare(
+            &[a, b],
+            &[population.clone(), population],
+            &keys,
+            Basis::Minutes(36.0),
+        );
+        assert_eq!(per_36[0].values, [Some(30.0), Some(40.0)]);
+        assert_eq!(per_36[0].leader, Some(1));
+    }
+
+    #[test]
+    fn test_shared_window() {
+        let mut early = game_line("1", "lal", "bos", None, &[]);
+        early.game.start -= chrono::Duration::days(10);
+        let middle = game_line("2", "lal", "bos", None, &[]);
+        let mut late = game_line("3", "lal", "bos", None, &[]);
+        late.game.start += chrono::Duration::days(10);
+
+        assert_eq!(
+            shared_window(&[
+                vec![early.clone(), middle.clone()],
+                vec![middle.clone(), late.clone()]
+            ]),
+            Some(middle.game.start..=middle.game.start)
+        );
+        assert_eq!(shared_window(&[vec![early], vec![late]]), None);
+        assert_eq!(shared_window(&[vec![middle], vec![]]), None);
+    }
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(
+            percentile(20.0, &[10.0, 20.0, 30.0, 40.0], false),
+            Some(50.0)
+        );
+        assert_eq!(
+            percentile(20.0, &[10.0, 20.0, 30.0, 40.0], true),
+            Some(75.0)
+        );
+        assert_eq!(percentile(20.0, &[], false), None);
+    }
+}
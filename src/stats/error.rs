@@ -0,0 +1,72 @@
+use crate::store::error::StoreError;
+use std::{error::Error, fmt};
+
+/// Represents the errors raised while querying stats
+#[derive(Debug)]
+pub enum StatsError {
+    Store(StoreError),
+    /// Nothing is named like the query, listing close names
+    NotFound {
+        kind: &'static str,
+        query: String,
+        suggestions: Vec<String>,
+    },
+    /// Several records are named like the query
+    Ambiguous {
+        kind: &'static str,
+        query: String,
+        candidates: Vec<String>,
+    },
+    /// A query (like a trend situation) does not parse
+    InvalidQuery {
+        query: String,
+        message: String,
+    },
+}
+impl fmt::Display for StatsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatsError::Store(error) => write!(f, "{}", error),
+            StatsError::NotFound {
+                kind,
+                query,
+                suggestions,
+            } => {
+                write!(f, "no {} named '{}'", kind, query)?;
+                if !suggestions.is_empty() {
+                    write!(f, ", did you mean '{}'?", suggestions.join("', '"))?;
+                }
+                Ok(())
+            }
+            StatsError::Ambiguous {
+                kind,
+                query,
+                candidates,
+            } => write!(
+                f,
+                "'{}' matches several {}s: {}",
+                query,
+                kind,
+                candidates.join(", ")
+            ),
+            StatsError::InvalidQuery { query, message } => {
+                write!(f, "invalid query '{}': {}", query, message)
+            }
+        }
+    }
+}
+impl Error for StatsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StatsError::Store(error) => Some(error),
+            StatsError::NotFound { .. }
+            | StatsError::Ambiguous { .. }
+            | StatsError::InvalidQuery { .. } => None,
+        }
+    }
+}
+impl From<StoreError> for StatsError {
+    fn from(error: StoreError) -> Self {
+        StatsError::Store(error)
+    }
+}
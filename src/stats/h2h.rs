@@ -0,0 +1,273 @@
+use crate::model::{
+    game::{Game, Outcome},
+    id::TeamId,
+    odds::{Market, OddsSnapshot, Selection},
+};
+use std::{collections::HashMap, fmt};
+
+/// Returns the final games the two teams played against each other, in the given order
+pub fn meetings<'g>(
+    games: impl IntoIterator<Item = &'g Game>,
+    team: &TeamId,
+    opponent: &TeamId,
+) -> Vec<&'g Game> {
+    games
+        .into_iter()
+        .filter(|game| game.opponent_of(team) == Some(opponent))
+        .filter(|game| game.home_score.is_some() && game.away_score.is_some())
+        .collect()
+}
+
+/// Returns the line a market closed at, the median over every book's last line before
+/// the game started
+pub fn closing_line(
+    game: &Game,
+    odds: &[OddsSnapshot],
+    market: &Market,
+    selection: Selection,
+) -> Option<f64> {
+    let mut latest: HashMap<&str, &OddsSnapshot> = HashMap::new();
+    for snapshot in odds.iter().filter(|snapshot| {
+        snapshot.game == game.id
+            && snapshot.market == *market
+            && snapshot.selection == selection
+            && snapshot.line.is_some()
+            && snapshot.taken_at <= game.start
+    }) {
+        latest
+            .entry(&snapshot.book)
+            .and_modify(|current| {
+                if snapshot.taken_at >= current.taken_at {
+                    *current = snapshot;
+                }
+            })
+            .or_insert(snapshot);
+    }
+    let mut lines: Vec<f64> = latest
+        .values()
+        .filter_map(|snapshot| snapshot.line)
+        .collect();
+    if lines.is_empty() {
+        return None;
+    }
+    lines.sort_by(f64::total_cmp);
+    let middle = lines.len() / 2;
+    Some(if lines.len().is_multiple_of(2) {
+        (lines[middle - 1] + lines[middle]) / 2.0
+    } else {
+        lines[middle]
+    })
+}
+
+/// Returns whether the team covered the spread, `Draw` for a push
+///
+/// `home_spread` is the handicap of the home team (`-3.5` when it gives 3.5 points).
+pub fn against_the_spread(game: &Game, team: &TeamId, home_spread: f64) -> Option<Outcome> {
+    let (scored, conceded) = game.score_for(team)?;
+    let spread = if *team == game.home {
+        home_spread
+    } else {
+        -home_spread
+    };
+    let margin = f64::from(scored) - f64::from(conceded) + spread;
+    Some(if margin > 0.0 {
+        Outcome::Win
+    } else if margin < 0.0 {
+        Outcome::Loss
+    } else {
+        Outcome::Draw
+    })
+}
+
+/// Returns the side of the total the combined score landed on, `None` for a push
+pub fn over_under(game: &Game, total: f64) -> Option<Selection> {
+    let combined = f64::from(game.home_score? + game.away_score?);
+    if combined > total {
+        Some(Selection::Over)
+    } else if combined < total {
+        Some(Selection::Under)
+    } else {
+        None
+    }
+}
+
+/// Represents graded bets, as wins (or overs), losses (or unders) and pushes
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BetRecord {
+    pub wins: u32,
+    pub losses: u32,
+    pub pushes: u32,
+}
+impl BetRecord {
+    pub fn graded(&self) -> u32 {
+        self.wins + self.losses + self.pushes
+    }
+}
+
+impl fmt::Display for BetRecord {
+    /// Formats the record as `wins-losses-pushes`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}-{}", self.wins, self.losses, self.pushes)
+    }
+}
+
+/// Grades the team against the closing spread of every game that has one
+pub fn ats_record<'g>(
+    games: impl IntoIterator<Item = &'g Game>,
+    team: &TeamId,
+    odds: &[OddsSnapshot],
+) -> BetRecord {
+    let mut record = BetRecord::default();
+    for game in games {
+        let Some(outcome) = closing_line(game, odds, &Market::Spread, Selection::Home)
+            .and_then(|spread| against_the_spread(game, team, spread))
+        else {
+            continue;
+        };
+        match outcome {
+            Outcome::Win => record.wins += 1,
+            Outcome::Loss => record.losses += 1,
+            Outcome::Draw => record.pushes += 1,
+        }
+    }
+    record
+}
+
+/// Grades the closing total of every game that has one, overs counting as wins
+pub fn totals_record<'g>(
+    games: impl IntoIterator<Item = &'g Game>,
+    odds: &[OddsSnapshot],
+) -> BetRecord {
+    let mut record = BetRecord::default();
+    for game in games {
+        let Some(total) = closing_line(game, odds, &Market::Total, Selection::Over) else {
+            continue;
+        };
+        match over_under(game, total) {
+            Some(Selection::Over) => record.wins += 1,
+            Some(_) => record.losses += 1,
+            None => record.pushes += 1,
+        }
+    }
+    record
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::*;
+
+    /// A snapshot of a spread or total line, taken `hours` before the game started
+    pub(crate) fn line(
+        game: &Game,
+        book: &str,
+        market: Market,
+        line: f64,
+        hours: i64,
+    ) -> OddsSnapshot {
+        OddsSnapshot {
+            game: game.id.clone(),
+            book: book.to_string(),
+            selection: match market {
+                Market::Total => Selection::Over,
+                _ => Selection::Home,
+            },
+            market,
+            line: Some(line),
+            price: 1.91,
+            taken_at: game.start - chrono::Duration::hours(hours),
+        }
+    }
+}
+
+#[cfg(test)]
+mod h2h_tests {
+    use super::{mock::line, *};
+    use crate::model::game::mock::game;
+
+    #[test]
+    fn test_meetings() {
+        let games = [
+            game("1", "lal", "bos", Some((110, 100))),
+            game("2", "bos", "lal", Some((101, 99))),
+            game("3", "lal", "den", Some((99, 98))),
+            game("4", "lal", "bos", None),
+        ];
+        let ids: Vec<&str> = meetings(&games, &TeamId::new("lal"), &TeamId::new("bos"))
+            .iter()
+            .map(|game| game.id.as_str())
+            .collect();
+
+        assert_eq!(ids, ["1", "2"]);
+    }
+
+    #[test]
+    fn test_closing_line() {
+        let game = game("1", "lal", "bos", Some((110, 100)));
+        let odds = [
+            line(&game, "pinnacle", Market::Spread, -3.0, 24),
+            line(&game, "pinnacle", Market::Spread, -5.0, 1),
+            line(&game, "pinnacle", Market::Spread, -9.0, -1),
+            line(&game, "draftkings", Market::Spread, -6.0, 2),
+            line(&game, "fanduel", Market::Spread, -4.5, 2),
+            line(&game, "fanduel", Market::Total, 220.5, 2),
+        ];
+
+        assert_eq!(
+            closing_line(&game, &odds, &Market::Spread, Selection::Home),
+            Some(-5.0)
+        );
+        assert_eq!(
+            closing_line(&game, &odds[..2], &Market::Spread, Selection::Home),
+            Some(-5.0)
+        );
+        assert_eq!(
+            closing_line(&game, &odds[3..5], &Market::Spread, Selection::Home),
+            Some(-5.25)
+        );
+        assert_eq!(
+            closing_line(&game, &odds, &Market::Moneyline, Selection::Home),
+            None
+        );
+    }
+
+    #[test]
+    fn test_against_the_spread() {
+        let game = game("1", "lal", "bos", Some((110, 100)));
+        let lal = TeamId::new("lal");
+        let bos = TeamId::new("bos");
+
+        assert_eq!(against_the_spread(&game, &lal, -7.5), Some(Outcome::Win));
+        assert_eq!(against_the_spread(&game, &bos, -7.5), Some(Outcome::Loss));
+        assert_eq!(against_the_spread(&game, &lal, -10.0), Some(Outcome::Draw));
+        assert_eq!(against_the_spread(&game, &bos, -12.5), Some(Outcome::Win));
+        assert_eq!(over_under(&game, 209.5), Some(Selection::Over));
+        assert_eq!(over_under(&game, 210.0), None);
+    }
+
+    #[test]
+    fn test_records() {
+        let games = [
+            game("1", "lal", "bos", Some((110, 100))),
+            game("2", "bos", "lal", Some((101, 99))),
+            game("3", "lal", "bos", Some((120, 90))),
+        ];
+        let odds = [
+            line(&games[0], "pinnacle", Market::Spread, -10.0, 1),
+            line(&games[1], "pinnacle", Market::Spread, -1.5, 1),
+            line(&games[0], "pinnacle", Market::Total, 215.5, 1),
+            line(&games[1], "pinnacle", Market::Total, 195.5, 1),
+        ];
+        let ats = ats_record(&games, &TeamId::new("lal"), &odds);
+
+        assert_eq!(
+            ats,
+            BetRecord {
+                wins: 0,
+                losses: 1,
+                pushes: 1
+            }
+        );
+        assert_eq!(ats.graded(), 2);
+        assert_eq!(totals_record(&games, &odds).to_string(), "1-1-0");
+    }
+}
@@ -0,0 +1,124 @@
+use crate::model::odds::{Market, OddsSnapshot, Selection};
+
+/// Represents the prices one book offered on one selection over time
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineHistory {
+    pub book: String,
+    pub market: Market,
+    pub selection: Selection,
+    /// Every snapshot, oldest first
+    pub snapshots: Vec<OddsSnapshot>,
+}
+impl LineHistory {
+    /// Returns the first snapshot taken
+    pub fn opening(&self) -> &OddsSnapshot {
+        &self.snapshots[0]
+    }
+    /// Returns the latest snapshot taken
+    pub fn current(&self) -> &OddsSnapshot {
+        &self.snapshots[self.snapshots.len() - 1]
+    }
+    /// Returns how far the line moved since opening, `None` for markets without a line
+    pub fn line_move(&self) -> Option<f64> {
+        Some(self.current().line? - self.opening().line?)
+    }
+    /// Returns how far the decimal price moved since opening
+    pub fn price_move(&self) -> f64 {
+        self.current().price - self.opening().price
+    }
+    /// Returns the opening snapshot and every later one changing the line or price
+    pub fn moves(&self) -> Vec<&OddsSnapshot> {
+        let mut moves: Vec<&OddsSnapshot> = vec![];
+        for snapshot in &self.snapshots {
+            if moves
+                .last()
+                .is_none_or(|last| last.line != snapshot.line || last.price != snapshot.price)
+            {
+                moves.push(snapshot);
+            }
+        }
+        moves
+    }
+}
+
+/// Groups snapshots by book, market and selection
+///
+/// ## Returns
+///
+/// One history per group, ordered by market, selection and book.
+pub fn line_histories(odds: &[OddsSnapshot]) -> Vec<LineHistory> {
+    let mut histories: Vec<LineHistory> = vec![];
+    for snapshot in odds {
+        match histories.iter_mut().find(|history| {
+            history.book == snapshot.book
+                && history.market == snapshot.market
+                && history.selection == snapshot.selection
+        }) {
+            Some(history) => history.snapshots.push(snapshot.clone()),
+            None => histories.push(LineHistory {
+                book: snapshot.book.clone(),
+                market: snapshot.market.clone(),
+                selection: snapshot.selection,
+                snapshots: vec![snapshot.clone()],
+            }),
+        }
+    }
+    for history in &mut histories {
+        history.snapshots.sort_by_key(|snapshot| snapshot.taken_at);
+    }
+    histories.sort_by(|a, b| {
+        (a.market.to_string(), a.selection.name(), &a.book).cmp(&(
+            b.market.to_string(),
+            b.selection.name(),
+            &b.book,
+        ))
+    });
+    histories
+}
+
+#[cfg(test)]
+mod line_history_tests {
+    use super::*;
+    use crate::model::game::mock::game;
+    use chrono::Duration;
+
+    fn snapshot(book: &str, line: f64, price: f64, hours: i64) -> OddsSnapshot {
+        let game = game("1", "lal", "bos", None);
+        OddsSnapshot {
+            game: game.id,
+            book: book.to_string(),
+            market: Market::Spread,
+            selection: Selection::Home,
+            line: Some(line),
+            price,
+            taken_at: game.start - Duration::hours(hours),
+        }
+    }
+
+    #[test]
+    fn test_line_histories() {
+        let odds = [
+            snapshot("pinnacle", -3.5, 1.95, 2),
+            snapshot("draftkings", -3.0, 1.91, 48),
+            snapshot("pinnacle", -3.0, 1.91, 48),
+            snapshot("pinnacle", -3.0, 1.91, 24),
+            snapshot("pinnacle", -4.0, 1.87, 1),
+        ];
+        let histories = line_histories(&odds);
+
+        assert_eq!(
+            histories
+                .iter()
+                .map(|history| history.book.as_str())
+                .collect::<Vec<_>>(),
+            ["draftkings", "pinnacle"]
+        );
+        let pinnacle = &histories[1];
+        assert_eq!(pinnacle.opening(), &odds[2]);
+        assert_eq!(pinnacle.current(), &odds[4]);
+        assert_eq!(pinnacle.line_move(), Some(-1.0));
+        assert!((pinnacle.price_move() + 0.04).abs() < 1e-9);
+        assert_eq!(pinnacle.moves(), [&odds[2], &odds[0], &odds[4]]);
+        assert_eq!(histories[0].moves().len(), 1);
+    }
+}
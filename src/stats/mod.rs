@@ -0,0 +1,48 @@
+use crate::model::{
+    game::Game,
+    id::{slug, SeasonId, TeamId},
+    sport::Sport,
+};
+
+pub mod compare;
+pub mod error;
+pub mod h2h;
+pub mod lines;
+pub mod player;
+pub mod resolve;
+pub mod team;
+pub mod trends;
+
+/// Represents the side of a game a team played on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HomeAway {
+    Home,
+    Away,
+}
+impl HomeAway {
+    /// Returns the side the team played on, if it played the game
+    pub fn of(game: &Game, team: &TeamId) -> Option<HomeAway> {
+        if *team == game.home {
+            Some(HomeAway::Home)
+        } else if *team == game.away {
+            Some(HomeAway::Away)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the minutes per-minute stats are normalized to for a sport (per-36, per-90...)
+pub fn per_minutes(sport: Sport) -> Option<f64> {
+    match sport {
+        Sport::Basketball => Some(36.0),
+        Sport::Soccer => Some(90.0),
+        Sport::Hockey => Some(60.0),
+        _ => None,
+    }
+}
+
+/// Indicates if a season ID is the season given by ID ("nba:2024-25") or name ("2024-25")
+pub fn is_season(season: &SeasonId, wanted: &str) -> bool {
+    season.as_str() == wanted || season.as_str().ends_with(&format!(":{}", slug(wanted)))
+}
@@ -0,0 +1,259 @@
+use super::{is_season, HomeAway};
+use crate::{
+    model::{
+        game::Game,
+        id::{PlayerId, TeamId},
+        stat::{keys, StatLine},
+    },
+    store::{error::StoreError, Store},
+};
+use std::collections::BTreeMap;
+
+/// Represents a player's stat line along with the game it was recorded in
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameLine {
+    pub game: Game,
+    pub line: StatLine,
+}
+impl GameLine {
+    /// Returns the team the player faced
+    pub fn opponent(&self) -> Option<&TeamId> {
+        self.game.opponent_of(&self.line.team)
+    }
+    /// Returns the side of the game the player's team was on
+    pub fn side(&self) -> Option<HomeAway> {
+        HomeAway::of(&self.game, &self.line.team)
+    }
+}
+
+/// Returns every stat line of a player with its game, oldest first
+pub fn game_lines(store: &Store, player: &PlayerId) -> Result<Vec<GameLine>, StoreError> {
+    let games = store.repo::<Game>();
+    let mut lines = vec![];
+    for line in store.repo::<StatLine>().for_player(player.as_str())? {
+        if let Some(game) = games.get(line.game.as_str())? {
+            lines.push(GameLine { game, line });
+        }
+    }
+    Ok(lines)
+}
+
+/// Narrows down the games of a player's stat lines
+///
+/// ## Examples
+///
+/// ```
+/// # use statpack::stats::{player::PlayerFilter, HomeAway};
+/// # let lines = vec![];
+/// let recent = PlayerFilter::new()
+///     .set_season("2024-25")
+///     .set_side(HomeAway::Away)
+///     .set_last(10)
+///     .apply(lines);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct PlayerFilter {
+    season: Option<String>,
+    last: Option<usize>,
+    opponent: Option<TeamId>,
+    side: Option<HomeAway>,
+}
+impl PlayerFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Keeps games of the season, given by ID ("nba:2024-25") or name ("2024-25")
+    pub fn set_season(&mut self, season: &str) -> &mut Self {
+        self.season = Some(season.to_string());
+        self
+    }
+    /// Keeps the most recent games, applied after every other filter
+    pub fn set_last(&mut self, last: usize) -> &mut Self {
+        self.last = Some(last);
+        self
+    }
+    /// Keeps games against the team
+    pub fn set_opponent(&mut self, opponent: TeamId) -> &mut Self {
+        self.opponent = Some(opponent);
+        self
+    }
+    /// Keeps games the player's team played at home or away
+    pub fn set_side(&mut self, side: HomeAway) -> &mut Self {
+        self.side = Some(side);
+        self
+    }
+    /// Indicates if a line passes every filter but `last`
+    pub fn matches(&self, line: &GameLine) -> bool {
+        self.season
+            .as_ref()
+            .is_none_or(|wanted| is_season(&line.game.season, wanted))
+            && self
+                .opponent
+                .as_ref()
+                .is_none_or(|opponent| line.opponent() == Some(opponent))
+            && self.side.is_none_or(|side| line.side() == Some(side))
+    }
+    /// Returns the lines passing the filters, oldest first
+    pub fn apply(&self, mut lines: Vec<GameLine>) -> Vec<GameLine> {
+        lines.retain(|line| self.matches(line));
+        if let Some(last) = self.last {
+            lines.drain(..lines.len().saturating_sub(last));
+        }
+        lines
+    }
+}
+
+/// Represents stats summed over several games
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Aggregate {
+    pub games: usize,
+    /// Minutes over the games that recorded them
+    pub minutes: f64,
+    /// Number of games that recorded minutes
+    pub timed_games: usize,
+    pub totals: BTreeMap<String, f64>,
+    /// Stat totals over the games that recorded minutes, the base of per-minute numbers
+    timed_totals: BTreeMap<String, f64>,
+}
+impl Aggregate {
+    /// Sums the stats of the lines
+    pub fn new<'a>(lines: impl IntoIterator<Item = &'a StatLine>) -> Self {
+        let mut aggregate = Self::default();
+        for line in lines {
+            aggregate.games += 1;
+            for (key, value) in &line.stats {
+                *aggregate.totals.entry(key.clone()).or_default() += value;
+            }
+            if let Some(minutes) = line.minutes {
+                aggregate.minutes += minutes;
+                aggregate.timed_games += 1;
+                for (key, value) in &line.stats {
+                    *aggregate.timed_totals.entry(key.clone()).or_default() += value;
+                }
+            }
+        }
+        aggregate
+    }
+    /// Returns the total of a stat, missing stats count as `0`
+    pub fn total(&self, key: &str) -> f64 {
+        self.totals.get(key).copied().unwrap_or(0.0)
+    }
+    /// Returns the per-game average of a stat
+    pub fn average(&self, key: &str) -> Option<f64> {
+        (self.games > 0).then(|| self.total(key) / self.games as f64)
+    }
+    /// Returns a stat normalized to `minutes` played (e.g. `36.0` for per-36 numbers),
+    /// counting only games that recorded minutes
+    pub fn per_minutes(&self, key: &str, minutes: f64) -> Option<f64> {
+        (self.minutes > 0.0)
+            .then(|| self.timed_totals.get(key).copied().unwrap_or(0.0) / self.minutes * minutes)
+    }
+    /// Returns a stat normalized to `possessions` (e.g. `100.0` for per-100 numbers),
+    /// `None` unless the lines recorded possessions
+    pub fn per_possessions(&self, key: &str, possessions: f64) -> Option<f64> {
+        let total = self.total(keys::basketball::POSSESSIONS);
+        (total > 0.0).then(|| self.total(key) / total * possessions)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::*;
+    use crate::model::game::mock::game;
+
+    /// A stat line of "lebron" for "lal" in a final game
+    pub(crate) fn game_line(
+        id: &str,
+        home: &str,
+        away: &str,
+        minutes: Option<f64>,
+        stats: &[(&str, f64)],
+    ) -> GameLine {
+        let game = game(id, home, away, Some((100, 90)));
+        let mut line = StatLine::new(game.id.clone(), PlayerId::new("lebron"), TeamId::new("lal"));
+        line.minutes = minutes;
+        for (key, value) in stats {
+            line.set(key, *value);
+        }
+        GameLine { game, line }
+    }
+}
+
+#[cfg(test)]
+mod player_filter_tests {
+    use super::{mock::game_line, *};
+
+    fn lines() -> Vec<GameLine> {
+        vec![
+            game_line("1", "lal", "bos", None, &[]),
+            game_line("2", "den", "lal", None, &[]),
+            game_line("3", "lal", "den", None, &[]),
+            game_line("4", "lal", "bos", None, &[]),
+        ]
+    }
+
+    fn ids(lines: Vec<GameLine>) -> Vec<String> {
+        lines.iter().map(|line| line.game.id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_apply() {
+        assert_eq!(
+            ids(PlayerFilter::new()
+                .set_side(HomeAway::Home)
+                .set_last(2)
+                .apply(lines())),
+            ["3", "4"]
+        );
+        assert_eq!(
+            ids(PlayerFilter::new()
+                .set_opponent(TeamId::new("den"))
+                .apply(lines())),
+            ["2", "3"]
+        );
+        assert_eq!(
+            ids(PlayerFilter::new().set_season("2024-25").apply(lines())).len(),
+            4
+        );
+        assert!(PlayerFilter::new()
+            .set_season("2023-24")
+            .apply(lines())
+            .is_empty());
+    }
+}
+
+#[cfg(test)]
+mod aggregate_tests {
+    use super::{mock::game_line, *};
+
+    #[test]
+    fn test_aggregate() {
+        let lines = [
+            game_line("1", "lal", "bos", Some(30.0), &[("pts", 30.0)]),
+            game_line(
+                "2",
+                "lal",
+                "bos",
+                Some(42.0),
+                &[("pts", 24.0), ("ast", 9.0)],
+            ),
+            game_line("3", "lal", "bos", None, &[("pts", 27.0)]),
+        ];
+        let aggregate = Aggregate::new(lines.iter().map(|line| &line.line));
+
+        assert_eq!(aggregate.games, 3);
+        assert_eq!(aggregate.total("pts"), 81.0);
+        assert_eq!(aggregate.average("pts"), Some(27.0));
+        assert_eq!(aggregate.average("reb"), Some(0.0));
+        assert_eq!(aggregate.per_minutes("pts", 36.0), Some(27.0));
+        assert_eq!(aggregate.per_minutes("ast", 36.0), Some(4.5));
+        assert_eq!(aggregate.per_possessions("pts", 100.0), None);
+
+        let paced =
+            Aggregate::new([
+                &game_line("4", "lal", "bos", None, &[("pts", 25.0), ("poss", 50.0)]).line,
+            ]);
+        assert_eq!(paced.per_possessions("pts", 100.0), Some(50.0));
+        assert_eq!(Aggregate::new([]).average("pts"), None);
+    }
+}
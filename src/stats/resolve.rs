@@ -0,0 +1,160 @@
+use super::error::StatsError;
+use crate::utils::search::{
+    fuzzy::{FuzzyAlgorithm, FuzzyMatcher},
+    prefix::{PrefixIndex, SortedPrefixIndex},
+};
+
+/// Most candidates listed when a name is ambiguous
+const MAX_CANDIDATES: usize = 5;
+
+/// Finds the record a user typed the name of.
+///
+/// Names are matched case-insensitively by prefix, on the whole name or from any of its
+/// words, so "lebron", "james" and "lebron j" all find "LeBron James". An exact name wins
+/// over prefixes.
+///
+/// ## Returns
+///
+/// The only matching record, `Ambiguous` when several match, or `NotFound` with fuzzy
+/// suggestions when none do.
+///
+/// ## Examples
+///
+/// ```no_run
+/// # use statpack::{model::team::Player, stats::resolve::resolve};
+/// # fn main() -> Result<(), statpack::stats::error::StatsError> {
+/// # let players: Vec<Player> = vec![];
+/// let player = resolve("player", "doncic", &players, |player| vec![player.name.as_str()])?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn resolve<'r, T, F>(
+    kind: &'static str,
+    query: &str,
+    records: &'r [T],
+    names: F,
+) -> Result<&'r T, StatsError>
+where
+    F: Fn(&T) -> Vec<&str>,
+{
+    let query = query.trim().to_lowercase();
+    // Every name is indexed whole and from the start of each of its words
+    let mut keys = vec![];
+    let mut owners = vec![];
+    for (id, record) in records.iter().enumerate() {
+        for name in names(record) {
+            let name = name.to_lowercase();
+            keys.push(name.clone());
+            owners.push(id);
+            for start in word_starts(&name).into_iter().filter(|&start| start > 0) {
+                keys.push(name[start..].to_string());
+                owners.push(id);
+            }
+        }
+    }
+    if let Some(record) = records.iter().find(|record| {
+        names(record)
+            .iter()
+            .any(|name| name.to_lowercase() == query)
+    }) {
+        return Ok(record);
+    }
+
+    let index = SortedPrefixIndex::new(keys.iter().map(String::as_str));
+    let mut found: Vec<usize> = index
+        .match_ids(&query)
+        .iter()
+        .map(|&position| owners[position])
+        .collect();
+    found.sort();
+    found.dedup();
+
+    let display = |id: usize| names(&records[id]).first().map(|name| name.to_string());
+    match found.as_slice() {
+        [id] => Ok(&records[*id]),
+        [] => {
+            let names: Vec<String> = (0..records.len()).filter_map(display).collect();
+            let suggestions = FuzzyMatcher::new()
+                .set_algorithm(FuzzyAlgorithm::Token)
+                .set_limit(3)
+                .rank(&query, names.iter().map(String::as_str))
+                .into_iter()
+                .map(|found| found.key.to_string())
+                .collect();
+            Err(StatsError::NotFound {
+                kind,
+                query,
+                suggestions,
+            })
+        }
+        ids => {
+            let mut candidates: Vec<String> = ids.iter().filter_map(|&id| display(id)).collect();
+            candidates.sort();
+            candidates.truncate(MAX_CANDIDATES);
+            Err(StatsError::Ambiguous {
+                kind,
+                query,
+                candidates,
+            })
+        }
+    }
+}
+
+/// Returns the byte offsets the alphanumeric words of a name start at
+fn word_starts(name: &str) -> Vec<usize> {
+    let mut starts = vec![];
+    let mut previous: Option<char> = None;
+    for (index, char) in name.char_indices() {
+        if char.is_alphanumeric() && !previous.is_some_and(char::is_alphanumeric) {
+            starts.push(index);
+        }
+        previous = Some(char);
+    }
+    starts
+}
+
+#[cfg(test)]
+mod resolve_tests {
+    use super::*;
+
+    const PLAYERS: [&str; 4] = [
+        "LeBron James",
+        "Bronny James",
+        "James Harden",
+        "Luka Dončić",
+    ];
+
+    fn find(query: &str) -> Result<&'static str, StatsError> {
+        resolve("player", query, &PLAYERS, |name| vec![*name]).copied()
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(find("lebron").unwrap(), "LeBron James");
+        assert_eq!(find("LEBRON J").unwrap(), "LeBron James");
+        assert_eq!(find("harden").unwrap(), "James Harden");
+        assert_eq!(find("dončić").unwrap(), "Luka Dončić");
+    }
+
+    #[test]
+    fn test_resolve_ambiguous() {
+        assert_eq!(
+            find("james").unwrap_err().to_string(),
+            "'james' matches several players: Bronny James, James Harden, LeBron James"
+        );
+    }
+
+    #[test]
+    fn test_resolve_not_found() {
+        assert_eq!(
+            find("lebrn james").unwrap_err().to_string(),
+            "no player named 'lebrn james', did you mean 'LeBron James'?"
+        );
+    }
+
+    #[test]
+    fn test_word_starts() {
+        assert_eq!(word_starts("man. united fc"), [0, 5, 12]);
+        assert_eq!(word_starts(" a"), [1]);
+    }
+}
@@ -0,0 +1,334 @@
+use super::HomeAway;
+use crate::model::{
+    game::{Game, Outcome},
+    id::TeamId,
+    sport::{StandingRules, TieBreaker},
+};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+/// Represents results and scoring over a set of games
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TeamRecord {
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub scored: u32,
+    pub conceded: u32,
+}
+impl TeamRecord {
+    /// Counts a game with the given outcome and score
+    pub fn add(&mut self, outcome: Outcome, scored: u32, conceded: u32) -> &mut Self {
+        match outcome {
+            Outcome::Win => self.wins += 1,
+            Outcome::Loss => self.losses += 1,
+            Outcome::Draw => self.draws += 1,
+        }
+        self.scored += scored;
+        self.conceded += conceded;
+        self
+    }
+    pub fn played(&self) -> u32 {
+        self.wins + self.losses + self.draws
+    }
+    /// Returns scored minus conceded
+    pub fn differential(&self) -> i64 {
+        i64::from(self.scored) - i64::from(self.conceded)
+    }
+    /// Returns the share of games won, draws counting half
+    pub fn win_percentage(&self) -> Option<f64> {
+        let played = self.played();
+        (played > 0)
+            .then(|| (f64::from(self.wins) + f64::from(self.draws) / 2.0) / f64::from(played))
+    }
+    /// Returns the league points earned under the rules
+    pub fn points(&self, rules: &StandingRules) -> u32 {
+        self.wins * rules.win_points
+            + self.draws * rules.draw_points
+            + self.losses * rules.loss_points
+    }
+}
+
+impl fmt::Display for TeamRecord {
+    /// Formats the record as `wins-losses-draws`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}-{}", self.wins, self.losses, self.draws)
+    }
+}
+
+/// Represents how a team fared over a set of games
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamSummary {
+    pub team: TeamId,
+    pub overall: TeamRecord,
+    pub home: TeamRecord,
+    pub away: TeamRecord,
+    /// Outcomes of the final games, oldest first
+    pub outcomes: Vec<Outcome>,
+}
+impl TeamSummary {
+    /// Summarizes the final games the team played, which must be ordered oldest first
+    pub fn new<'g>(team: &TeamId, games: impl IntoIterator<Item = &'g Game>) -> Self {
+        let mut summary = TeamSummary {
+            team: team.clone(),
+            overall: TeamRecord::default(),
+            home: TeamRecord::default(),
+            away: TeamRecord::default(),
+            outcomes: vec![],
+        };
+        for game in games {
+            let (Some(outcome), Some((scored, conceded)), Some(side)) = (
+                game.outcome_for(team),
+                game.score_for(team),
+                HomeAway::of(game, team),
+            ) else {
+                continue;
+            };
+            summary.overall.add(outcome, scored, conceded);
+            match side {
+                HomeAway::Home => summary.home.add(outcome, scored, conceded),
+                HomeAway::Away => summary.away.add(outcome, scored, conceded),
+            };
+            summary.outcomes.push(outcome);
+        }
+        summary
+    }
+    /// Returns the outcome of the latest games and how many games in a row it repeats
+    pub fn streak(&self) -> Option<(Outcome, usize)> {
+        let last = *self.outcomes.last()?;
+        let length = self
+            .outcomes
+            .iter()
+            .rev()
+            .take_while(|&&outcome| outcome == last)
+            .count();
+        Some((last, length))
+    }
+    /// Returns the outcomes of the last `n` games, oldest first
+    pub fn form(&self, n: usize) -> &[Outcome] {
+        &self.outcomes[self.outcomes.len().saturating_sub(n)..]
+    }
+}
+
+/// Represents a team's row in a league table
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    /// Position in the table, from 1
+    pub rank: usize,
+    pub points: u32,
+    pub summary: TeamSummary,
+}
+
+/// Ranks every team playing the final games by the rules of the league
+///
+/// ## Returns
+///
+/// The table from first to last, teams still level after every tie-breaker ordered by ID.
+pub fn standings(games: &[Game], rules: &StandingRules) -> Vec<Standing> {
+    let mut teams: Vec<&TeamId> = games
+        .iter()
+        .filter(|game| game.home_score.is_some() && game.away_score.is_some())
+        .flat_map(|game| [&game.home, &game.away])
+        .collect();
+    teams.sort();
+    teams.dedup();
+
+    let mut table: Vec<Standing> = teams
+        .into_iter()
+        .map(|team| {
+            let summary = TeamSummary::new(team, games);
+            Standing {
+                rank: 0,
+                points: summary.overall.points(rules),
+                summary,
+            }
+        })
+        .collect();
+    rank(&mut table, &rules.tie_breakers, games, rules);
+    for (index, standing) in table.iter_mut().enumerate() {
+        standing.rank = index + 1;
+    }
+    table
+}
+
+/// Orders teams level on the tie-breakers applied so far by the next one, then breaks the
+/// ties left within every group of still level teams by the ones after it
+fn rank(
+    group: &mut [Standing],
+    tie_breakers: &[TieBreaker],
+    games: &[Game],
+    rules: &StandingRules,
+) {
+    let Some((&tie_breaker, rest)) = tie_breakers.split_first() else {
+        group.sort_by(|a, b| a.summary.team.cmp(&b.summary.team));
+        return;
+    };
+    if group.len() < 2 {
+        return;
+    }
+    let head_to_head = (tie_breaker == TieBreaker::HeadToHead).then(|| head_to_head(group, games));
+    let order = |a: &Standing, b: &Standing| match &head_to_head {
+        Some(records) => {
+            let (a, b) = (&records[&a.summary.team], &records[&b.summary.team]);
+            b.points(rules)
+                .cmp(&a.points(rules))
+                .then_with(|| b.differential().cmp(&a.differential()))
+        }
+        None => compare(tie_breaker, a, b),
+    };
+    group.sort_by(order);
+
+    let mut start = 0;
+    while start < group.len() {
+        let end = (start + 1..group.len())
+            .find(|&end| order(&group[start], &group[end]).is_ne())
+            .unwrap_or(group.len());
+        rank(&mut group[start..end], rest, games, rules);
+        start = end;
+    }
+}
+
+/// Returns the record of every team of the group in the games between teams of the group
+fn head_to_head(group: &[Standing], games: &[Game]) -> HashMap<TeamId, TeamRecord> {
+    let teams: HashSet<&TeamId> = group
+        .iter()
+        .map(|standing| &standing.summary.team)
+        .collect();
+    let meetings: Vec<&Game> = games
+        .iter()
+        .filter(|game| teams.contains(&game.home) && teams.contains(&game.away))
+        .collect();
+    teams
+        .into_iter()
+        .map(|team| {
+            let record = TeamSummary::new(team, meetings.iter().copied()).overall;
+            (team.clone(), record)
+        })
+        .collect()
+}
+
+/// Orders two standings by a tie-breaker on their whole records, the better team first
+///
+/// `TieBreaker::HeadToHead` depends on every level team at once, `rank` applies it.
+fn compare(tie_breaker: TieBreaker, a: &Standing, b: &Standing) -> Ordering {
+    let (a_record, b_record) = (&a.summary.overall, &b.summary.overall);
+    match tie_breaker {
+        TieBreaker::Points => b.points.cmp(&a.points),
+        TieBreaker::WinPercentage => b_record
+            .win_percentage()
+            .unwrap_or(0.0)
+            .total_cmp(&a_record.win_percentage().unwrap_or(0.0)),
+        TieBreaker::Wins => b_record.wins.cmp(&a_record.wins),
+        TieBreaker::Differential => b_record.differential().cmp(&a_record.differential()),
+        TieBreaker::ScoredFor => b_record.scored.cmp(&a_record.scored),
+        TieBreaker::HeadToHead => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod team_summary_tests {
+    use super::*;
+    use crate::model::game::mock::game;
+
+    #[test]
+    fn test_team_summary() {
+        let games = [
+            game("1", "lal", "bos", Some((110, 100))),
+            game("2", "den", "lal", Some((120, 101))),
+            game("3", "lal", "den", Some((99, 98))),
+            game("4", "bos", "lal", Some((90, 95))),
+            game("5", "lal", "bos", None),
+        ];
+        let summary = TeamSummary::new(&TeamId::new("lal"), &games);
+
+        assert_eq!((summary.overall.wins, summary.overall.losses), (3, 1));
+        assert_eq!(summary.overall.differential(), 405 - 408);
+        assert_eq!((summary.home.wins, summary.away.wins), (2, 1));
+        assert_eq!(summary.streak(), Some((Outcome::Win, 2)));
+        assert_eq!(summary.form(2), [Outcome::Win, Outcome::Win]);
+        assert_eq!(summary.overall.win_percentage(), Some(0.75));
+        assert_eq!(summary.away.to_string(), "1-1-0");
+    }
+}
+
+#[cfg(test)]
+mod standings_tests {
+    use super::*;
+    use crate::model::{game::mock::game, sport::Sport};
+
+    fn ranked(games: &[Game], rules: &StandingRules) -> Vec<String> {
+        standings(games, rules)
+            .iter()
+            .map(|standing| standing.summary.team.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_standings_soccer() {
+        let games = [
+            game("1", "ars", "che", Some((2, 0))),
+            game("2", "che", "liv", Some((1, 1))),
+            game("3", "liv", "ars", Some((3, 0))),
+            game("4", "che", "ars", Some((1, 1))),
+        ];
+        let table = standings(&games, &StandingRules::for_sport(Sport::Soccer));
+
+        assert_eq!(
+            ranked(&games, &StandingRules::for_sport(Sport::Soccer)),
+            ["liv", "ars", "che"]
+        );
+        assert_eq!(table[0].points, 4);
+        assert_eq!(table[1].points, 4);
+        assert_eq!(table[2].rank, 3);
+    }
+
+    #[test]
+    fn test_standings_head_to_head() {
+        // lal and bos are level on every count but lal won their meeting
+        let games = [
+            game("1", "lal", "bos", Some((100, 90))),
+            game("2", "bos", "den", Some((100, 90))),
+            game("3", "den", "lal", Some((100, 90))),
+        ];
+        let mut rules = StandingRules::for_sport(Sport::Basketball);
+        rules.tie_breakers = vec![TieBreaker::WinPercentage, TieBreaker::HeadToHead];
+
+        assert_eq!(ranked(&games[..1], &rules), ["lal", "bos"]);
+        rules.tie_breakers = vec![TieBreaker::WinPercentage];
+        assert_eq!(ranked(&games, &rules), ["bos", "den", "lal"]);
+    }
+
+    #[test]
+    fn test_standings_head_to_head_cycle() {
+        // lal beat bos, bos beat den and den beat lal, all by the same margin, and mia lost
+        // to each of them
+        let mut games = vec![
+            game("1", "lal", "bos", Some((100, 90))),
+            game("2", "bos", "den", Some((100, 90))),
+            game("3", "den", "lal", Some((100, 90))),
+        ];
+        for (id, team) in [("4", "lal"), ("5", "bos"), ("6", "den")] {
+            games.push(game(id, team, "mia", Some((100, 80))));
+        }
+        let mut rules = StandingRules::for_sport(Sport::Basketball);
+        rules.tie_breakers = vec![TieBreaker::WinPercentage, TieBreaker::HeadToHead];
+
+        // The games against mia do not count between the three level teams, they fall back
+        // to ID whatever order they come in
+        for _ in 0..games.len() {
+            assert_eq!(ranked(&games, &rules), ["bos", "den", "lal", "mia"]);
+            games.rotate_left(1);
+        }
+
+        // A wider margin of lal over bos settles the mini-table on differential
+        let first = games
+            .iter_mut()
+            .find(|game| game.id.as_str() == "1")
+            .unwrap();
+        first.home_score = Some(120);
+        assert_eq!(ranked(&games, &rules), ["lal", "den", "bos", "mia"]);
+    }
+}
@@ -0,0 +1,596 @@
+use super::{
+    error::StatsError,
+    h2h::{against_the_spread, closing_line, over_under, BetRecord},
+    team::TeamRecord,
+    HomeAway,
+};
+use crate::model::{
+    game::{Game, Outcome},
+    id::TeamId,
+    odds::{Market, OddsSnapshot, Selection},
+};
+use std::{fmt, str::FromStr};
+
+/// Represents a final game of a team along with the situation it was played in
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameContext<'g> {
+    pub game: &'g Game,
+    pub side: HomeAway,
+    pub opponent: &'g TeamId,
+    pub outcome: Outcome,
+    pub scored: u32,
+    pub conceded: u32,
+    /// Full days off since the previous game, `0` on the second night of a back-to-back
+    pub rest: Option<i64>,
+    /// Outcome of the previous game
+    pub previous: Option<Outcome>,
+    /// Closing spread of the team, negative when it was favored
+    pub spread: Option<f64>,
+    /// Closing total of the game
+    pub total: Option<f64>,
+    /// Whether the team covered the closing spread, `Draw` for a push
+    pub ats: Option<Outcome>,
+    /// Side of the closing total the game landed on, `Win` for an over and `Draw` for a
+    /// push
+    pub over_under: Option<Outcome>,
+}
+
+/// Puts every final game of the team in its situation
+///
+/// `games` must be ordered oldest first, rest is counted from the previous game in them.
+pub fn contexts<'g>(
+    team: &TeamId,
+    games: &'g [Game],
+    odds: &[OddsSnapshot],
+) -> Vec<GameContext<'g>> {
+    let mut contexts: Vec<GameContext> = vec![];
+    for game in games {
+        let (Some(outcome), Some((scored, conceded)), Some(side), Some(opponent)) = (
+            game.outcome_for(team),
+            game.score_for(team),
+            HomeAway::of(game, team),
+            game.opponent_of(team),
+        ) else {
+            continue;
+        };
+        let previous = contexts.last();
+        let home_spread = closing_line(game, odds, &Market::Spread, Selection::Home);
+        let total = closing_line(game, odds, &Market::Total, Selection::Over);
+        contexts.push(GameContext {
+            game,
+            side,
+            opponent,
+            outcome,
+            scored,
+            conceded,
+            rest: previous.map(|previous| {
+                (game.start.date_naive() - previous.game.start.date_naive()).num_days() - 1
+            }),
+            previous: previous.map(|previous| previous.outcome),
+            spread: home_spread.map(|spread| match side {
+                HomeAway::Home => spread,
+                HomeAway::Away => -spread,
+            }),
+            total,
+            ats: home_spread.and_then(|spread| against_the_spread(game, team, spread)),
+            over_under: total.map(|total| match over_under(game, total) {
+                Some(Selection::Over) => Outcome::Win,
+                Some(_) => Outcome::Loss,
+                None => Outcome::Draw,
+            }),
+        });
+    }
+    contexts
+}
+
+/// Represents how a team fared in a set of games, straight up, against the spread and
+/// on totals
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct TrendSummary {
+    pub record: TeamRecord,
+    pub ats: BetRecord,
+    /// Overs counting as wins
+    pub over_under: BetRecord,
+}
+impl TrendSummary {
+    pub fn new<'c, 'g: 'c>(contexts: impl IntoIterator<Item = &'c GameContext<'g>>) -> Self {
+        let mut summary = Self::default();
+        let count = |record: &mut BetRecord, outcome: Option<Outcome>| match outcome {
+            Some(Outcome::Win) => record.wins += 1,
+            Some(Outcome::Loss) => record.losses += 1,
+            Some(Outcome::Draw) => record.pushes += 1,
+            None => (),
+        };
+        for context in contexts {
+            summary
+                .record
+                .add(context.outcome, context.scored, context.conceded);
+            count(&mut summary.ats, context.ats);
+            count(&mut summary.over_under, context.over_under);
+        }
+        summary
+    }
+}
+
+/// Represents the length of the latest and the longest run of a value
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Streak {
+    /// Length of the run the latest items are in, `0` when the last item differs
+    pub current: usize,
+    pub longest: usize,
+}
+
+/// Returns the runs of `wanted` in items ordered oldest first, skipping `None`
+pub fn streak<T: PartialEq>(items: impl IntoIterator<Item = Option<T>>, wanted: &T) -> Streak {
+    let mut streak = Streak::default();
+    for item in items.into_iter().flatten() {
+        if item == *wanted {
+            streak.current += 1;
+            streak.longest = streak.longest.max(streak.current);
+        } else {
+            streak.current = 0;
+        }
+    }
+    streak
+}
+
+/// Returns the average of every value with the ones before it, over at most `window`
+/// values
+pub fn rolling_average(values: &[f64], window: usize) -> Vec<f64> {
+    (0..values.len())
+        .map(|end| {
+            let values = &values[(end + 1).saturating_sub(window.max(1))..=end];
+            values.iter().sum::<f64>() / values.len() as f64
+        })
+        .collect()
+}
+
+/// Represents a condition on the situation of a game, parsed from a query such as
+/// `away & rest<2` or `home & !(b2b | after-loss)`
+///
+/// ## Conditions
+///
+/// - `home`, `away`: side of the game
+/// - `b2b`: second game of a back-to-back
+/// - `after-win`, `after-loss`, `after-draw`: outcome of the previous game
+/// - `fav`, `dog`: favored or not by the closing spread
+/// - `vs:<team>`: against the team, by ID
+/// - `rest`, `spread`, `total` compared to a number with `<`, `<=`, `>`, `>=`, `=` or
+///   `!=`
+///
+/// Conditions combine with `!`, `&` and `|`, in that order of precedence, and
+/// parentheses. Comparisons on a missing value (no previous game, no closing line) fail.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Situation {
+    query: String,
+    expression: Expression,
+}
+impl Situation {
+    /// Indicates if a game was played in the situation
+    pub fn matches(&self, context: &GameContext) -> bool {
+        self.expression.matches(context)
+    }
+}
+
+impl fmt::Display for Situation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.query)
+    }
+}
+
+impl FromStr for Situation {
+    type Err = StatsError;
+
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        let error = |message: String| StatsError::InvalidQuery {
+            query: query.to_string(),
+            message,
+        };
+        let mut parser = Parser {
+            tokens: tokenize(query).map_err(error)?,
+            position: 0,
+        };
+        let expression = parser.or().map_err(error)?;
+        if let Some(token) = parser.peek() {
+            return Err(error(format!("unexpected '{}'", token)));
+        }
+        Ok(Situation {
+            query: query.trim().to_string(),
+            expression,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expression {
+    Condition(Condition),
+    Not(Box<Expression>),
+    And(Box<Expression>, Box<Expression>),
+    Or(Box<Expression>, Box<Expression>),
+}
+impl Expression {
+    fn matches(&self, context: &GameContext) -> bool {
+        match self {
+            Expression::Condition(condition) => condition.matches(context),
+            Expression::Not(expression) => !expression.matches(context),
+            Expression::And(left, right) => left.matches(context) && right.matches(context),
+            Expression::Or(left, right) => left.matches(context) || right.matches(context),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    Side(HomeAway),
+    BackToBack,
+    After(Outcome),
+    Favorite,
+    Underdog,
+    Versus(String),
+    Compare(Variable, Comparison, f64),
+}
+impl Condition {
+    fn matches(&self, context: &GameContext) -> bool {
+        match self {
+            Condition::Side(side) => context.side == *side,
+            Condition::BackToBack => context.rest == Some(0),
+            Condition::After(outcome) => context.previous == Some(*outcome),
+            Condition::Favorite => context.spread.is_some_and(|spread| spread < 0.0),
+            Condition::Underdog => context.spread.is_some_and(|spread| spread > 0.0),
+            Condition::Versus(team) => {
+                let opponent = context.opponent.as_str();
+                opponent == team || opponent.ends_with(&format!(":{}", team))
+            }
+            Condition::Compare(variable, comparison, number) => {
+                let value = match variable {
+                    Variable::Rest => context.rest.map(|rest| rest as f64),
+                    Variable::Spread => context.spread,
+                    Variable::Total => context.total,
+                };
+                value.is_some_and(|value| comparison.holds(value, *number))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Variable {
+    Rest,
+    Spread,
+    Total,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+impl Comparison {
+    fn holds(&self, value: f64, number: f64) -> bool {
+        match self {
+            Comparison::Less => value < number,
+            Comparison::LessOrEqual => value <= number,
+            Comparison::Greater => value > number,
+            Comparison::GreaterOrEqual => value >= number,
+            Comparison::Equal => value == number,
+            Comparison::NotEqual => value != number,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Compare(Comparison),
+    Not,
+    And,
+    Or,
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "{}", word),
+            Token::Compare(comparison) => write!(
+                f,
+                "{}",
+                match comparison {
+                    Comparison::Less => "<",
+                    Comparison::LessOrEqual => "<=",
+                    Comparison::Greater => ">",
+                    Comparison::GreaterOrEqual => ">=",
+                    Comparison::Equal => "=",
+                    Comparison::NotEqual => "!=",
+                }
+            ),
+            Token::Not => write!(f, "!"),
+            Token::And => write!(f, "&"),
+            Token::Or => write!(f, "|"),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+        }
+    }
+}
+
+/// Splits a query into tokens, `&&`, `||` and `==` being read as `&`, `|` and `=`
+fn tokenize(query: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = query.chars().peekable();
+    while let Some(char) = chars.next() {
+        let mut followed_by = |next: char| chars.next_if_eq(&next).is_some();
+        let token = match char {
+            ' ' | '\t' => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '&' => {
+                followed_by('&');
+                Token::And
+            }
+            '|' => {
+                followed_by('|');
+                Token::Or
+            }
+            '!' if followed_by('=') => Token::Compare(Comparison::NotEqual),
+            '!' => Token::Not,
+            '<' if followed_by('=') => Token::Compare(Comparison::LessOrEqual),
+            '<' => Token::Compare(Comparison::Less),
+            '>' if followed_by('=') => Token::Compare(Comparison::GreaterOrEqual),
+            '>' => Token::Compare(Comparison::Greater),
+            '=' => {
+                followed_by('=');
+                Token::Compare(Comparison::Equal)
+            }
+            char if is_word(char) => {
+                let mut word = char.to_string();
+                while let Some(char) = chars.next_if(|char| is_word(*char)) {
+                    word.push(char);
+                }
+                Token::Word(word.to_lowercase())
+            }
+            char => return Err(format!("unexpected '{}'", char)),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+fn is_word(char: char) -> bool {
+    char.is_alphanumeric() || matches!(char, '-' | '+' | '.' | ':' | '_')
+}
+
+/// Recursive descent parser over the tokens of a query
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+    fn or(&mut self) -> Result<Expression, String> {
+        let mut expression = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.position += 1;
+            expression = Expression::Or(Box::new(expression), Box::new(self.and()?));
+        }
+        Ok(expression)
+    }
+    fn and(&mut self) -> Result<Expression, String> {
+        let mut expression = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.position += 1;
+            expression = Expression::And(Box::new(expression), Box::new(self.unary()?));
+        }
+        Ok(expression)
+    }
+    fn unary(&mut self) -> Result<Expression, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(Expression::Not(Box::new(self.unary()?))),
+            Some(Token::Open) => {
+                let expression = self.or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(expression),
+                    _ => Err("missing ')'".to_string()),
+                }
+            }
+            Some(Token::Word(word)) => self.condition(&word).map(Expression::Condition),
+            Some(token) => Err(format!("expected a condition, found '{}'", token)),
+            None => Err("expected a condition".to_string()),
+        }
+    }
+    fn condition(&mut self, word: &str) -> Result<Condition, String> {
+        let variable = match word {
+            "rest" => Some(Variable::Rest),
+            "spread" => Some(Variable::Spread),
+            "total" => Some(Variable::Total),
+            _ => None,
+        };
+        if let Some(variable) = variable {
+            let Some(Token::Compare(comparison)) = self.next() else {
+                return Err(format!("expected a comparison after '{}'", word));
+            };
+            return match self.next() {
+                Some(Token::Word(number)) => number
+                    .parse()
+                    .map(|number| Condition::Compare(variable, comparison, number))
+                    .map_err(|_| format!("'{}' is not a number", number)),
+                _ => Err(format!(
+                    "expected a number after '{} {}'",
+                    word,
+                    Token::Compare(comparison)
+                )),
+            };
+        }
+        Ok(match word {
+            "home" => Condition::Side(HomeAway::Home),
+            "away" => Condition::Side(HomeAway::Away),
+            "b2b" => Condition::BackToBack,
+            "after-win" => Condition::After(Outcome::Win),
+            "after-loss" => Condition::After(Outcome::Loss),
+            "after-draw" => Condition::After(Outcome::Draw),
+            "fav" => Condition::Favorite,
+            "dog" => Condition::Underdog,
+            _ => match word.strip_prefix("vs:").filter(|team| !team.is_empty()) {
+                Some(team) => Condition::Versus(team.to_string()),
+                None => return Err(format!("unknown condition '{}'", word)),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod trends_tests {
+    use super::*;
+    use crate::{model::game::mock::game, stats::h2h::mock::line};
+    use chrono::Duration;
+
+    /// lal plays on days 0, 1, 3 and 7, scores 100 and concedes 95, 105, 90 and 100
+    fn games() -> Vec<Game> {
+        let mut games = vec![
+            game("1", "lal", "bos", Some((100, 95))),
+            game("2", "den", "lal", Some((105, 100))),
+            game("3", "bos", "lal", Some((90, 100))),
+            game("4", "lal", "den", Some((100, 100))),
+            game("5", "lal", "bos", None),
+        ];
+        for (game, day) in games.iter_mut().zip([0, 1, 3, 7, 8]) {
+            game.start += Duration::days(day);
+        }
+        games
+    }
+
+    fn situation(query: &str) -> Situation {
+        query.parse().unwrap()
+    }
+
+    fn ids(contexts: &[GameContext], query: &str) -> Vec<String> {
+        let situation = situation(query);
+        contexts
+            .iter()
+            .filter(|context| situation.matches(context))
+            .map(|context| context.game.id.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_contexts() {
+        let games = games();
+        let odds = [
+            line(&games[0], "pinnacle", Market::Spread, -3.5, 1),
+            line(&games[1], "pinnacle", Market::Spread, -6.5, 1),
+            line(&games[1], "pinnacle", Market::Total, 205.0, 1),
+        ];
+        let contexts = contexts(&TeamId::new("lal"), &games, &odds);
+
+        assert_eq!(contexts.len(), 4);
+        assert_eq!(
+            contexts
+                .iter()
+                .map(|context| context.rest)
+                .collect::<Vec<_>>(),
+            [None, Some(0), Some(1), Some(3)]
+        );
+        assert_eq!(contexts[1].previous, Some(Outcome::Win));
+        assert_eq!(contexts[0].ats, Some(Outcome::Win));
+        // lal got 6.5 points away and lost by 5
+        assert_eq!(contexts[1].spread, Some(6.5));
+        assert_eq!(contexts[1].ats, Some(Outcome::Win));
+        assert_eq!(contexts[1].over_under, Some(Outcome::Draw));
+
+        let summary = TrendSummary::new(&contexts);
+        assert_eq!(summary.record.to_string(), "2-1-1");
+        assert_eq!(summary.ats.to_string(), "2-0-0");
+        assert_eq!(summary.over_under.to_string(), "0-0-1");
+    }
+
+    #[test]
+    fn test_situation() {
+        let games = games();
+        let odds = [
+            line(&games[0], "pinnacle", Market::Spread, -3.5, 1),
+            line(&games[1], "pinnacle", Market::Spread, -6.5, 1),
+        ];
+        let contexts = contexts(&TeamId::new("lal"), &games, &odds);
+
+        assert_eq!(ids(&contexts, "away & rest<2"), ["2", "3"]);
+        assert_eq!(ids(&contexts, "b2b"), ["2"]);
+        assert_eq!(ids(&contexts, "home && !after-loss"), ["1", "4"]);
+        assert_eq!(ids(&contexts, "after-loss | vs:bos"), ["1", "3"]);
+        assert_eq!(ids(&contexts, "!(home | b2b) & rest >= 1"), ["3"]);
+        assert_eq!(ids(&contexts, "fav"), ["1"]);
+        assert_eq!(ids(&contexts, "dog | spread<=-3.5"), ["1", "2"]);
+        assert!(ids(&contexts, "total>200").is_empty());
+        assert_eq!(situation(" away &  b2b ").to_string(), "away &  b2b");
+    }
+
+    #[test]
+    fn test_situation_errors() {
+        let error = |query: &str| query.parse::<Situation>().unwrap_err().to_string();
+
+        assert_eq!(
+            error("away &"),
+            "invalid query 'away &': expected a condition"
+        );
+        assert_eq!(
+            error("road"),
+            "invalid query 'road': unknown condition 'road'"
+        );
+        assert_eq!(
+            error("rest<two"),
+            "invalid query 'rest<two': 'two' is not a number"
+        );
+        assert_eq!(error("(home"), "invalid query '(home': missing ')'");
+        assert_eq!(
+            error("home away"),
+            "invalid query 'home away': unexpected 'away'"
+        );
+        assert_eq!(
+            error("rest"),
+            "invalid query 'rest': expected a comparison after 'rest'"
+        );
+        assert_eq!(error("home $"), "invalid query 'home $': unexpected '$'");
+    }
+
+    #[test]
+    fn test_streak_and_rolling_average() {
+        let outcomes = [
+            Some(Outcome::Win),
+            Some(Outcome::Win),
+            None,
+            Some(Outcome::Win),
+            Some(Outcome::Loss),
+            Some(Outcome::Win),
+        ];
+
+        assert_eq!(
+            streak(outcomes, &Outcome::Win),
+            Streak {
+                current: 1,
+                longest: 3
+            }
+        );
+        assert_eq!(
+            streak(outcomes, &Outcome::Loss),
+            Streak {
+                current: 0,
+                longest: 1
+            }
+        );
+        assert_eq!(
+            rolling_average(&[10.0, 20.0, 30.0, 40.0], 2),
+            [10.0, 15.0, 25.0, 35.0]
+        );
+        assert!(rolling_average(&[], 3).is_empty());
+    }
+}
@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use std::{error::Error, fmt};
+
+/// Represents the errors raised by the local stats store
+#[derive(Debug)]
+pub enum StoreError {
+    Sqlite(rusqlite::Error),
+    Json(serde_json::Error),
+    Io(std::io::Error),
+    /// The database schema does not match the version this binary expects
+    SchemaMismatch {
+        current: u32,
+        expected: u32,
+    },
+    /// A write was attempted while the store shows the database as it was in the past
+    ReadOnly {
+        as_of: DateTime<Utc>,
+    },
+}
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::Sqlite(error) => write!(f, "database error: {}", error),
+            StoreError::Json(error) => write!(f, "corrupt record: {}", error),
+            StoreError::Io(error) => write!(f, "{}", error),
+            StoreError::SchemaMismatch { current, expected } if current < expected => write!(
+                f,
+                "database schema is at v{} but v{} is required, run `statpack db migrate`",
+                current, expected
+            ),
+            StoreError::SchemaMismatch { current, expected } => write!(
+                f,
+                "database schema v{} is newer than the v{} this build supports",
+                current, expected
+            ),
+            StoreError::ReadOnly { as_of } => write!(
+                f,
+                "the database is read-only as of {}, drop --as-of to change it",
+                as_of.to_rfc3339()
+            ),
+        }
+    }
+}
+impl Error for StoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StoreError::Sqlite(error) => Some(error),
+            StoreError::Json(error) => Some(error),
+            StoreError::Io(error) => Some(error),
+            StoreError::SchemaMismatch { .. } | StoreError::ReadOnly { .. } => None,
+        }
+    }
+}
+impl From<rusqlite::Error> for StoreError {
+    fn from(error: rusqlite::Error) -> Self {
+        StoreError::Sqlite(error)
+    }
+}
+impl From<serde_json::Error> for StoreError {
+    fn from(error: serde_json::Error) -> Self {
+        StoreError::Json(error)
+    }
+}
+impl From<std::io::Error> for StoreError {
+    fn from(error: std::io::Error) -> Self {
+        StoreError::Io(error)
+    }
+}
@@ -0,0 +1,236 @@
+use super::error::StoreError;
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Represents a single, append-only step of the database schema
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// Every schema migration in order. Never edit an applied migration, append a new one.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "core model",
+        sql: "
+        CREATE TABLE leagues (
+            id TEXT PRIMARY KEY,
+            sport TEXT NOT NULL,
+            data TEXT NOT NULL
+        );
+        CREATE TABLE seasons (
+            id TEXT PRIMARY KEY,
+            league TEXT NOT NULL,
+            data TEXT NOT NULL
+        );
+        CREATE TABLE venues (
+            id TEXT PRIMARY KEY,
+            data TEXT NOT NULL
+        );
+        CREATE TABLE teams (
+            id TEXT PRIMARY KEY,
+            league TEXT NOT NULL,
+            name TEXT NOT NULL,
+            data TEXT NOT NULL
+        );
+        CREATE TABLE players (
+            id TEXT PRIMARY KEY,
+            team TEXT,
+            name TEXT NOT NULL,
+            data TEXT NOT NULL
+        );
+        CREATE TABLE games (
+            id TEXT PRIMARY KEY,
+            league TEXT NOT NULL,
+            season TEXT NOT NULL,
+            home TEXT NOT NULL,
+            away TEXT NOT NULL,
+            start TEXT NOT NULL,
+            data TEXT NOT NULL
+        );
+        CREATE INDEX games_home ON games (home, start);
+        CREATE INDEX games_away ON games (away, start);
+        CREATE INDEX games_season ON games (season, start);
+        CREATE TABLE events (
+            id TEXT PRIMARY KEY,
+            game TEXT NOT NULL,
+            data TEXT NOT NULL
+        );
+        CREATE INDEX events_game ON events (game);
+        CREATE TABLE stat_lines (
+            id TEXT PRIMARY KEY,
+            game TEXT NOT NULL,
+            player TEXT NOT NULL,
+            team TEXT NOT NULL,
+            data TEXT NOT NULL
+        );
+        CREATE INDEX stat_lines_player ON stat_lines (player);
+        CREATE INDEX stat_lines_game ON stat_lines (game);
+    ",
+    },
+    Migration {
+        version: 2,
+        name: "odds snapshots",
+        sql: "
+        CREATE TABLE odds (
+            id TEXT PRIMARY KEY,
+            game TEXT NOT NULL,
+            book TEXT NOT NULL,
+            taken_at TEXT NOT NULL,
+            data TEXT NOT NULL
+        );
+        CREATE INDEX odds_game ON odds (game, taken_at);
+    ",
+    },
+    Migration {
+        version: 3,
+        name: "favorites",
+        sql: "
+        CREATE TABLE favorites (
+            id TEXT PRIMARY KEY,
+            kind TEXT NOT NULL,
+            data TEXT NOT NULL
+        );
+    ",
+    },
+    Migration {
+        version: 4,
+        name: "bets",
+        sql: "
+        CREATE TABLE bets (
+            id TEXT PRIMARY KEY,
+            game TEXT NOT NULL,
+            placed_at TEXT NOT NULL,
+            data TEXT NOT NULL
+        );
+        CREATE INDEX bets_game ON bets (game);
+    ",
+    },
+];
+
+/// The schema version a fully migrated database is at
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Represents a migration along with when it was applied, if it was
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationStatus {
+    pub migration: Migration,
+    pub applied_at: Option<String>,
+}
+
+/// Creates the bookkeeping table tracking applied migrations
+fn ensure_table(conn: &Connection) -> Result<(), StoreError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        );",
+    )?;
+    Ok(())
+}
+
+/// Returns the highest applied migration version (`0` for a fresh database)
+pub fn current_version(conn: &Connection) -> Result<u32, StoreError> {
+    ensure_table(conn)?;
+    let version: Option<u32> = conn
+        .query_row("SELECT MAX(version) FROM schema_migrations", [], |row| {
+            row.get(0)
+        })
+        .optional()?
+        .flatten();
+    Ok(version.unwrap_or(0))
+}
+
+/// Returns every known migration with its applied date
+pub fn status(conn: &Connection) -> Result<Vec<MigrationStatus>, StoreError> {
+    ensure_table(conn)?;
+    let mut statement =
+        conn.prepare("SELECT applied_at FROM schema_migrations WHERE version = ?1")?;
+
+    MIGRATIONS
+        .iter()
+        .map(|migration| {
+            let applied_at = statement
+                .query_row([migration.version], |row| row.get(0))
+                .optional()?;
+            Ok(MigrationStatus {
+                migration: *migration,
+                applied_at,
+            })
+        })
+        .collect()
+}
+
+/// Applies every pending migration, each in its own transaction
+///
+/// ## Returns
+///
+/// The migrations that were applied.
+pub fn migrate(conn: &mut Connection) -> Result<Vec<Migration>, StoreError> {
+    let current = current_version(conn)?;
+    if current > SCHEMA_VERSION {
+        return Err(StoreError::SchemaMismatch {
+            current,
+            expected: SCHEMA_VERSION,
+        });
+    }
+
+    let mut applied = vec![];
+    for migration in MIGRATIONS.iter().filter(|item| item.version > current) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration.sql)?;
+        tx.execute(
+            "INSERT INTO schema_migrations (version, name, applied_at) VALUES (?1, ?2, ?3)",
+            params![migration.version, migration.name, Utc::now().to_rfc3339()],
+        )?;
+        tx.commit()?;
+        applied.push(*migration);
+    }
+    Ok(applied)
+}
+
+#[cfg(test)]
+mod migrate_tests {
+    use super::*;
+
+    #[test]
+    fn test_migrations_are_sequential() {
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version, index as u32 + 1);
+        }
+    }
+
+    #[test]
+    fn test_migrate() {
+        let mut conn = Connection::open_in_memory().unwrap();
+
+        assert_eq!(current_version(&conn).unwrap(), 0);
+        assert_eq!(migrate(&mut conn).unwrap().len(), MIGRATIONS.len());
+        assert_eq!(current_version(&conn).unwrap(), SCHEMA_VERSION);
+        assert!(migrate(&mut conn).unwrap().is_empty());
+        assert!(status(&conn)
+            .unwrap()
+            .iter()
+            .all(|item| item.applied_at.is_some()));
+    }
+
+    #[test]
+    fn test_migrate_newer_schema() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+        conn.execute(
+            "INSERT INTO schema_migrations VALUES (?1, 'future', 'now')",
+            [SCHEMA_VERSION + 1],
+        )
+        .unwrap();
+
+        assert!(matches!(
+            migrate(&mut conn),
+            Err(StoreError::SchemaMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,452 @@
+use self::{
+    error::StoreError,
+    migration::{MigrationStatus, SCHEMA_VERSION},
+    record::Record,
+    repository::Repository,
+};
+use crate::model::{game::UNKNOWN_SPORT_DURATION, sport::Sport};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{Connection, Transaction};
+use std::{fs, path::Path};
+
+pub mod error;
+pub mod migration;
+pub mod record;
+pub mod repository;
+
+/// Represents the local, offline-first stats database
+#[derive(Debug)]
+pub struct Store {
+    conn: Connection,
+    as_of: Option<DateTime<Utc>>,
+}
+impl Store {
+    /// Opens (or creates) the database file, creating missing parent directories
+    pub fn open(path: &Path) -> Result<Self, StoreError> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)?;
+        }
+        Self::from_connection(Connection::open(path)?)
+    }
+    /// Opens a throwaway database living in memory
+    pub fn open_in_memory() -> Result<Self, StoreError> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+    fn from_connection(conn: Connection) -> Result<Self, StoreError> {
+        conn.execute_batch("PRAGMA foreign_keys = ON; PRAGMA journal_mode = WAL;")?;
+        Ok(Self { conn, as_of: None })
+    }
+    /// Applies every pending schema migration
+    pub fn migrate(&mut self) -> Result<Vec<migration::Migration>, StoreError> {
+        migration::migrate(&mut self.conn)
+    }
+    /// Returns the state of every schema migration
+    pub fn status(&self) -> Result<Vec<MigrationStatus>, StoreError> {
+        migration::status(&self.conn)
+    }
+    /// Returns the version the database schema is at
+    pub fn version(&self) -> Result<u32, StoreError> {
+        migration::current_version(&self.conn)
+    }
+    /// Fails unless the database schema is exactly at the version this build expects
+    pub fn ensure_migrated(&self) -> Result<(), StoreError> {
+        let current = self.version()?;
+        if current != SCHEMA_VERSION {
+            return Err(StoreError::SchemaMismatch {
+                current,
+                expected: SCHEMA_VERSION,
+            });
+        }
+        Ok(())
+    }
+    /// Shows the database as it was at the given time, until the store is dropped
+    ///
+    /// Temporary views shadow the tables holding history, so every query, joins included,
+    /// only sees what was known then: games not over by then (see `Sport::max_duration`)
+    /// lose their results along with their stat lines and events, and odds, bets and
+    /// favorites recorded later are hidden. Players are placed on the last team they played
+    /// for. The store becomes read-only.
+    pub fn set_as_of(&mut self, at: DateTime<Utc>) -> Result<&mut Self, StoreError> {
+        // Views cannot take parameters, the timestamp is formatted by chrono
+        self.conn.execute_batch(
+            &AS_OF_VIEWS
+                .replace("{ended}", &game_end())
+                .replace("{at}", &at.to_rfc3339()),
+        )?;
+        self.as_of = Some(at);
+        Ok(self)
+    }
+    /// Returns the time the store looks back to, if any
+    pub fn as_of(&self) -> Option<DateTime<Utc>> {
+        self.as_of
+    }
+    /// Fails when the store looks back in time
+    fn ensure_writable(&self) -> Result<(), StoreError> {
+        match self.as_of {
+            Some(as_of) => Err(StoreError::ReadOnly { as_of }),
+            None => Ok(()),
+        }
+    }
+    /// Returns the repository of a record type
+    pub fn repo<T: Record>(&self) -> Repository<'_, T> {
+        Repository::as_of(&self.conn, self.as_of)
+    }
+    /// Runs `f` in a transaction, committed when it succeeds and rolled back otherwise
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use statpack::{model::team::{Player, Team}, store::{repository::Repository, Store}};
+    /// # fn main() -> Result<(), statpack::store::error::StoreError> {
+    /// # let mut store = Store::open_in_memory()?;
+    /// # store.migrate()?;
+    /// # let (teams, players): (Vec<Team>, Vec<Player>) = (vec![], vec![]);
+    /// store.transaction(|tx| {
+    ///     Repository::new(tx).upsert_all(&teams)?;
+    ///     Repository::new(tx).upsert_all(&players)
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transaction<R>(
+        &mut self,
+        f: impl FnOnce(&Transaction) -> Result<R, StoreError>,
+    ) -> Result<R, StoreError> {
+        self.ensure_writable()?;
+        let tx = self.conn.transaction()?;
+        let result = f(&tx)?;
+        tx.commit()?;
+        Ok(result)
+    }
+    /// Runs `f` in a transaction that is always rolled back, so nothing it writes is kept
+    pub fn dry_run<R>(
+        &mut self,
+        f: impl FnOnce(&Transaction) -> Result<R, StoreError>,
+    ) -> Result<R, StoreError> {
+        self.ensure_writable()?;
+        let tx = self.conn.transaction()?;
+        let result = f(&tx)?;
+        tx.rollback()?;
+        Ok(result)
+    }
+}
+
+/// Returns the SQL expression of the `julianday` a row of `games` is sure to be over by,
+/// from the sport of its league
+fn game_end() -> String {
+    let days = |duration: Duration| duration.num_seconds() as f64 / 86_400.0;
+    let sports: String = Sport::ALL
+        .iter()
+        .map(|sport| format!(" WHEN '{}' THEN {}", sport, days(sport.max_duration())))
+        .collect();
+    format!(
+        "(julianday(games.start) + CASE (\
+            SELECT sport FROM main.leagues WHERE leagues.id = games.league\
+        ){} ELSE {} END)",
+        sports,
+        days(UNKNOWN_SPORT_DURATION)
+    )
+}
+
+/// Views shadowing the tables holding history with what was known at `{at}`, `{ended}`
+/// standing for the time a game is over by
+///
+/// Timestamps are compared with `julianday` since chrono writes UTC as `+00:00` in columns
+/// but as `Z` inside JSON.
+const AS_OF_VIEWS: &str = "
+    DROP VIEW IF EXISTS temp.games;
+    DROP VIEW IF EXISTS temp.stat_lines;
+    DROP VIEW IF EXISTS temp.events;
+    DROP VIEW IF EXISTS temp.odds;
+    DROP VIEW IF EXISTS temp.players;
+    DROP VIEW IF EXISTS temp.bets;
+    DROP VIEW IF EXISTS temp.favorites;
+    CREATE TEMP VIEW games AS
+        SELECT id, league, season, home, away, start,
+            CASE WHEN {ended} < julianday('{at}') THEN data
+            ELSE json_remove(
+                CASE WHEN json_extract(data, '$.status') IN ('live', 'final')
+                    THEN json_set(data, '$.status', 'scheduled')
+                    ELSE data END,
+                '$.home_score', '$.away_score', '$.extension'
+            ) END AS data
+        FROM main.games;
+    CREATE TEMP VIEW stat_lines AS
+        SELECT * FROM main.stat_lines WHERE game IN (
+            SELECT id FROM main.games WHERE {ended} < julianday('{at}')
+        );
+    CREATE TEMP VIEW events AS
+        SELECT * FROM main.events WHERE game IN (
+            SELECT id FROM main.games WHERE {ended} < julianday('{at}')
+        );
+    CREATE TEMP VIEW odds AS
+        SELECT * FROM main.odds WHERE julianday(taken_at) < julianday('{at}');
+    CREATE TEMP VIEW players AS
+        SELECT id, COALESCE(played_for, team) AS team, name,
+            CASE WHEN played_for IS NULL THEN data
+            ELSE json_set(data, '$.team', played_for) END AS data
+        FROM (
+            SELECT *, (
+                SELECT stat_lines.team FROM main.stat_lines
+                JOIN main.games ON games.id = stat_lines.game
+                WHERE stat_lines.player = players.id
+                    AND {ended} < julianday('{at}')
+                ORDER BY games.start DESC LIMIT 1
+            ) AS played_for
+            FROM main.players
+        );
+    CREATE TEMP VIEW bets AS
+        SELECT id, game, placed_at,
+            CASE WHEN julianday(json_extract(data, '$.settled_at')) < julianday('{at}') THEN data
+            ELSE json_remove(data, '$.result', '$.settled_at') END AS data
+        FROM main.bets WHERE julianday(placed_at) < julianday('{at}');
+    CREATE TEMP VIEW favorites AS
+        SELECT * FROM main.favorites
+        WHERE julianday(json_extract(data, '$.added_at')) < julianday('{at}');
+";
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::*;
+
+    /// A migrated in-memory store
+    pub(crate) fn store() -> Store {
+        let mut store = Store::open_in_memory().unwrap();
+        store.migrate().unwrap();
+        store
+    }
+}
+
+#[cfg(test)]
+mod repository_tests {
+    use super::{mock::store, *};
+    use crate::model::{
+        game::{mock::game, Game, GameStatus},
+        id::{LeagueId, PlayerId, TeamId},
+        sport::League,
+        stat::StatLine,
+        team::{Player, Team},
+    };
+
+    fn team(name: &str) -> Team {
+        Team {
+            id: TeamId::derive(&["nba", name]),
+            league: LeagueId::new("nba"),
+            name: name.to_string(),
+            abbreviation: None,
+            venue: None,
+            aliases: vec![],
+        }
+    }
+
+    #[test]
+    fn test_upsert_and_get() {
+        let store = store();
+        let mut lakers = team("Lakers");
+        store.repo().upsert(&lakers).unwrap();
+        lakers.abbreviation = Some("LAL".to_string());
+        store.repo().upsert(&lakers).unwrap();
+
+        assert_eq!(store.repo::<Team>().count().unwrap(), 1);
+        assert_eq!(
+            store.repo::<Team>().get("nba:lakers").unwrap(),
+            Some(lakers)
+        );
+        assert_eq!(store.repo::<Team>().get("nba:celtics").unwrap(), None);
+    }
+
+    #[test]
+    fn test_upsert_all_and_find() {
+        let store = store();
+        let teams = [team("Lakers"), team("Celtics")];
+
+        assert_eq!(store.repo().upsert_all(&teams).unwrap(), 2);
+        assert_eq!(
+            store.repo::<Team>().find("name", "Celtics").unwrap(),
+            [teams[1].clone()]
+        );
+        assert!(store.repo::<Team>().delete("nba:lakers").unwrap());
+        assert_eq!(store.repo::<Team>().all().unwrap(), [teams[1].clone()]);
+    }
+
+    #[test]
+    fn test_transaction_rollback() {
+        let mut store = store();
+        let result: Result<(), StoreError> = store.transaction(|tx| {
+            Repository::new(tx).upsert(&team("Lakers"))?;
+            Err(StoreError::SchemaMismatch {
+                current: 0,
+                expected: 1,
+            })
+        });
+
+        assert!(result.is_err());
+        assert_eq!(store.repo::<Team>().count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_games_and_stat_lines() {
+        let store = store();
+        let mut late = game("2", "lal", "bos", Some((100, 90)));
+        late.start += chrono::Duration::days(1);
+        let early = game("1", "bos", "lal", Some((95, 99)));
+        store.repo::<Game>().upsert_all([&late, &early]).unwrap();
+        let lines: Vec<StatLine> = [&late, &early]
+            .iter()
+            .map(|game| StatLine::new(game.id.clone(), PlayerId::new("lebron"), TeamId::new("lal")))
+            .collect();
+        store.repo().upsert_all(&lines).unwrap();
+
+        assert_eq!(
+            store.repo::<Game>().for_team(&TeamId::new("lal")).unwrap(),
+            [early.clone(), late.clone()]
+        );
+        assert_eq!(
+            store
+                .repo::<StatLine>()
+                .for_player("lebron")
+                .unwrap()
+                .iter()
+                .map(|line| line.game.as_str())
+                .collect::<Vec<_>>(),
+            ["1", "2"]
+        );
+    }
+
+    #[test]
+    fn test_as_of() {
+        let mut store = store();
+        let played = game("1", "lal", "bos", Some((100, 90)));
+        let mut next = game("2", "bos", "lal", Some((95, 99)));
+        next.start += chrono::Duration::days(2);
+        store.repo::<Game>().upsert_all([&played, &next]).unwrap();
+        let lines: Vec<StatLine> = [(&played, "lal"), (&next, "bos")]
+            .iter()
+            .map(|(game, team)| {
+                StatLine::new(game.id.clone(), PlayerId::new("lebron"), TeamId::new(*team))
+            })
+            .collect();
+        store.repo().upsert_all(&lines).unwrap();
+        store
+            .repo()
+            .upsert(&Player {
+                id: PlayerId::new("lebron"),
+                name: "LeBron James".to_string(),
+                team: Some(TeamId::new("bos")),
+                position: None,
+                birth_date: None,
+            })
+            .unwrap();
+
+        let at = played.start + chrono::Duration::days(1);
+        store.set_as_of(at).unwrap();
+        assert_eq!(store.as_of(), Some(at));
+        let games = store.repo::<Game>().all().unwrap();
+        assert_eq!(games[0], played);
+        assert_eq!(
+            (games[1].status, games[1].home_score),
+            (GameStatus::Scheduled, None)
+        );
+        assert_eq!(store.repo::<StatLine>().count().unwrap(), 1);
+        assert_eq!(
+            store.repo::<StatLine>().for_player("lebron").unwrap().len(),
+            1
+        );
+        let player = store.repo::<Player>().get("lebron").unwrap().unwrap();
+        assert_eq!(player.team, Some(TeamId::new("lal")));
+        assert_eq!(
+            store.repo::<Player>().find("team", "lal").unwrap(),
+            [player]
+        );
+        assert!(matches!(
+            store.repo().upsert(&team("Lakers")),
+            Err(StoreError::ReadOnly { .. })
+        ));
+        assert!(matches!(
+            store.transaction(|_| Ok(())),
+            Err(StoreError::ReadOnly { .. })
+        ));
+
+        // Looking back further replaces the views
+        store.set_as_of(played.start).unwrap();
+        assert_eq!(store.repo::<StatLine>().count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_as_of_mid_game() {
+        let played = game("1", "lal", "bos", Some((100, 90)));
+        let known_at = |league: Option<Sport>, hours| {
+            let mut store = store();
+            store.repo::<Game>().upsert(&played).unwrap();
+            store
+                .repo()
+                .upsert(&StatLine::new(
+                    played.id.clone(),
+                    PlayerId::new("lebron"),
+                    TeamId::new("lal"),
+                ))
+                .unwrap();
+            if let Some(sport) = league {
+                store
+                    .repo()
+                    .upsert(&League {
+                        id: LeagueId::new("nba"),
+                        sport,
+                        name: "NBA".to_string(),
+                        country: None,
+                        rules: None,
+                    })
+                    .unwrap();
+            }
+            store
+                .set_as_of(played.start + chrono::Duration::hours(hours))
+                .unwrap();
+            let game = store.repo::<Game>().get("1").unwrap().unwrap();
+            let lines = store.repo::<StatLine>().count().unwrap();
+            (game.status, game.home_score, lines)
+        };
+
+        // Basketball games are over within 3 hours
+        let basketball = Some(Sport::Basketball);
+        assert_eq!(known_at(basketball, 1), (GameStatus::Scheduled, None, 0));
+        assert_eq!(known_at(basketball, 4), (GameStatus::Final, Some(100), 1));
+        // Games of leagues not stored are given 6 hours
+        assert_eq!(known_at(None, 5), (GameStatus::Scheduled, None, 0));
+        assert_eq!(known_at(None, 7), (GameStatus::Final, Some(100), 1));
+    }
+
+    #[test]
+    fn test_as_of_postponed() {
+        let mut store = store();
+        let mut postponed = game("1", "lal", "bos", None);
+        postponed.status = GameStatus::Postponed;
+        let mut cancelled = game("2", "bos", "lal", None);
+        cancelled.status = GameStatus::Cancelled;
+        store
+            .repo::<Game>()
+            .upsert_all([&postponed, &cancelled])
+            .unwrap();
+
+        store
+            .set_as_of(postponed.start - chrono::Duration::days(1))
+            .unwrap();
+        let games = store.repo::<Game>().all().unwrap();
+        assert_eq!(games, [postponed, cancelled]);
+    }
+
+    #[test]
+    fn test_ensure_migrated() {
+        let store = Store::open_in_memory().unwrap();
+
+        assert!(matches!(
+            store.ensure_migrated(),
+            Err(StoreError::SchemaMismatch {
+                current: 0,
+                expected: SCHEMA_VERSION
+            })
+        ));
+    }
+}
@@ -0,0 +1,187 @@
+use crate::model::{
+    bet::Bet,
+    favorite::Favorite,
+    game::{Event, Game},
+    odds::OddsSnapshot,
+    sport::{League, Season},
+    stat::StatLine,
+    team::{Player, Team, Venue},
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// The `Record` trait maps a model type onto its table.
+///
+/// Records are stored whole as JSON in a `data` column, next to the few `COLUMNS` that
+/// are copied out of the record so they can be indexed and filtered on.
+pub trait Record: Serialize + DeserializeOwned {
+    /// Name of the table holding the records
+    const TABLE: &'static str;
+    /// Indexed columns besides `id` and `data`, in the order `values` returns them
+    const COLUMNS: &'static [&'static str];
+    /// Returns the stable primary key of the record
+    fn key(&self) -> String;
+    /// Returns the values of `COLUMNS` for the record
+    fn values(&self) -> Vec<Option<String>>;
+}
+
+impl Record for League {
+    const TABLE: &'static str = "leagues";
+    const COLUMNS: &'static [&'static str] = &["sport"];
+
+    fn key(&self) -> String {
+        self.id.to_string()
+    }
+    fn values(&self) -> Vec<Option<String>> {
+        vec![Some(self.sport.to_string())]
+    }
+}
+
+impl Record for Season {
+    const TABLE: &'static str = "seasons";
+    const COLUMNS: &'static [&'static str] = &["league"];
+
+    fn key(&self) -> String {
+        self.id.to_string()
+    }
+    fn values(&self) -> Vec<Option<String>> {
+        vec![Some(self.league.to_string())]
+    }
+}
+
+impl Record for Venue {
+    const TABLE: &'static str = "venues";
+    const COLUMNS: &'static [&'static str] = &[];
+
+    fn key(&self) -> String {
+        self.id.to_string()
+    }
+    fn values(&self) -> Vec<Option<String>> {
+        vec![]
+    }
+}
+
+impl Record for Team {
+    const TABLE: &'static str = "teams";
+    const COLUMNS: &'static [&'static str] = &["league", "name"];
+
+    fn key(&self) -> String {
+        self.id.to_string()
+    }
+    fn values(&self) -> Vec<Option<String>> {
+        vec![Some(self.league.to_string()), Some(self.name.clone())]
+    }
+}
+
+impl Record for Player {
+    const TABLE: &'static str = "players";
+    const COLUMNS: &'static [&'static str] = &["team", "name"];
+
+    fn key(&self) -> String {
+        self.id.to_string()
+    }
+    fn values(&self) -> Vec<Option<String>> {
+        vec![
+            self.team.as_ref().map(ToString::to_string),
+            Some(self.name.clone()),
+        ]
+    }
+}
+
+impl Record for Favorite {
+    const TABLE: &'static str = "favorites";
+    const COLUMNS: &'static [&'static str] = &["kind"];
+
+    fn key(&self) -> String {
+        format!("{}|{}", self.kind, self.id)
+    }
+    fn values(&self) -> Vec<Option<String>> {
+        vec![Some(self.kind.to_string())]
+    }
+}
+
+impl Record for Bet {
+    const TABLE: &'static str = "bets";
+    const COLUMNS: &'static [&'static str] = &["game", "placed_at"];
+
+    /// Keys are zero-padded so bets are ordered by number
+    fn key(&self) -> String {
+        format!("{:08}", self.id)
+    }
+    fn values(&self) -> Vec<Option<String>> {
+        vec![
+            Some(self.game.to_string()),
+            Some(self.placed_at.to_rfc3339()),
+        ]
+    }
+}
+
+impl Record for Game {
+    const TABLE: &'static str = "games";
+    const COLUMNS: &'static [&'static str] = &["league", "season", "home", "away", "start"];
+
+    fn key(&self) -> String {
+        self.id.to_string()
+    }
+    fn values(&self) -> Vec<Option<String>> {
+        vec![
+            Some(self.league.to_string()),
+            Some(self.season.to_string()),
+            Some(self.home.to_string()),
+            Some(self.away.to_string()),
+            Some(self.start.to_rfc3339()),
+        ]
+    }
+}
+
+impl Record for Event {
+    const TABLE: &'static str = "events";
+    const COLUMNS: &'static [&'static str] = &["game"];
+
+    fn key(&self) -> String {
+        self.id.to_string()
+    }
+    fn values(&self) -> Vec<Option<String>> {
+        vec![Some(self.game.to_string())]
+    }
+}
+
+impl Record for StatLine {
+    const TABLE: &'static str = "stat_lines";
+    const COLUMNS: &'static [&'static str] = &["game", "player", "team"];
+
+    /// A player has a single line per game
+    fn key(&self) -> String {
+        format!("{}|{}", self.game, self.player)
+    }
+    fn values(&self) -> Vec<Option<String>> {
+        vec![
+            Some(self.game.to_string()),
+            Some(self.player.to_string()),
+            Some(self.team.to_string()),
+        ]
+    }
+}
+
+impl Record for OddsSnapshot {
+    const TABLE: &'static str = "odds";
+    const COLUMNS: &'static [&'static str] = &["game", "book", "taken_at"];
+
+    /// A book prices a selection once per snapshot time
+    fn key(&self) -> String {
+        format!(
+            "{}|{}|{}|{}|{}",
+            self.game,
+            self.book,
+            self.market,
+            self.selection.name(),
+            self.taken_at.to_rfc3339()
+        )
+    }
+    fn values(&self) -> Vec<Option<String>> {
+        vec![
+            Some(self.game.to_string()),
+            Some(self.book.clone()),
+            Some(self.taken_at.to_rfc3339()),
+        ]
+    }
+}
@@ -0,0 +1,197 @@
+use super::{error::StoreError, record::Record};
+use crate::model::{game::Game, id::TeamId, odds::OddsSnapshot, stat::StatLine};
+use chrono::{DateTime, Utc};
+use rusqlite::{params_from_iter, types::Value, Connection};
+use std::marker::PhantomData;
+
+/// A typed view over the table of a `Record`
+///
+/// Repositories only borrow a connection, so they work the same on a `Store` and inside
+/// one of its transactions.
+pub struct Repository<'c, T> {
+    conn: &'c Connection,
+    /// Set when the store looks back in time, which makes the repository read-only
+    as_of: Option<DateTime<Utc>>,
+    record: PhantomData<T>,
+}
+impl<'c, T: Record> Repository<'c, T> {
+    pub fn new(conn: &'c Connection) -> Self {
+        Self {
+            conn,
+            as_of: None,
+            record: PhantomData,
+        }
+    }
+    /// Creates a repository over a store showing the database as it was at `as_of`
+    pub(super) fn as_of(conn: &'c Connection, as_of: Option<DateTime<Utc>>) -> Self {
+        Self {
+            as_of,
+            ..Self::new(conn)
+        }
+    }
+    /// Fails when the repository looks back in time, as history cannot be rewritten
+    fn ensure_writable(&self) -> Result<(), StoreError> {
+        match self.as_of {
+            Some(as_of) => Err(StoreError::ReadOnly { as_of }),
+            None => Ok(()),
+        }
+    }
+    /// Inserts the record or replaces the stored one with the same key
+    pub fn upsert(&self, record: &T) -> Result<(), StoreError> {
+        self.ensure_writable()?;
+        let mut columns = vec!["id", "data"];
+        columns.extend(T::COLUMNS);
+        let placeholders: Vec<String> = (1..=columns.len()).map(|i| format!("?{}", i)).collect();
+        let updates: Vec<String> = columns[1..]
+            .iter()
+            .map(|column| format!("{0} = excluded.{0}", column))
+            .collect();
+        let sql = format!(
+            "INSERT INTO {} ({}) VALUES ({}) ON CONFLICT(id) DO UPDATE SET {}",
+            T::TABLE,
+            columns.join(", "),
+            placeholders.join(", "),
+            updates.join(", ")
+        );
+
+        let mut values = vec![Some(record.key()), Some(serde_json::to_string(record)?)];
+        values.extend(record.values());
+        self.conn
+            .prepare_cached(&sql)?
+            .execute(params_from_iter(values))?;
+        Ok(())
+    }
+    /// Upserts every record atomically, either all of them are stored or none are
+    ///
+    /// ## Returns
+    ///
+    /// The number of records upserted.
+    pub fn upsert_all<'r>(
+        &self,
+        records: impl IntoIterator<Item = &'r T>,
+    ) -> Result<usize, StoreError>
+    where
+        T: 'r,
+    {
+        self.ensure_writable()?;
+        // A savepoint nests inside an outer transaction where a plain transaction cannot
+        self.conn.execute_batch("SAVEPOINT upsert_all")?;
+        let mut count = 0;
+        for record in records {
+            if let Err(error) = self.upsert(record) {
+                self.conn
+                    .execute_batch("ROLLBACK TO upsert_all; RELEASE upsert_all")?;
+                return Err(error);
+            }
+            count += 1;
+        }
+        self.conn.execute_batch("RELEASE upsert_all")?;
+        Ok(count)
+    }
+    /// Returns the record with the given key
+    pub fn get(&self, key: &str) -> Result<Option<T>, StoreError> {
+        Ok(self
+            .query(
+                &format!("SELECT data FROM {} WHERE id = ?1", T::TABLE),
+                &[key],
+            )?
+            .pop())
+    }
+    /// Returns every record ordered by key
+    pub fn all(&self) -> Result<Vec<T>, StoreError> {
+        self.query(&format!("SELECT data FROM {} ORDER BY id", T::TABLE), &[])
+    }
+    /// Returns the records whose indexed `column` equals `value`
+    pub fn find(&self, column: &str, value: &str) -> Result<Vec<T>, StoreError> {
+        assert!(
+            T::COLUMNS.contains(&column),
+            "column is not indexed for this record"
+        );
+        self.query(
+            &format!(
+                "SELECT data FROM {} WHERE {} = ?1 ORDER BY id",
+                T::TABLE,
+                column
+            ),
+            &[value],
+        )
+    }
+    /// Returns the number of stored records
+    pub fn count(&self) -> Result<usize, StoreError> {
+        Ok(self
+            .conn
+            .query_row(&format!("SELECT COUNT(*) FROM {}", T::TABLE), [], |row| {
+                row.get(0)
+            })?)
+    }
+    /// Deletes the record with the given key
+    ///
+    /// ## Returns
+    ///
+    /// `true` if a record was deleted.
+    pub fn delete(&self, key: &str) -> Result<bool, StoreError> {
+        self.ensure_writable()?;
+        Ok(self
+            .conn
+            .execute(&format!("DELETE FROM {} WHERE id = ?1", T::TABLE), [key])?
+            > 0)
+    }
+    /// Runs a query selecting the `data` column and decodes the records
+    pub(super) fn query(&self, sql: &str, params: &[&str]) -> Result<Vec<T>, StoreError> {
+        let mut statement = self.conn.prepare_cached(sql)?;
+        let rows = statement.query_map(
+            params_from_iter(params.iter().map(|param| Value::Text(param.to_string()))),
+            |row| row.get::<_, String>(0),
+        )?;
+
+        let mut records = vec![];
+        for data in rows {
+            records.push(serde_json::from_str(&data?)?);
+        }
+        Ok(records)
+    }
+}
+impl Repository<'_, Game> {
+    /// Returns the games a team played home or away, oldest first
+    pub fn for_team(&self, team: &TeamId) -> Result<Vec<Game>, StoreError> {
+        self.query(
+            "SELECT data FROM games WHERE home = ?1 OR away = ?1 ORDER BY start, id",
+            &[team.as_str()],
+        )
+    }
+    /// Returns the games of a season, oldest first
+    pub fn for_season(&self, season: &str) -> Result<Vec<Game>, StoreError> {
+        self.query(
+            "SELECT data FROM games WHERE season = ?1 ORDER BY start, id",
+            &[season],
+        )
+    }
+    /// Returns the games starting in `[from, to)`, oldest first
+    pub fn between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<Game>, StoreError> {
+        self.query(
+            "SELECT data FROM games WHERE start >= ?1 AND start < ?2 ORDER BY start, id",
+            &[&from.to_rfc3339(), &to.to_rfc3339()],
+        )
+    }
+}
+impl Repository<'_, StatLine> {
+    /// Returns every stat line of a player, ordered by game start, oldest first
+    pub fn for_player(&self, player: &str) -> Result<Vec<StatLine>, StoreError> {
+        self.query(
+            "SELECT stat_lines.data FROM stat_lines
+                JOIN games ON games.id = stat_lines.game
+                WHERE stat_lines.player = ?1
+                ORDER BY games.start, games.id",
+            &[player],
+        )
+    }
+}
+impl Repository<'_, OddsSnapshot> {
+    /// Returns every snapshot of a game, oldest first
+    pub fn for_game(&self, game: &str) -> Result<Vec<OddsSnapshot>, StoreError> {
+        self.query(
+            "SELECT data FROM odds WHERE game = ?1 ORDER BY taken_at, id",
+            &[game],
+        )
+    }
+}
@@ -1,45 +1,53 @@
+pub mod fuzzy;
+pub mod prefix;
+
 /// Performs a binary search on a sorted list of items, comparing the items' keys
-/// (extracted using the provided `key_extractor` function) to the given `search_key`.
+/// (borrowed using the provided `key_extractor` function) to the given `search_key`.
 /// Depending on the value of the `right` parameter, it returns the index of the first
 /// or last occurrence of the matching key.
+///
+/// The extractor may return anything that borrows as a `str` (`&str`, `Cow<str>`, ...),
+/// so lookups over large lists stay allocation-free when the key is already stored.
 /// ## Examples
 ///
 /// ```
+/// # use statpack::utils::search::bisect_search_str_key;
 /// let items = vec!["apple", "banana", "cherry"];
-/// let index = bisect_search_str_key(&items, "banana", false, |item, _| item.to_string());
-/// assert_eq!(index, 1);
+/// let index = bisect_search_str_key(&items, "banana", false, |item| *item);
+/// assert_eq!(index, Some(1));
 /// ```
 /// ## Returns
-/// Returns the `index` of the found option or `-1` if the option is not found.
-pub fn bisect_search_str_key<'a, U, F>(
+/// Returns the `index` of the found item or `None` if no key starts with `search_key`.
+pub fn bisect_search_str_key<'a, U, F, K>(
     list: &'a [U],
-    search_key: &'a str,
+    search_key: &str,
     right: bool,
     key_extractor: F,
-) -> i32
+) -> Option<usize>
 where
-    F: Fn(&'a U, &str) -> String,
+    F: Fn(&'a U) -> K,
+    K: AsRef<str>,
 {
-    let mut l = 0i32;
-    let mut r = list.len() as i32 - 1i32;
-    let mut index = -1;
+    let mut l = 0;
+    let mut r = list.len();
+    let mut index = None;
 
-    while l <= r {
-        let mid: usize = ((l + r) / 2) as usize;
-        let mid_i32 = mid as i32;
-        let item_key = key_extractor(&list[mid], search_key);
+    while l < r {
+        let mid = l + (r - l) / 2;
+        let item_key = key_extractor(&list[mid]);
+        let item_key = item_key.as_ref();
 
         if item_key.starts_with(search_key) {
-            index = mid_i32;
+            index = Some(mid);
             if right {
-                l = mid_i32 + 1;
+                l = mid + 1;
             } else {
-                r = mid_i32 - 1;
+                r = mid;
             }
-        } else if item_key < search_key.to_string() {
-            l = mid_i32 + 1;
+        } else if item_key < search_key {
+            l = mid + 1;
         } else {
-            r = mid_i32 - 1;
+            r = mid;
         }
     }
 
@@ -55,8 +63,8 @@ mod bisect_search_str_key_tests {
         name: String,
     }
 
-    fn key_extractor(item: &TestStruct, _search_key: &str) -> String {
-        item.name.clone()
+    fn key_extractor(item: &TestStruct) -> &str {
+        &item.name
     }
 
     #[test]
@@ -81,11 +89,11 @@ mod bisect_search_str_key_tests {
 
         assert_eq!(
             bisect_search_str_key(&list, "banana", false, key_extractor),
-            1
+            Some(1)
         );
         assert_eq!(
             bisect_search_str_key(&list, "cherry", true, key_extractor),
-            2
+            Some(2)
         );
     }
 
@@ -111,11 +119,11 @@ mod bisect_search_str_key_tests {
 
         assert_eq!(
             bisect_search_str_key(&list, "grape", false, key_extractor),
-            -1
+            None
         );
         assert_eq!(
             bisect_search_str_key(&list, "apricot", true, key_extractor),
-            -1
+            None
         );
     }
 
@@ -142,8 +150,14 @@ mod bisect_search_str_key_tests {
             },
         ];
 
-        assert_eq!(bisect_search_str_key(&list, "ap", false, key_extractor), 0);
-        assert_eq!(bisect_search_str_key(&list, "ap", true, key_extractor), 1);
+        assert_eq!(
+            bisect_search_str_key(&list, "ap", false, key_extractor),
+            Some(0)
+        );
+        assert_eq!(
+            bisect_search_str_key(&list, "ap", true, key_extractor),
+            Some(1)
+        );
     }
 
     #[test]
@@ -152,7 +166,7 @@ mod bisect_search_str_key_tests {
 
         assert_eq!(
             bisect_search_str_key(&list, "apple", false, key_extractor),
-            -1
+            None
         );
     }
 
@@ -164,11 +178,11 @@ mod bisect_search_str_key_tests {
 
         assert_eq!(
             bisect_search_str_key(&list, "apple", false, key_extractor),
-            0
+            Some(0)
         );
         assert_eq!(
             bisect_search_str_key(&list, "apple", true, key_extractor),
-            0
+            Some(0)
         );
     }
 
@@ -180,7 +194,7 @@ mod bisect_search_str_key_tests {
 
         assert_eq!(
             bisect_search_str_key(&list, "banana", false, key_extractor),
-            -1
+            None
         );
     }
 
@@ -206,11 +220,55 @@ mod bisect_search_str_key_tests {
 
         assert_eq!(
             bisect_search_str_key(&list, "banana", false, key_extractor),
-            1
+            Some(1)
         );
         assert_eq!(
             bisect_search_str_key(&list, "banana", true, key_extractor),
-            3
+            Some(3)
         );
     }
 }
+
+#[cfg(test)]
+mod bisect_search_str_key_borrow_tests {
+    use super::*;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_bisect_search_str_key_cow_extractor() {
+        let list = vec!["Apple", "apricot", "banana"];
+        fn key_extractor<'a>(item: &&'a str) -> Cow<'a, str> {
+            if item.chars().any(char::is_uppercase) {
+                Cow::Owned(item.to_lowercase())
+            } else {
+                Cow::Borrowed(item)
+            }
+        }
+
+        assert_eq!(
+            bisect_search_str_key(&list, "ap", false, key_extractor),
+            Some(0)
+        );
+        assert_eq!(
+            bisect_search_str_key(&list, "ap", true, key_extractor),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_bisect_search_str_key_matches_partition_point() {
+        let list = vec!["ab", "abc", "abd", "b", "ba", "c"];
+
+        for prefix in ["a", "ab", "abc", "b", "bb", "c", "d", ""] {
+            let left = list.partition_point(|item| *item < prefix);
+            let right = left + list[left..].partition_point(|item| item.starts_with(prefix));
+            let expected = (left < right).then(|| (left, right - 1));
+
+            assert_eq!(
+                bisect_search_str_key(&list, prefix, false, |item| *item)
+                    .zip(bisect_search_str_key(&list, prefix, true, |item| *item)),
+                expected
+            );
+        }
+    }
+}
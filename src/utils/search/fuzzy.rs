@@ -0,0 +1,377 @@
+/// Computes the Levenshtein edit distance (insertions, deletions and substitutions)
+/// between two strings, counted in characters.
+///
+/// ## Examples
+///
+/// ```
+/// # use statpack::utils::search::fuzzy::levenshtein;
+/// assert_eq!(levenshtein("kitten", "sitting"), 3);
+/// ```
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let cost = usize::from(a_char != *b_char);
+            current[j + 1] = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Computes the Damerau-Levenshtein edit distance between two strings, counted in characters.
+///
+/// Same as `levenshtein` but a transposition of two adjacent characters ("jmaes" for
+/// "james") counts as a single edit (optimal string alignment variant).
+///
+/// ## Examples
+///
+/// ```
+/// # use statpack::utils::search::fuzzy::damerau_levenshtein;
+/// assert_eq!(damerau_levenshtein("jmaes", "james"), 1);
+/// ```
+pub fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j - 1] + cost)
+                .min(rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+/// Turns an edit distance into a similarity score between `0.0` and `1.0`
+fn similarity(distance: usize, a: &str, b: &str) -> f64 {
+    let len = a.chars().count().max(b.chars().count());
+    if len == 0 {
+        return 1.0;
+    }
+    1.0 - distance as f64 / len as f64
+}
+
+/// Scores how well a single query token matches a candidate token.
+///
+/// Prefixes ("man" for "manchester") and abbreviations keeping the first letter
+/// ("utd" for "united") score above plain typos.
+fn token_similarity(query: &str, candidate: &str) -> f64 {
+    if query == candidate {
+        return 1.0;
+    }
+    let query_len = query.chars().count();
+    let candidate_len = candidate.chars().count();
+
+    if candidate.starts_with(query) {
+        return 0.9 + 0.1 * query_len as f64 / candidate_len as f64;
+    }
+    if query_len > 1 && query.chars().next() == candidate.chars().next() {
+        let mut candidate_chars = candidate.chars();
+        if query
+            .chars()
+            .all(|char| candidate_chars.any(|other| other == char))
+        {
+            return 0.8;
+        }
+    }
+
+    similarity(damerau_levenshtein(query, candidate), query, candidate)
+}
+
+/// Computes a token-based similarity score between `0.0` and `1.0`, ignoring case.
+///
+/// Both strings are split on anything that is not alphanumeric and every query token is
+/// paired with its best scoring candidate token, so word order and abbreviations matter
+/// far less than with a plain edit distance.
+///
+/// ## Examples
+///
+/// ```
+/// # use statpack::utils::search::fuzzy::token_score;
+/// assert!(token_score("man utd", "manchester united") > token_score("man utd", "manchester city"));
+/// ```
+pub fn token_score(query: &str, candidate: &str) -> f64 {
+    case_sensitive_token_score(&query.to_lowercase(), &candidate.to_lowercase())
+}
+
+/// Computes the score of `token_score` without folding case
+fn case_sensitive_token_score(query: &str, candidate: &str) -> f64 {
+    let tokenize = |string: &str| -> Vec<String> {
+        string
+            .split(|char: char| !char.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let query_tokens = tokenize(query);
+    let candidate_tokens = tokenize(candidate);
+
+    if query_tokens.is_empty() || candidate_tokens.is_empty() {
+        return 0.0;
+    }
+
+    let total: f64 = query_tokens
+        .iter()
+        .map(|query_token| {
+            candidate_tokens
+                .iter()
+                .map(|candidate_token| token_similarity(query_token, candidate_token))
+                .fold(0.0, f64::max)
+        })
+        .sum();
+    total / query_tokens.len() as f64
+}
+
+/// Represents the scoring strategy used by a `FuzzyMatcher`
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum FuzzyAlgorithm {
+    /// Normalized `levenshtein` distance over the whole string
+    Levenshtein,
+    /// Normalized `damerau_levenshtein` distance over the whole string
+    #[default]
+    DamerauLevenshtein,
+    /// Per-word scoring through `token_score`
+    Token,
+}
+
+/// Represents a candidate that passed the `FuzzyMatcher` threshold
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch<'a> {
+    /// The matched candidate
+    pub key: &'a str,
+    /// The position of the candidate in the searched list
+    pub id: usize,
+    /// Similarity between `0.0` and `1.0`, higher is better
+    pub score: f64,
+}
+
+/// Ranks candidates by similarity to a query
+///
+/// ## Examples
+///
+/// ```
+/// # use statpack::utils::search::fuzzy::FuzzyMatcher;
+/// let matches = FuzzyMatcher::new()
+///     .set_threshold(0.8)
+///     .rank("lebron jmes", ["lebron james", "kevin durant"]);
+/// assert_eq!(matches[0].key, "lebron james");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatcher {
+    algorithm: FuzzyAlgorithm,
+    threshold: f64,
+    limit: Option<usize>,
+    case_sensitive: bool,
+}
+impl Default for FuzzyMatcher {
+    fn default() -> Self {
+        Self {
+            algorithm: FuzzyAlgorithm::default(),
+            threshold: 0.6,
+            limit: None,
+            case_sensitive: false,
+        }
+    }
+}
+impl FuzzyMatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Specifies the scoring strategy (defaults to `DamerauLevenshtein`)
+    pub fn set_algorithm(&mut self, algorithm: FuzzyAlgorithm) -> &mut Self {
+        self.algorithm = algorithm;
+        self
+    }
+    /// Specifies the minimum score a candidate needs to be returned (defaults to `0.6`)
+    pub fn set_threshold(&mut self, threshold: f64) -> &mut Self {
+        self.threshold = threshold;
+        self
+    }
+    /// Specifies the maximum number of ranked candidates returned
+    pub fn set_limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+    /// Compares candidates without folding case first
+    pub fn set_case_sensitive(&mut self) -> &mut Self {
+        self.case_sensitive = true;
+        self
+    }
+    /// Scores a single candidate against the query
+    pub fn score(&self, query: &str, candidate: &str) -> f64 {
+        let (query, candidate) = if self.case_sensitive {
+            (query.to_string(), candidate.to_string())
+        } else {
+            (query.to_lowercase(), candidate.to_lowercase())
+        };
+
+        match self.algorithm {
+            FuzzyAlgorithm::Levenshtein => {
+                similarity(levenshtein(&query, &candidate), &query, &candidate)
+            }
+            FuzzyAlgorithm::DamerauLevenshtein => {
+                similarity(damerau_levenshtein(&query, &candidate), &query, &candidate)
+            }
+            FuzzyAlgorithm::Token => case_sensitive_token_score(&query, &candidate),
+        }
+    }
+    /// Scores every candidate and keeps the ones meeting the threshold.
+    ///
+    /// ## Returns
+    ///
+    /// The matches ordered from best to worst score, ties broken alphabetically.
+    pub fn rank<'a>(
+        &self,
+        query: &str,
+        candidates: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<FuzzyMatch<'a>> {
+        let mut matches: Vec<FuzzyMatch> = candidates
+            .into_iter()
+            .enumerate()
+            .map(|(id, key)| FuzzyMatch {
+                key,
+                id,
+                score: self.score(query, key),
+            })
+            .filter(|found| found.score >= self.threshold)
+            .collect();
+
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.key.cmp(b.key)));
+        if let Some(limit) = self.limit {
+            matches.truncate(limit);
+        }
+        matches
+    }
+}
+
+#[cfg(test)]
+mod levenshtein_tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("james", "jmes"), 1);
+        assert_eq!(levenshtein("jmaes", "james"), 2);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("abc", ""), 3);
+        assert_eq!(levenshtein("zoë", "zoe"), 1);
+    }
+
+    #[test]
+    fn test_damerau_levenshtein() {
+        assert_eq!(damerau_levenshtein("kitten", "sitting"), 3);
+        assert_eq!(damerau_levenshtein("jmaes", "james"), 1);
+        assert_eq!(damerau_levenshtein("ca", "abc"), 3);
+        assert_eq!(damerau_levenshtein("", ""), 0);
+    }
+}
+
+#[cfg(test)]
+mod token_score_tests {
+    use super::*;
+
+    #[test]
+    fn test_token_score_abbreviation() {
+        let united = token_score("man utd", "Manchester United");
+        let city = token_score("man utd", "Manchester City");
+
+        assert!(united > 0.8);
+        assert!(united > city);
+    }
+
+    #[test]
+    fn test_token_score_word_order() {
+        assert_eq!(token_score("james lebron", "LeBron James"), 1.0);
+    }
+
+    #[test]
+    fn test_token_score_empty() {
+        assert_eq!(token_score("", "lebron james"), 0.0);
+        assert_eq!(token_score("lebron", "--"), 0.0);
+    }
+}
+
+#[cfg(test)]
+mod fuzzy_matcher_tests {
+    use super::*;
+
+    const PLAYERS: [&str; 4] = [
+        "LeBron James",
+        "Kevin Durant",
+        "James Harden",
+        "LeBron James Jr",
+    ];
+
+    #[test]
+    fn test_rank_typo() {
+        let matches = FuzzyMatcher::new().rank("lebron jmes", PLAYERS);
+
+        assert_eq!(matches[0].key, "LeBron James");
+        assert_eq!(matches[0].id, 0);
+        assert!(matches.iter().all(|found| found.key != "Kevin Durant"));
+    }
+
+    #[test]
+    fn test_rank_token_threshold_and_limit() {
+        let matches = FuzzyMatcher::new()
+            .set_algorithm(FuzzyAlgorithm::Token)
+            .set_threshold(0.9)
+            .set_limit(2)
+            .rank("james", PLAYERS);
+
+        assert_eq!(
+            matches.iter().map(|found| found.key).collect::<Vec<_>>(),
+            ["James Harden", "LeBron James"]
+        );
+    }
+
+    #[test]
+    fn test_rank_case_sensitive() {
+        let matcher = FuzzyMatcher::new();
+        let mut case_sensitive = FuzzyMatcher::new();
+        case_sensitive.set_case_sensitive();
+
+        assert_eq!(matcher.score("LEBRON", "lebron"), 1.0);
+        assert_eq!(case_sensitive.score("LEBRON", "lebron"), 0.0);
+
+        case_sensitive.set_algorithm(FuzzyAlgorithm::Token);
+        assert_eq!(case_sensitive.score("LEBRON james", "lebron james"), 0.5);
+        assert_eq!(
+            matcher
+                .clone()
+                .set_algorithm(FuzzyAlgorithm::Token)
+                .score("LEBRON james", "lebron james"),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_rank_no_match() {
+        let matches = FuzzyMatcher::new().rank("zlatan", PLAYERS);
+
+        assert!(matches.is_empty());
+    }
+}
@@ -0,0 +1,271 @@
+use std::ops::Range;
+
+/// Finds the contiguous range of items in a sorted `list` whose key (borrowed through
+/// `key_extractor`) starts with `prefix`.
+///
+/// The list must already be sorted by the same key the extractor returns.
+///
+/// ## Examples
+///
+/// ```
+/// # use statpack::utils::search::prefix::prefix_range;
+/// let items = vec!["apple", "apricot", "banana"];
+/// let range = prefix_range(&items, "ap", |item| item);
+/// assert_eq!(range, Some(0..2));
+/// ```
+/// ## Returns
+/// Returns the `range` of matching items or `None` if no key starts with `prefix`.
+pub fn prefix_range<T, F>(list: &[T], prefix: &str, key_extractor: F) -> Option<Range<usize>>
+where
+    F: Fn(&T) -> &str,
+{
+    let start = list.partition_point(|item| key_extractor(item) < prefix);
+    let end = start + list[start..].partition_point(|item| key_extractor(item).starts_with(prefix));
+
+    if start == end {
+        None
+    } else {
+        Some(start..end)
+    }
+}
+
+/// The `PrefixIndex` trait provides prefix lookups over a fixed set of borrowed keys.
+///
+/// Keys are held in sorted order and every key remembers the position it was inserted at,
+/// so callers can map matches back to their own records (options, players, teams...).
+/// Keys are compared byte-wise; normalize them (e.g. lowercase) before indexing when a
+/// case-insensitive lookup is needed.
+pub trait PrefixIndex<'a> {
+    /// Returns the keys held by the index in sorted order
+    fn keys(&self) -> &[&'a str];
+    /// Returns the insertion positions of the keys, aligned with `keys`
+    fn ids(&self) -> &[usize];
+    /// Searches for the sorted range of keys starting with `prefix`.
+    ///
+    /// ## Returns
+    ///
+    /// The `range` into `keys`/`ids` or `None` if no key starts with `prefix`.
+    fn prefix_range(&self, prefix: &str) -> Option<Range<usize>>;
+    /// Returns the keys starting with `prefix`
+    fn matches(&self, prefix: &str) -> &[&'a str] {
+        self.prefix_range(prefix)
+            .map_or(&[], |range| &self.keys()[range])
+    }
+    /// Returns the insertion positions of the keys starting with `prefix`
+    fn match_ids(&self, prefix: &str) -> &[usize] {
+        self.prefix_range(prefix)
+            .map_or(&[], |range| &self.ids()[range])
+    }
+}
+
+/// Sorts keys alongside their insertion positions
+fn sort_keys<'a>(keys: impl IntoIterator<Item = &'a str>) -> (Vec<&'a str>, Vec<usize>) {
+    let mut entries: Vec<(&'a str, usize)> = keys
+        .into_iter()
+        .enumerate()
+        .map(|(id, key)| (key, id))
+        .collect();
+    entries.sort();
+    entries.into_iter().unzip()
+}
+
+/// A `PrefixIndex` backed by a sorted array and binary search.
+///
+/// Cheapest to build and smallest in memory; every lookup costs two binary searches.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SortedPrefixIndex<'a> {
+    keys: Vec<&'a str>,
+    ids: Vec<usize>,
+}
+impl<'a> SortedPrefixIndex<'a> {
+    pub fn new(keys: impl IntoIterator<Item = &'a str>) -> Self {
+        let (keys, ids) = sort_keys(keys);
+        Self { keys, ids }
+    }
+}
+impl<'a> PrefixIndex<'a> for SortedPrefixIndex<'a> {
+    fn keys(&self) -> &[&'a str] {
+        &self.keys
+    }
+    fn ids(&self) -> &[usize] {
+        &self.ids
+    }
+    fn prefix_range(&self, prefix: &str) -> Option<Range<usize>> {
+        prefix_range(&self.keys, prefix, |key| key)
+    }
+}
+
+/// A node of the `TriePrefixIndex` covering every sorted key below it
+#[derive(Default, Debug, Clone, PartialEq)]
+struct TrieNode {
+    children: Vec<(char, usize)>,
+    range: Range<usize>,
+}
+
+/// A `PrefixIndex` backed by a character trie.
+///
+/// Every node stores the sorted range of keys below it, so a lookup only walks the
+/// characters of the prefix no matter how many keys are indexed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriePrefixIndex<'a> {
+    keys: Vec<&'a str>,
+    ids: Vec<usize>,
+    nodes: Vec<TrieNode>,
+}
+impl<'a> TriePrefixIndex<'a> {
+    pub fn new(keys: impl IntoIterator<Item = &'a str>) -> Self {
+        let (keys, ids) = sort_keys(keys);
+        let mut nodes = vec![TrieNode {
+            children: vec![],
+            range: 0..keys.len(),
+        }];
+
+        // Keys arrive sorted, so a matching child can only ever be the last one added
+        for (index, key) in keys.iter().enumerate() {
+            let mut node = 0;
+            for char in key.chars() {
+                let child = match nodes[node].children.last() {
+                    Some(&(last, child)) if last == char => child,
+                    _ => {
+                        nodes.push(TrieNode {
+                            children: vec![],
+                            range: index..index,
+                        });
+                        let child = nodes.len() - 1;
+                        nodes[node].children.push((char, child));
+                        child
+                    }
+                };
+                nodes[child].range.end = index + 1;
+                node = child;
+            }
+        }
+
+        Self { keys, ids, nodes }
+    }
+}
+impl<'a> PrefixIndex<'a> for TriePrefixIndex<'a> {
+    fn keys(&self) -> &[&'a str] {
+        &self.keys
+    }
+    fn ids(&self) -> &[usize] {
+        &self.ids
+    }
+    fn prefix_range(&self, prefix: &str) -> Option<Range<usize>> {
+        let mut node = &self.nodes[0];
+        for char in prefix.chars() {
+            let child = node
+                .children
+                .binary_search_by(|(other, _)| other.cmp(&char))
+                .ok()?;
+            node = &self.nodes[node.children[child].1];
+        }
+
+        if node.range.is_empty() {
+            None
+        } else {
+            Some(node.range.clone())
+        }
+    }
+}
+
+#[cfg(test)]
+mod prefix_range_tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_range_partial_match() {
+        let list = vec!["apple", "apricot", "banana", "cherry"];
+
+        assert_eq!(prefix_range(&list, "ap", |item| item), Some(0..2));
+        assert_eq!(prefix_range(&list, "b", |item| item), Some(2..3));
+    }
+
+    #[test]
+    fn test_prefix_range_no_match() {
+        let list = vec!["apple", "apricot", "banana", "cherry"];
+
+        assert_eq!(prefix_range(&list, "grape", |item| item), None);
+        assert_eq!(prefix_range(&list, "apples", |item| item), None);
+    }
+
+    #[test]
+    fn test_prefix_range_empty_list() {
+        let list: Vec<&str> = vec![];
+
+        assert_eq!(prefix_range(&list, "apple", |item| item), None);
+    }
+
+    #[test]
+    fn test_prefix_range_key_extractor() {
+        let list = vec![("apple", 1), ("banana", 2), ("banana", 3)];
+
+        assert_eq!(prefix_range(&list, "banana", |item| item.0), Some(1..3));
+    }
+}
+
+#[cfg(test)]
+mod prefix_index_tests {
+    use super::*;
+
+    const NAMES: [&str; 6] = [
+        "lebron james",
+        "kevin durant",
+        "lebron james jr",
+        "kawhi leonard",
+        "kevin love",
+        "luka doncic",
+    ];
+
+    fn assert_backend<'a>(index: &impl PrefixIndex<'a>) {
+        assert_eq!(
+            index.keys(),
+            [
+                "kawhi leonard",
+                "kevin durant",
+                "kevin love",
+                "lebron james",
+                "lebron james jr",
+                "luka doncic"
+            ]
+        );
+        assert_eq!(index.prefix_range("kevin"), Some(1..3));
+        assert_eq!(index.matches("lebron"), ["lebron james", "lebron james jr"]);
+        assert_eq!(index.match_ids("lebron"), [0, 2]);
+        assert_eq!(index.match_ids("luka doncic"), [5]);
+        assert_eq!(index.prefix_range(""), Some(0..6));
+        assert_eq!(index.prefix_range("kevin g"), None);
+        assert_eq!(index.prefix_range("z"), None);
+        assert!(index.matches("luka doncic!").is_empty());
+    }
+
+    #[test]
+    fn test_sorted_prefix_index() {
+        assert_backend(&SortedPrefixIndex::new(NAMES));
+    }
+
+    #[test]
+    fn test_trie_prefix_index() {
+        assert_backend(&TriePrefixIndex::new(NAMES));
+    }
+
+    #[test]
+    fn test_prefix_index_empty() {
+        let sorted = SortedPrefixIndex::new([]);
+        let trie = TriePrefixIndex::new([]);
+
+        assert_eq!(sorted.prefix_range(""), None);
+        assert_eq!(trie.prefix_range(""), None);
+    }
+
+    #[test]
+    fn test_prefix_index_unicode() {
+        let keys = ["zoë", "zoo", "zlatan"];
+        let sorted = SortedPrefixIndex::new(keys);
+        let trie = TriePrefixIndex::new(keys);
+
+        assert_eq!(sorted.matches("zo"), ["zoo", "zoë"]);
+        assert_eq!(trie.matches("zo"), ["zoo", "zoë"]);
+        assert_eq!(trie.match_ids("zoë"), [0]);
+    }
+}